@@ -0,0 +1,97 @@
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::AnnounceConfig;
+use crate::roon::NowPlaying;
+
+/// Speaks track changes through a text-to-speech command, for eyes-free use
+///
+/// An announcement waits out `delay_ms` first, so skipping through several
+/// tracks only announces the one that stuck.
+pub struct Announcer {
+    config: AnnounceConfig,
+    /// Text to speak and when it was queued
+    pending: Option<(String, Instant)>,
+    /// The command speaking the last announcement
+    speaking: Option<Child>,
+}
+
+impl Announcer {
+    pub fn new(config: &AnnounceConfig) -> Self {
+        Self {
+            config: config.clone(),
+            pending: None,
+            speaking: None,
+        }
+    }
+
+    /// Queue an announcement of a new track (replacing one not spoken yet)
+    pub fn track_changed(&mut self, np: &NowPlaying) {
+        if !self.config.enabled || np.track.is_empty() {
+            return;
+        }
+        let text = self
+            .config
+            .format
+            .replace("{track}", &np.track)
+            .replace("{artist}", &np.artist)
+            .replace("{album}", &np.album);
+        self.pending = Some((text, Instant::now()));
+    }
+
+    /// Speak the queued announcement once it's settled
+    pub fn tick(&mut self) {
+        if let Some(child) = &mut self.speaking {
+            if !matches!(child.try_wait(), Ok(None)) {
+                self.speaking = None;
+            }
+        }
+        let delay = Duration::from_millis(self.config.delay_ms);
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|(_, queued)| queued.elapsed() >= delay)
+        {
+            if let Some((text, _)) = self.pending.take() {
+                self.speak(&text);
+            }
+        }
+    }
+
+    /// Pipe text into the TTS command, cutting off the previous announcement
+    fn speak(&mut self, text: &str) {
+        self.stop();
+        let Some((program, args)) = self.config.command.split_first() else {
+            return;
+        };
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match child {
+            Ok(mut child) => {
+                if let Some(mut stdin) = child.stdin.take() {
+                    let _ = writeln!(stdin, "{}", text);
+                }
+                self.speaking = Some(child);
+            }
+            Err(e) => tracing::warn!("Failed to run {}: {}", program, e),
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(mut child) = self.speaking.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for Announcer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
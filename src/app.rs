@@ -1,9 +1,47 @@
-use std::time::Instant;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crossterm::event::KeyEvent;
 use image::DynamicImage;
+use ratatui::layout::Rect;
 use ratatui_image::picker::Picker;
+use ratatui_image::protocol::StatefulProtocol;
 
-use crate::roon::{BrowseItem, PlaybackState, Zone};
+use crate::announce::Announcer;
+use crate::clock;
+use crate::colors::ColorDepth;
+use crate::config::{Config, CoreConfig, EndsAt, KeymapScheme, TimeDisplay};
+use crate::cores::Cores;
+use crate::credentials::{self, Service, Store};
+use crate::external;
+use crate::history::ListeningHistory;
+use crate::icons::{self, Icons};
+use crate::keymap::{Keymap, LeaderMenu};
+use crate::lyrics::{Lyrics, LyricsQuery};
+use crate::media_keys::MediaKeys;
+use crate::metrics::Metrics;
+use crate::plugins::Plugins;
+use crate::resume::{self, ResumeStore};
+use crate::roon::{
+    BrowseItem, BrowsePath, Browser, DspPreset, FocusQuery, MergedCategory, NowPlaying, Origin,
+    Output, PlaybackState, QualityBadge, QueueItem, VolumeType, Zone,
+};
+use crate::snapshot::ZoneSnapshot;
+use crate::theme::Theme;
+use crate::trims::{self, Trims};
+use crate::zones::ZoneRules;
+
+/// Positions this close to the start or end of a long item aren't worth
+/// resuming (seconds)
+const RESUME_MARGIN: f64 = 30.0;
+
+/// Longest gap between zone refreshes counted as listening (longer ones
+/// mean the TUI was suspended or disconnected)
+const MAX_LISTENING_GAP: Duration = Duration::from_secs(5);
+
+/// How long a status bar notice stays up
+const NOTICE_DURATION: Duration = Duration::from_secs(3);
 
 /// Active view
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -12,6 +50,31 @@ pub enum View {
     NowPlaying,
     Browse,
     Search,
+    Queue,
+    Settings,
+    ForYou,
+}
+
+impl View {
+    pub const ALL: [View; 6] = [
+        View::NowPlaying,
+        View::Browse,
+        View::Search,
+        View::Queue,
+        View::Settings,
+        View::ForYou,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            View::NowPlaying => "Now Playing",
+            View::Browse => "Browse",
+            View::Search => "Search",
+            View::Queue => "Queue",
+            View::Settings => "Settings",
+            View::ForYou => "For You",
+        }
+    }
 }
 
 /// Popup overlay state
@@ -19,6 +82,75 @@ pub enum View {
 pub enum Popup {
     Help,
     ZoneSelector,
+    OpenIn,
+    Lyrics,
+    Volume,
+    ThemeSwitcher,
+    Onboarding,
+    ConfirmQuit,
+    Resume,
+    Credentials,
+    Stats,
+    ItemDetail,
+    /// Details of the selected queue item
+    QueueItem,
+    CoreSwitcher,
+    /// Focus filter builder for the library's albums
+    Focus,
+    /// Relative trims of a grouped zone's outputs
+    Trim,
+    /// Passphrase prompt ending party mode
+    PartyUnlock,
+    /// DSP preset picker of the current zone
+    DspPresets,
+    /// The selected zone went away: follow its successor or pick another
+    ZoneGone,
+    /// Devices of the current zone's outputs and what they're fed
+    Outputs,
+}
+
+/// Pages of the help popup
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HelpPage {
+    /// Keys of the active view
+    #[default]
+    View,
+    /// Keys available everywhere
+    Global,
+}
+
+/// What typed keys do in the help popup
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HelpInput {
+    /// Scroll and switch pages
+    #[default]
+    Browse,
+    /// Edit the filter
+    Filter,
+    /// Wait for a key to look up
+    Lookup,
+}
+
+/// Steps of the first-run onboarding flow
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingStep {
+    /// Check that the roon CLI runs and reaches the Core
+    #[default]
+    Connection,
+    /// Pick the zone to show at startup
+    Zone,
+    /// Keybinding cheat card
+    Keys,
+}
+
+/// State of the first-run onboarding popup
+#[derive(Default)]
+pub struct OnboardingState {
+    pub step: OnboardingStep,
+    /// Whether the roon binary could be run
+    pub cli_found: bool,
+    /// Zone picked as the startup default
+    pub default_zone: Option<String>,
 }
 
 /// State for the library browse view
@@ -26,8 +158,13 @@ pub struct BrowseState {
     pub items: Vec<BrowseItem>,
     pub selected_index: usize,
     pub breadcrumbs: Vec<String>,
+    /// Level shown, for the browse cache (None until a root is opened)
+    pub path: Option<BrowsePath>,
     pub loading: bool,
     pub error: Option<String>,
+    /// Index in the CLI's level of each item, when they're sorted here
+    /// (empty in the CLI's order)
+    pub order: Vec<usize>,
 }
 
 impl Default for BrowseState {
@@ -36,8 +173,10 @@ impl Default for BrowseState {
             items: Vec::new(),
             selected_index: 0,
             breadcrumbs: vec!["Library".to_string()],
+            path: None,
             loading: false,
             error: None,
+            order: Vec::new(),
         }
     }
 }
@@ -47,16 +186,226 @@ impl BrowseState {
         self.items.clear();
         self.selected_index = 0;
         self.breadcrumbs = vec!["Library".to_string()];
+        self.path = None;
         self.loading = false;
         self.error = None;
+        self.order.clear();
+    }
+
+    /// Show a level's items in the CLI's order
+    pub fn set_items(&mut self, items: Vec<BrowseItem>) {
+        self.items = items;
+        self.order.clear();
+    }
+
+    /// Index in the CLI's level of the item shown at `index`
+    pub fn level_index(&self, index: usize) -> usize {
+        self.order.get(index).copied().unwrap_or(index)
+    }
+
+    /// Whether the level is an artist's page (Roon starts it with the
+    /// "Play Artist" actions)
+    pub fn is_discography(&self) -> bool {
+        self.items.iter().any(|item| {
+            item.hint.as_deref() == Some("action_list")
+                && item.title.eq_ignore_ascii_case("Play Artist")
+        })
+    }
+
+    /// Put the albums of an artist's page in `sort` order, keeping the
+    /// selected item selected
+    ///
+    /// Entries that aren't albums (the "Play Artist" actions, "Show more")
+    /// stay where Roon puts them.
+    pub fn sort_albums(&mut self, sort: DiscographySort, plays: impl Fn(&BrowseItem) -> u32) {
+        let selected = self.level_index(self.selected_index);
+        let mut items: Vec<(usize, BrowseItem)> = std::mem::take(&mut self.items)
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| (self.level_index(i), item))
+            .collect();
+        items.sort_by_key(|(index, _)| *index);
+
+        let slots: Vec<usize> = (0..items.len())
+            .filter(|&i| items[i].1.hint.as_deref() == Some("list"))
+            .collect();
+        let mut albums = slots.clone();
+        match sort {
+            DiscographySort::Roon => {}
+            DiscographySort::Year => {
+                albums.sort_by_key(|&i| std::cmp::Reverse(items[i].1.release_year()))
+            }
+            DiscographySort::Title => {
+                albums.sort_by_cached_key(|&i| items[i].1.title.to_lowercase())
+            }
+            DiscographySort::MostPlayed => {
+                albums.sort_by_key(|&i| std::cmp::Reverse(plays(&items[i].1)))
+            }
+        }
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        for (slot, album) in slots.into_iter().zip(albums) {
+            order[slot] = album;
+        }
+
+        let mut items: Vec<Option<BrowseItem>> =
+            items.into_iter().map(|(_, item)| Some(item)).collect();
+        self.items = order.iter().filter_map(|&i| items[i].take()).collect();
+        self.selected_index = order.iter().position(|&i| i == selected).unwrap_or(0);
+        self.order = if sort == DiscographySort::Roon {
+            Vec::new()
+        } else {
+            order
+        };
+    }
+}
+
+/// Order of the albums on an artist's page in Browse
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiscographySort {
+    /// As Roon lists them
+    #[default]
+    Roon,
+    /// Newest release first (albums without a known year last)
+    Year,
+    Title,
+    /// Most tracks played first, from the listening history
+    MostPlayed,
+}
+
+impl DiscographySort {
+    pub fn next(self) -> Self {
+        match self {
+            DiscographySort::Roon => DiscographySort::Year,
+            DiscographySort::Year => DiscographySort::Title,
+            DiscographySort::Title => DiscographySort::MostPlayed,
+            DiscographySort::MostPlayed => DiscographySort::Roon,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DiscographySort::Roon => "Roon's order",
+            DiscographySort::Year => "newest first",
+            DiscographySort::Title => "by title",
+            DiscographySort::MostPlayed => "most played first",
+        }
+    }
+}
+
+/// A level of a unified search, merged from the library and the streaming
+/// service sections
+#[derive(Debug, Clone)]
+pub enum Merged {
+    /// Result categories, each listed by one or more sources
+    Categories(Vec<MergedCategory>),
+    /// A category's items from every source
+    Items(MergedCategory, Vec<Origin>),
+}
+
+/// How long the party mode key is held to end party mode
+const PARTY_HOLD: Duration = Duration::from_secs(2);
+
+/// Longest gap between key repeats that still counts as holding the key
+const PARTY_HOLD_GAP: Duration = Duration::from_millis(700);
+
+/// A selected zone that went away: grouped into another zone, ungrouped or
+/// renamed (Roon gives the zone a new id for each)
+pub struct ZoneGone {
+    pub name: String,
+    /// Its outputs' ids, to find the zone that took them over
+    outputs: Vec<String>,
+    /// Id of the zone holding most of its outputs now
+    pub successor: Option<String>,
+    /// Whether the prompt was shown (it waits while another popup is open)
+    prompted: bool,
+}
+
+/// Party mode: quitting, zone switching and queue clean-up are locked
+#[derive(Default)]
+pub struct PartyState {
+    pub on: bool,
+    /// First and latest press of the held party mode key
+    hold: Option<(Instant, Instant)>,
+    /// Passphrase typed in the unlock popup
+    pub input: String,
+    /// Feedback in the unlock popup (wrong passphrase)
+    pub message: Option<String>,
+}
+
+impl PartyState {
+    /// Count a press of the party mode key towards a long press; returns
+    /// whether it has now been held long enough
+    ///
+    /// Terminals repeat a held key, so a run of presses without a long gap
+    /// is one long press.
+    pub fn hold(&mut self) -> bool {
+        let now = Instant::now();
+        let start = match self.hold {
+            Some((start, last)) if now.duration_since(last) < PARTY_HOLD_GAP => start,
+            _ => now,
+        };
+        self.hold = Some((start, now));
+        if now.duration_since(start) >= PARTY_HOLD {
+            self.hold = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// How long fetched mixes are shown before the For You view refetches them
+const FOR_YOU_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// State for the For You view: mixes and recommendations gathered from the
+/// library and the streaming services
+pub struct ForYouState {
+    pub list: BrowseState,
+    /// Where each item is selected in the browse tree
+    pub origins: Vec<Origin>,
+    /// When the list was last fetched (None until the view is first opened)
+    pub fetched_at: Option<Instant>,
+}
+
+impl Default for ForYouState {
+    fn default() -> Self {
+        Self {
+            list: BrowseState {
+                breadcrumbs: vec!["For You".to_string()],
+                ..Default::default()
+            },
+            origins: Vec::new(),
+            fetched_at: None,
+        }
+    }
+}
+
+impl ForYouState {
+    /// Whether the list needs fetching (never fetched, or fetched too long ago)
+    pub fn is_stale(&self) -> bool {
+        self.fetched_at
+            .is_none_or(|fetched| fetched.elapsed() >= FOR_YOU_TTL)
     }
 }
 
+/// A level left for a deeper one, restored on back
+struct SearchFrame {
+    merged: Option<Merged>,
+    items: Vec<BrowseItem>,
+    selected_index: usize,
+    path: Option<BrowsePath>,
+    /// Breadcrumb count while it was shown
+    depth: usize,
+}
+
 /// State for the search view
 pub struct SearchState {
     pub query: String,
     pub input_active: bool,
     pub results: BrowseState,
+    /// The merged level on screen (None on a plain browse level)
+    pub merged: Option<Merged>,
+    /// Levels to return to from merged ones and their items
+    frames: Vec<SearchFrame>,
 }
 
 impl Default for SearchState {
@@ -68,26 +417,319 @@ impl Default for SearchState {
                 breadcrumbs: vec!["Search".to_string()],
                 ..Default::default()
             },
+            merged: None,
+            frames: Vec::new(),
         }
     }
 }
 
 impl SearchState {
     pub fn reset(&mut self) {
-        self.query.clear();
-        self.input_active = true;
-        self.results = BrowseState {
-            breadcrumbs: vec!["Search".to_string()],
-            ..Default::default()
+        *self = Self::default();
+    }
+
+    /// Show a new level reached from a merged one, remembering the current
+    /// level for `back_to_merged`
+    pub fn enter_from_merged(
+        &mut self,
+        merged: Option<Merged>,
+        items: Vec<BrowseItem>,
+        path: Option<BrowsePath>,
+        title: String,
+    ) {
+        let results = &mut self.results;
+        self.frames.push(SearchFrame {
+            merged: self.merged.take(),
+            items: std::mem::replace(&mut results.items, items),
+            selected_index: results.selected_index,
+            path: std::mem::replace(&mut results.path, path),
+            depth: results.breadcrumbs.len(),
+        });
+        results.order.clear();
+        self.merged = merged;
+        results.breadcrumbs.push(title);
+        results.selected_index = 0;
+        results.error = None;
+    }
+
+    /// Go back to the merged level this one was entered from, if it was
+    pub fn back_to_merged(&mut self) -> bool {
+        let results = &mut self.results;
+        if self.frames.last().map(|f| f.depth + 1) != Some(results.breadcrumbs.len()) {
+            return false;
+        }
+        let Some(frame) = self.frames.pop() else {
+            return false;
         };
+        self.merged = frame.merged;
+        results.set_items(frame.items);
+        results.selected_index = frame.selected_index;
+        results.path = frame.path;
+        results.breadcrumbs.pop();
+        results.error = None;
+        true
+    }
+}
+
+/// State for the queue view
+#[derive(Default)]
+pub struct QueueState {
+    pub items: Vec<QueueItem>,
+    pub selected_index: usize,
+    pub error: Option<String>,
+}
+
+/// State for the settings (keybinding editor) view
+#[derive(Default)]
+pub struct SettingsState {
+    pub selected_index: usize,
+    /// Waiting for the new key of the selected binding
+    pub capturing: bool,
+    /// Result of the last edit (conflict, saved, …)
+    pub message: Option<String>,
+}
+
+/// State for the credentials popup
+#[derive(Default)]
+pub struct CredentialsState {
+    pub selected_index: usize,
+    /// Where each of `Service::ALL`'s tokens is stored
+    pub stored: Vec<Option<Store>>,
+    /// Token being typed for the selected service
+    pub input: Option<String>,
+    /// Result of the last save or removal
+    pub message: Option<String>,
+    /// A token changed since the lyrics providers were built
+    pub changed: bool,
+}
+
+impl CredentialsState {
+    pub fn selected(&self) -> Service {
+        Service::ALL[self.selected_index]
     }
 }
 
+/// A filter of the Focus builder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusField {
+    YearFrom,
+    YearTo,
+    Format,
+    Label,
+    Genres,
+}
+
+impl FocusField {
+    pub const ALL: [FocusField; 5] = [
+        FocusField::YearFrom,
+        FocusField::YearTo,
+        FocusField::Format,
+        FocusField::Label,
+        FocusField::Genres,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FocusField::YearFrom => "From year",
+            FocusField::YearTo => "To year",
+            FocusField::Format => "Format",
+            FocusField::Label => "Label",
+            FocusField::Genres => "Genres",
+        }
+    }
+
+    /// What to type, shown while the field is empty
+    pub fn placeholder(self) -> &'static str {
+        match self {
+            FocusField::YearFrom | FocusField::YearTo => "any year",
+            FocusField::Format => "any format (e.g. FLAC, DSD)",
+            FocusField::Label => "any label",
+            FocusField::Genres => "any genre (comma-separated, all must match)",
+        }
+    }
+}
+
+/// State for the Focus filter popup; the filters are kept between openings
+#[derive(Default)]
+pub struct FocusState {
+    pub selected_index: usize,
+    /// Text of each filter, in `FocusField::ALL` order
+    pub values: [String; 5],
+    /// Text being typed for the selected filter
+    pub input: Option<String>,
+    /// Why the filters couldn't be applied
+    pub message: Option<String>,
+}
+
+impl FocusState {
+    /// The filters as a query, or what's wrong with them
+    pub fn query(&self) -> Result<FocusQuery, String> {
+        let text = |field: FocusField| {
+            let value = self.values[field as usize].trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let year = |field: FocusField| {
+            text(field)
+                .map(|value| {
+                    value
+                        .parse::<u16>()
+                        .map_err(|_| format!("{} isn't a year: {}", field.label(), value))
+                })
+                .transpose()
+        };
+        let query = FocusQuery {
+            year_from: year(FocusField::YearFrom)?,
+            year_to: year(FocusField::YearTo)?,
+            format: text(FocusField::Format),
+            label: text(FocusField::Label),
+            genres: text(FocusField::Genres)
+                .map(|genres| {
+                    genres
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|genre| !genre.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+        if let (Some(from), Some(to)) = (query.year_from, query.year_to) {
+            if from > to {
+                return Err(format!("{} is after {}", from, to));
+            }
+        }
+        Ok(query)
+    }
+}
+
+/// How often the screensaver moves one cell
+const SCREENSAVER_DRIFT: Duration = Duration::from_secs(3);
+
+/// Idle screensaver state
+#[derive(Debug, Clone)]
+pub struct Screensaver {
+    since: Instant,
+    /// Drift steps taken so far (positions are derived from this)
+    pub step: u64,
+}
+
+impl Screensaver {
+    pub fn new() -> Self {
+        Self {
+            since: Instant::now(),
+            step: 0,
+        }
+    }
+}
+
+/// A–B repeat: a section of the current track played over and over
+#[derive(Debug, Clone)]
+pub struct AbRepeat {
+    /// Track the points were marked in (`resume::item_key`)
+    track: String,
+    pub a: f64,
+    /// Unset while waiting for the second mark
+    pub b: Option<f64>,
+    /// When playback last jumped back to A; the position reported by the
+    /// next refresh may still be the one before the jump
+    jumped_at: Option<Instant>,
+}
+
+/// Screen areas of clickable/scrollable widgets, recorded while drawing
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HitAreas {
+    /// Now Playing volume display
+    pub volume: Rect,
+    /// Now Playing progress bar
+    pub progress: Rect,
+    /// Now Playing time line above the progress bar
+    pub time: Rect,
+}
+
+/// A decoded cover plus the encoded file it came from
+pub struct Cover {
+    pub url: String,
+    pub image: DynamicImage,
+    /// Original (compressed) image data, kept so the art can be rebuilt after
+    /// its decoded form was released
+    pub encoded: Arc<[u8]>,
+}
+
+/// A Browse or Search item shown in the detail popup
+pub struct ItemDetail {
+    pub item: BrowseItem,
+    /// Entries of a list item
+    pub count: Option<usize>,
+    /// Actions of an action list item ("Play Now", "Queue", ...)
+    pub actions: Vec<String>,
+    pub error: Option<String>,
+    /// Artwork URL, and the artwork once loaded
+    art_url: Option<String>,
+    art_requested: bool,
+    pub art: Option<StatefulProtocol>,
+}
+
+impl ItemDetail {
+    pub fn new(art_url: Option<String>, item: BrowseItem) -> Self {
+        Self {
+            item,
+            count: None,
+            actions: Vec::new(),
+            error: None,
+            art_url,
+            art_requested: false,
+            art: None,
+        }
+    }
+}
+
+/// Something that changed in a zone between two refreshes (carries the zone_id)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZoneEvent {
+    /// A zone joined an already known list (filling the list at startup,
+    /// after a Core switch or over snapshot zones adds none)
+    Added(String),
+    Removed(String),
+    /// A different track started (seek position changes don't count)
+    TrackChanged(String),
+    /// Playback state changed (playing, paused, stopped, loading)
+    StateChanged(String),
+    /// An output's volume or mute changed
+    VolumeChanged(String),
+    /// The selected zone went away (ungrouped, renamed or turned off), or
+    /// another zone took over its outputs since
+    SelectedGone(String),
+}
+
 /// Application state
 pub struct App {
     /// Whether the app should quit
     pub should_quit: bool,
 
+    /// User configuration
+    pub config: Config,
+
+    /// Active keybindings (editable in the Settings view)
+    pub keymap: Keymap,
+
+    /// Compiled `[zones]` rules (hidden zones)
+    pub zone_rules: ZoneRules,
+
+    // ========== Theme ==========
+    /// Active color theme
+    pub theme: Theme,
+    /// Colors the terminal can show, which themes are reduced to
+    color_depth: ColorDepth,
+
+    /// Installed themes (loaded when the theme switcher opens)
+    pub themes: Vec<Theme>,
+
+    /// Highlighted theme in the switcher
+    pub theme_index: usize,
+
+    /// Theme that was active before previewing (restored on cancel)
+    pub theme_before_preview: Option<Theme>,
+
     /// Current active view
     pub view: View,
 
@@ -100,69 +742,421 @@ pub struct App {
     /// Error message if any
     pub error: Option<String>,
 
+    /// Last refresh's warnings about entries that couldn't be parsed
+    parse_warnings: Vec<String>,
+
+    /// Glyphs for playback state, modes and volume
+    pub icons: &'static Icons,
+
+    /// Short-lived status bar message ("Copied …") and when it was shown
+    pub notice: Option<(String, Instant)>,
+
+    /// Newer release tag, if the update check found one
+    pub update_available: Option<String>,
+
+    /// Whether state changed since the last frame (skip identical redraws)
+    pub dirty: bool,
+
+    /// Whether the performance metrics overlay is shown
+    pub show_metrics: bool,
+
+    /// Show Browse/Search beside a compact Now Playing panel (wide terminals)
+    pub split_view: bool,
+
+    /// Show only the 3-row mini-player (e.g. in a small tmux pane)
+    pub mini_player: bool,
+
+    /// Follow the music: switch to whichever zone most recently started playing
+    pub follow: bool,
+
+    /// Album shuffle: queue a random album whenever the queue runs out
+    pub album_shuffle: bool,
+    /// Track the last album was queued after, so it's queued once
+    album_shuffle_after: Option<String>,
+
+    /// Terminal title last set, so it's only written when it changes
+    terminal_title: Option<String>,
+    /// Taskbar progress last reported (OSC 9;4 state, percent)
+    taskbar_progress: Option<(u8, u8)>,
+
+    /// Open which-key menu of the leader scheme
+    pub leader_menu: Option<LeaderMenu>,
+
+    /// Hide the tab bar, status bar and hints, leaving only the view content
+    pub zen_mode: bool,
+
+    /// Album art fills the terminal ("ambient" display), until any key
+    pub fullscreen_art: bool,
+
+    /// Active idle screensaver, until any key
+    pub screensaver: Option<Screensaver>,
+
+    /// Latest visualizer bar levels (`0..=visualizer::MAX_LEVEL`)
+    pub spectrum: Vec<u8>,
+
+    /// Frame and event loop timings
+    pub metrics: Metrics,
+
     // ========== Zones ==========
     /// All available zones
     pub zones: Vec<Zone>,
 
-    /// Index of currently selected zone
-    pub selected_zone_index: usize,
+    /// Id of the selected zone (None until zones arrive); a zone that goes
+    /// away stays selected, showing nothing, until another one is picked
+    pub selected_zone: Option<String>,
+    /// The selected zone went away and no other has been picked yet
+    pub zone_gone: Option<ZoneGone>,
 
     /// Zone selector index (when popup is open)
     pub zone_selector_index: usize,
 
+    /// "Open in…" service index (when popup is open)
+    pub open_in_index: usize,
+
+    // ========== Volume ==========
+    /// Output index in the volume popup
+    pub volume_output_index: usize,
+
+    /// Whether volume changes apply to all outputs of a grouped zone
+    /// (starts from config, can be overridden in the volume popup)
+    pub volume_linked: bool,
+
     // ========== Album Art ==========
-    /// Current album art image (decoded)
-    pub album_art: Option<DynamicImage>,
+    /// Current album art, ready to render (the protocol keeps its encoded
+    /// image between frames and only re-encodes when the area changes)
+    pub album_art: Option<StatefulProtocol>,
 
     /// Current album art URL (to avoid re-fetching)
     pub album_art_url: Option<String>,
 
-    /// Image picker for protocol detection
+    /// Compressed data of the current album art; it stays while the decoded
+    /// protocol is released after idling outside Now Playing
+    pub album_art_encoded: Option<Arc<[u8]>>,
+
+    /// Upcoming track's cover, decoded ahead of the track change
+    pub prefetched_art: Option<Cover>,
+
+    /// URL of the upcoming cover being (or already) prefetched
+    pub prefetch_art_url: Option<String>,
+
+    /// Image picker for protocol detection (queried lazily, when the first
+    /// cover arrives, since slow terminals can stall the query)
     pub image_picker: Option<Picker>,
 
+    /// Cover waiting for the image picker
+    pub pending_art: Option<Cover>,
+
+    // ========== Lyrics ==========
+    /// Lyrics for the current track (None while loading or if not found)
+    pub lyrics: Option<Lyrics>,
+
+    /// Track key the lyrics (or the in-flight fetch) belong to
+    pub lyrics_key: Option<String>,
+
+    /// Whether a lyrics fetch is in flight
+    pub lyrics_loading: bool,
+
+    /// Scroll offset of the lyrics popup
+    pub lyrics_scroll: u16,
+
+    /// Help popup page and scroll offset
+    pub help_page: HelpPage,
+    pub help_scroll: u16,
+    pub help_input: HelpInput,
+    /// Bindings filter (matches action names and descriptions)
+    pub help_filter: String,
+    /// Result of the last "press a key" lookup
+    pub help_lookup: Option<String>,
+
+    // ========== Mouse ==========
+    /// Where widgets were drawn in the last frame, for mouse hit testing
+    pub hit_areas: HitAreas,
+
+    /// Target position (seconds) while dragging on the progress bar
+    pub seek_drag: Option<f64>,
+
+    /// Show the time left instead of the elapsed time (click the time line
+    /// to toggle)
+    pub show_remaining: bool,
+
+    /// A–B repeat points, while marking or looping
+    pub ab_repeat: Option<AbRepeat>,
+
     // ========== Time Tracking ==========
     /// When zones were last refreshed (for interpolating progress)
     pub last_refresh: Instant,
 
     // ========== Browse & Search ==========
     pub browse: BrowseState,
+    /// Order of the albums on artist pages in Browse
+    pub discography_sort: DiscographySort,
     pub search: SearchState,
+    pub for_you: ForYouState,
+    pub browser: Browser,
+
+    // ========== Queue ==========
+    pub queue: QueueState,
+
+    // ========== Settings ==========
+    pub settings: SettingsState,
+    pub credentials: CredentialsState,
+    pub focus: FocusState,
+
+    /// Running plugins and what they registered
+    pub plugins: Plugins,
+
+    // ========== Onboarding ==========
+    pub onboarding: OnboardingState,
+
+    /// Item shown in the detail popup
+    pub item_detail: Option<ItemDetail>,
+
+    /// Saved positions of long items
+    pub resume: ResumeStore,
+    /// Key of the long item currently being tracked
+    resume_item: Option<String>,
+    /// Saved position offered in the resume popup
+    pub resume_offer: Option<f64>,
+
+    /// The Core in use and the zone last selected on each
+    pub cores: Cores,
+    /// Core switcher index (when popup is open)
+    pub core_index: usize,
+
+    /// Zones are the snapshot saved at the last quit, not refreshed yet
+    pub stale: bool,
+
+    /// When each zone was last seen playing
+    last_playing: HashMap<String, Instant>,
+
+    /// Party mode (destructive keys locked)
+    pub party: PartyState,
+
+    /// DSP presets of the current zone (fetched when the picker opens)
+    pub dsp_presets: Vec<DspPreset>,
+    /// DSP preset picker index (when popup is open)
+    pub dsp_index: usize,
+    /// Why the presets couldn't be fetched
+    pub dsp_error: Option<String>,
+
+    /// Preferred trims of grouped outputs
+    pub trims: Trims,
+    /// Trim popup output index (when popup is open)
+    pub trim_index: usize,
+    /// Groups seen at the last zone refresh (None before the first one)
+    known_groups: Option<HashSet<String>>,
+
+    /// Weekly listening totals
+    pub history: ListeningHistory,
+    /// When listening time was last counted
+    listening_at: Instant,
+    /// Year shown in the stats heatmap
+    pub stats_year: i64,
+
+    /// Spoken track changes
+    pub announcer: Announcer,
+
+    /// System Now Playing widget and media keys (macOS)
+    pub media_keys: MediaKeys,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn new(config: Config) -> Self {
+        let volume_linked = config.volume.link_grouped;
+        let follow = config.follow_music;
+        let album_shuffle = config.album_shuffle;
+        let show_remaining = config.time_display == TimeDisplay::Remaining;
+        let zone_rules = ZoneRules::new(&config.zones);
+        let mut keymap = Keymap::new(&config.keys);
+        if config.keymap == KeymapScheme::Leader {
+            keymap.set_leader(&config.leader);
+        }
+        let color_depth = ColorDepth::resolve(config.colors);
+        let theme = Theme::named(&config.theme).fit(color_depth);
+        let announcer = Announcer::new(&config.announce);
+        let icons = icons::resolve(config.icons);
         Self {
             should_quit: false,
+            config,
+            keymap,
+            zone_rules,
+            theme,
+            color_depth,
+            themes: Vec::new(),
+            theme_index: 0,
+            theme_before_preview: None,
             view: View::default(),
             popup: None,
             connected: false,
             error: None,
+            parse_warnings: Vec::new(),
+            icons,
+            notice: None,
+            update_available: None,
+            dirty: true,
+            show_metrics: false,
+            split_view: false,
+            mini_player: false,
+            follow,
+            album_shuffle,
+            album_shuffle_after: None,
+            terminal_title: None,
+            taskbar_progress: None,
+            leader_menu: None,
+            zen_mode: false,
+            fullscreen_art: false,
+            screensaver: None,
+            spectrum: Vec::new(),
+            metrics: Metrics::default(),
             zones: Vec::new(),
-            selected_zone_index: 0,
+            selected_zone: None,
+            zone_gone: None,
             zone_selector_index: 0,
+            open_in_index: 0,
+            volume_output_index: 0,
+            volume_linked,
             album_art: None,
             album_art_url: None,
-            image_picker: Picker::from_query_stdio().ok(),
+            album_art_encoded: None,
+            prefetched_art: None,
+            prefetch_art_url: None,
+            image_picker: None,
+            pending_art: None,
+            lyrics: None,
+            lyrics_key: None,
+            lyrics_loading: false,
+            lyrics_scroll: 0,
+            help_page: HelpPage::View,
+            help_scroll: 0,
+            help_input: HelpInput::Browse,
+            help_filter: String::new(),
+            help_lookup: None,
+            hit_areas: HitAreas::default(),
+            seek_drag: None,
+            show_remaining,
+            ab_repeat: None,
             last_refresh: Instant::now(),
             browse: BrowseState::default(),
+            discography_sort: DiscographySort::default(),
             search: SearchState::default(),
+            for_you: ForYouState::default(),
+            browser: Browser::default(),
+            queue: QueueState::default(),
+            settings: SettingsState::default(),
+            credentials: CredentialsState::default(),
+            focus: FocusState::default(),
+            plugins: Plugins::default(),
+            onboarding: OnboardingState::default(),
+            item_detail: None,
+            resume: ResumeStore::load(),
+            resume_item: None,
+            resume_offer: None,
+            cores: Cores::load(),
+            core_index: 0,
+            party: PartyState::default(),
+            stale: false,
+            last_playing: HashMap::new(),
+            dsp_presets: Vec::new(),
+            dsp_index: 0,
+            dsp_error: None,
+            trims: Trims::load(),
+            trim_index: 0,
+            known_groups: None,
+            history: ListeningHistory::load(),
+            listening_at: Instant::now(),
+            stats_year: clock::civil_date(clock::local_day()).0,
+            announcer,
+            media_keys: MediaKeys::default(),
+        }
+    }
+
+    /// Show a message in the status bar for a few seconds
+    pub fn show_notice(&mut self, text: impl Into<String>) {
+        self.notice = Some((text.into(), Instant::now()));
+    }
+
+    /// Drop the notice once it's been up long enough
+    pub fn expire_notice(&mut self) {
+        if self
+            .notice
+            .as_ref()
+            .is_some_and(|(_, shown)| shown.elapsed() >= NOTICE_DURATION)
+        {
+            self.notice = None;
+            self.dirty = true;
         }
     }
 
+    /// "Artist — Track (Album)" of the current track, for the clipboard
+    pub fn track_info_text(&self) -> Option<String> {
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        let mut text = format!("{} — {}", np.artist, np.track);
+        if !np.album.is_empty() {
+            text.push_str(&format!(" ({})", np.album));
+        }
+        Some(text)
+    }
+
     /// Mark that zones were just refreshed
     pub fn mark_refreshed(&mut self) {
         self.last_refresh = Instant::now();
     }
 
+    /// Seconds from now until each queue item starts, and the total time
+    /// left in the queue (the first item is the track playing now)
+    pub fn queue_timeline(&self) -> (Vec<f64>, f64) {
+        let mut offset = -self.interpolated_seek();
+        let starts = self
+            .queue
+            .items
+            .iter()
+            .map(|item| {
+                let start = offset;
+                offset += item.length;
+                start
+            })
+            .collect();
+        (starts, offset.max(0.0))
+    }
+
+    /// Seconds left in the track, once within the countdown to the next one
+    pub fn countdown(&self) -> Option<f64> {
+        let seconds = f64::from(self.config.countdown_seconds);
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        if seconds == 0.0 || !self.is_playing() || self.is_live() || np.length <= 0.0 {
+            return None;
+        }
+        let left = np.length - self.interpolated_seek();
+        (left > 0.0 && left <= seconds).then_some(left)
+    }
+
+    /// The track after the one playing, if the queue was fetched for it
+    pub fn next_queue_item(&self) -> Option<&QueueItem> {
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        let mut items = self.queue.items.iter();
+        if items.next()?.track != np.track {
+            return None;
+        }
+        items.next()
+    }
+
     /// Get interpolated seek position (for smooth progress bar)
     fn interpolated_seek(&self) -> f64 {
+        if let Some(target) = self.seek_drag {
+            return target;
+        }
         if let Some(zone) = self.current_zone() {
             if let Some(np) = &zone.now_playing {
                 let base_position = np.seek_position;
-                // Only interpolate if playing
-                if zone.state == "playing" {
+                // Only interpolate if playing (a stale snapshot's position
+                // is long gone)
+                if self.is_playing() && !self.stale {
                     let elapsed = self.last_refresh.elapsed().as_secs_f64();
-                    return (base_position + elapsed).min(np.length);
+                    let position = base_position + elapsed;
+                    if np.length > 0.0 {
+                        return position.min(np.length);
+                    }
+                    return position;
                 }
                 return base_position;
             }
@@ -170,39 +1164,337 @@ impl App {
         0.0
     }
 
+    /// Wall-clock end of the playing track (and queue), e.g. "Ends 14:32 · queue 16:05"
+    pub fn ends_at_display(&self) -> Option<String> {
+        if self.config.ends_at == EndsAt::Off || !self.is_playing() {
+            return None;
+        }
+        let zone = self.current_zone()?;
+        let np = zone.now_playing.as_ref()?;
+        if self.is_live() {
+            return None;
+        }
+        let position = self.interpolated_seek();
+        let track_left = (np.length - position).max(0.0);
+        let mut text = format!(
+            "Ends {}",
+            clock::time_in(Duration::from_secs_f64(track_left))
+        );
+
+        if self.config.ends_at == EndsAt::Queue && zone.queue_items_remaining > 1 {
+            // The queue total is as of the last refresh and includes this track
+            let played = (position - np.seek_position).max(0.0);
+            let queue_left = (f64::from(zone.queue_time_remaining) - played).max(track_left);
+            text.push_str(&format!(
+                " · queue {}",
+                clock::time_in(Duration::from_secs_f64(queue_left))
+            ));
+        }
+        Some(text)
+    }
+
+    /// Advance the screensaver's drift, redrawing only when it moves
+    pub fn tick_screensaver(&mut self) {
+        if let Some(screensaver) = &mut self.screensaver {
+            let step = screensaver.since.elapsed().as_secs() / SCREENSAVER_DRIFT.as_secs();
+            if step != screensaver.step {
+                screensaver.step = step;
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// Whether the visualizer shows in Now Playing: enabled, playing, and the
+    /// current zone is one of the configured local zones
+    pub fn visualizer_visible(&self) -> bool {
+        let config = &self.config.visualizer;
+        config.enabled
+            && self.view == View::NowPlaying
+            && !self.mini_player
+            && self.screensaver.is_none()
+            && !self.fullscreen_art
+            && self.is_playing()
+            && self.current_zone().is_some_and(|zone| {
+                config.zones.is_empty() || config.zones.contains(&zone.display_name)
+            })
+    }
+
+    /// Whether the current zone is playing (the progress bar is moving)
+    pub fn is_playing(&self) -> bool {
+        self.current_zone().map(Zone::is_playing).unwrap_or(false)
+    }
+
+    /// Whether the selected zone is buffering the next track
+    pub fn is_loading(&self) -> bool {
+        self.playback_state() == PlaybackState::Loading
+    }
+
+    /// Whether any zone is playing (not just the selected one)
+    pub fn any_playing(&self) -> bool {
+        self.zones.iter().any(Zone::is_playing)
+    }
+
+    /// Quit, or ask first when configured to and music is playing
+    pub fn request_quit(&mut self) {
+        if self.config.confirm_quit && self.any_playing() {
+            self.show_popup(Popup::ConfirmQuit);
+        } else {
+            self.should_quit = true;
+        }
+    }
+
     /// Get the currently selected zone
     pub fn current_zone(&self) -> Option<&Zone> {
-        self.zones.get(self.selected_zone_index)
+        let id = self.selected_zone.as_deref()?;
+        self.zones.iter().find(|z| z.zone_id == id)
+    }
+
+    fn current_zone_mut(&mut self) -> Option<&mut Zone> {
+        let id = self.selected_zone.as_deref()?;
+        self.zones.iter_mut().find(|z| z.zone_id == id)
+    }
+
+    /// Position of the selected zone in the zone list
+    pub fn selected_zone_position(&self) -> Option<usize> {
+        let id = self.selected_zone.as_deref()?;
+        self.zones.iter().position(|z| z.zone_id == id)
+    }
+
+    /// Select a zone by id, dropping the album art of the previous one
+    fn set_selected_zone(&mut self, id: String) {
+        self.selected_zone = Some(id);
+        self.zone_gone = None;
+        if self.popup == Some(Popup::ZoneGone) {
+            self.close_popup();
+        }
+        self.clear_album_art();
     }
 
     /// Get the current zone name for display
     pub fn current_zone_name(&self) -> &str {
         self.current_zone()
-            .map(|z| z.display_name.as_str())
+            .map(|z| self.zone_label(z))
             .unwrap_or("No Zone")
     }
 
-    /// Get the display name of the first output in the current zone
-    pub fn first_output_name(&self) -> Option<String> {
-        self.current_zone()
-            .and_then(|z| z.outputs.first())
-            .map(|o| o.display_name.clone())
+    /// Name to show for a zone (its configured alias, or the Roon name)
+    pub fn zone_label<'a>(&'a self, zone: &'a Zone) -> &'a str {
+        self.zone_rules.label(&zone.display_name)
+    }
+
+    /// Move the highlighted zone in the selector one place up or down,
+    /// returning the new zone order (Roon names) to persist
+    pub fn move_selector_zone(&mut self, up: bool) -> Option<Vec<String>> {
+        let from = self.zone_selector_index;
+        let to = if up {
+            from.checked_sub(1)?
+        } else {
+            Some(from + 1).filter(|&to| to < self.zones.len())?
+        };
+        self.zones.swap(from, to);
+        self.zone_selector_index = to;
+
+        let order: Vec<String> = self.zones.iter().map(|z| z.display_name.clone()).collect();
+        self.zone_rules.set_order(order.clone());
+        Some(order)
+    }
+
+    /// Get current playback state
+    pub fn playback_state(&self) -> PlaybackState {
+        self.current_zone().map(|z| z.state).unwrap_or_default()
+    }
+
+    /// Get the (output name, step) pairs a volume change of `step` percent applies to
+    ///
+    /// When linked, every output is adjusted by the same fraction of its own
+    /// range (Roon's group volume semantics). Otherwise the highlighted output
+    /// in the volume popup, or the first output elsewhere, is adjusted.
+    /// Fixed-volume outputs are skipped.
+    pub fn volume_steps(&self, step: f64) -> Vec<(String, f64)> {
+        let Some(zone) = self.current_zone() else {
+            return Vec::new();
+        };
+        let outputs: Vec<&Output> = if self.volume_linked {
+            zone.outputs.iter().collect()
+        } else if self.popup == Some(Popup::Volume) {
+            zone.outputs
+                .get(self.volume_output_index)
+                .into_iter()
+                .collect()
+        } else {
+            zone.outputs.first().into_iter().collect()
+        };
+
+        outputs
+            .into_iter()
+            .filter_map(|output| {
+                let vol = output.volume.as_ref()?;
+                Some((output.display_name.clone(), vol.relative_change(step)))
+            })
+            .collect()
+    }
+
+    /// Relative change that nudges the trim popup's selected output by
+    /// `step` percent of its range: (output, change), or an error message
+    /// when that output can't be trimmed
+    pub fn trim_step(&self, step: f64) -> Result<(String, f64), String> {
+        let outputs = self
+            .current_zone()
+            .map(|z| z.outputs.as_slice())
+            .unwrap_or_default();
+        let Some(output) = outputs.get(self.trim_index) else {
+            return Err("No output selected".to_string());
+        };
+        if trims::reference(outputs) == Some(self.trim_index) {
+            return Err(format!(
+                "{} is the reference the others are trimmed against",
+                output.display_name
+            ));
+        }
+        match &output.volume {
+            Some(volume) if trims::offsets(outputs)[self.trim_index].is_some() => {
+                Ok((output.display_name.clone(), volume.relative_change(step)))
+            }
+            _ => Err(format!("{} has no adjustable level", output.display_name)),
+        }
+    }
+
+    /// Save the current zone's trims, if it's a group
+    pub fn remember_trims(&mut self) {
+        let zone = self.selected_zone_position().map(|i| &self.zones[i]);
+        if let Some(zone) = zone {
+            if zone.outputs.len() > 1 {
+                self.trims.remember(&zone.outputs);
+            }
+        }
+    }
+
+    /// Groups formed since the last refresh that have saved trims, with the
+    /// volume changes that restore them
+    ///
+    /// Groups already there at startup are left as they are.
+    pub fn regrouped_trims(&mut self) -> Vec<(String, Vec<(String, f64)>)> {
+        let groups: Vec<&Zone> = self.zones.iter().filter(|z| z.outputs.len() > 1).collect();
+        let keys: HashSet<String> = groups
+            .iter()
+            .map(|z| trims::group_key(&z.outputs))
+            .collect();
+        let restore = match &self.known_groups {
+            Some(known) => groups
+                .iter()
+                .filter(|zone| !known.contains(&trims::group_key(&zone.outputs)))
+                .map(|zone| (zone.display_name.clone(), self.trims.reapply(&zone.outputs)))
+                .filter(|(_, changes)| !changes.is_empty())
+                .collect(),
+            None => Vec::new(),
+        };
+        self.known_groups = Some(keys);
+        restore
+    }
+
+    /// Show the zones saved at the last quit until the first refresh, if
+    /// they're from the Core in use; returns whether they were
+    pub fn restore_snapshot(&mut self, snapshot: ZoneSnapshot) -> bool {
+        let core = self.current_core().map(|core| core.name.clone());
+        if snapshot.core != core || snapshot.zones.is_empty() {
+            return false;
+        }
+        self.zones = snapshot.zones;
+        self.selected_zone = snapshot
+            .selected
+            .filter(|id| self.zones.iter().any(|z| &z.zone_id == id))
+            .or_else(|| self.zones.first().map(|z| z.zone_id.clone()));
+        self.stale = true;
+        self.dirty = true;
+        true
+    }
+
+    /// The zones as they are now, to show at the next startup
+    pub fn snapshot(&self) -> ZoneSnapshot {
+        ZoneSnapshot {
+            core: self.current_core().map(|core| core.name.clone()),
+            selected: self.current_zone().map(|z| z.zone_id.clone()),
+            zones: self.zones.clone(),
+        }
+    }
+
+    /// Remember which zones are playing, to tell when a zone has been idle
+    pub fn note_playing(&mut self) {
+        let now = Instant::now();
+        for zone in self.zones.iter().filter(|z| z.is_playing()) {
+            self.last_playing.insert(zone.zone_id.clone(), now);
+        }
+    }
+
+    /// Volume changes that bring the current zone to its configured start
+    /// level, when it's been idle (not playing for `idle_minutes`, or not
+    /// seen playing since startup): (output, relative change)
+    pub fn start_volume_changes(&self) -> Vec<(String, f64)> {
+        let Some(zone) = self.current_zone() else {
+            return Vec::new();
+        };
+        let Some(&level) = self.config.volume.start_levels.get(&zone.display_name) else {
+            return Vec::new();
+        };
+        let idle = Duration::from_secs(self.config.volume.idle_minutes * 60);
+        let idle = !zone.is_playing()
+            && self
+                .last_playing
+                .get(&zone.zone_id)
+                .is_none_or(|at| at.elapsed() >= idle);
+        if !idle {
+            return Vec::new();
+        }
+        zone.outputs
+            .iter()
+            .filter_map(|output| {
+                let volume = output.volume.as_ref()?;
+                // Incremental outputs have no level to set, and a range with
+                // min above max (a defaulted min) has none to clamp into
+                if volume.kind == VolumeType::Incremental || volume.min > volume.max {
+                    return None;
+                }
+                let delta = level.clamp(volume.min, volume.max) - volume.value;
+                (delta != 0.0).then(|| (output.display_name.clone(), delta))
+            })
+            .collect()
+    }
+
+    /// Get the number of tracks left in the current zone's queue
+    pub fn queue_items_remaining(&self) -> Option<u32> {
+        self.current_zone().map(|z| z.queue_items_remaining)
+    }
+
+    /// Set queue items, keeping the selection in range
+    pub fn set_queue(&mut self, items: Vec<QueueItem>) {
+        if self.queue.items == items && self.queue.error.is_none() {
+            return;
+        }
+        self.dirty = true;
+        self.queue.selected_index = self.queue.selected_index.min(items.len().saturating_sub(1));
+        self.queue.items = items;
+        self.queue.error = None;
     }
 
-    /// Get current playback state
-    pub fn playback_state(&self) -> PlaybackState {
-        self.current_zone()
-            .map(|z| PlaybackState::from(z.state.as_str()))
-            .unwrap_or_default()
+    /// Queue items repeating an earlier one (same title and artist, ignoring
+    /// case), in queue order; the first of each stays
+    pub fn queue_duplicates(&self) -> Vec<u64> {
+        let mut seen = HashSet::new();
+        self.queue
+            .items
+            .iter()
+            .filter(|item| !seen.insert((item.track.to_lowercase(), item.artist.to_lowercase())))
+            .map(|item| item.queue_item_id)
+            .collect()
     }
 
     /// Get playback state icon
     pub fn playback_icon(&self) -> &'static str {
         match self.playback_state() {
-            PlaybackState::Playing => "▶",
-            PlaybackState::Paused => "⏸",
-            PlaybackState::Stopped => "⏹",
-            PlaybackState::Loading => "⏳",
+            PlaybackState::Playing => self.icons.playing,
+            PlaybackState::Paused => self.icons.paused,
+            PlaybackState::Stopped => self.icons.stopped,
+            PlaybackState::Loading => self.icons.loading,
         }
     }
 
@@ -213,9 +1505,9 @@ impl App {
             .map(|z| z.settings.shuffle)
             .unwrap_or(false);
         if shuffle {
-            "🔀"
+            self.icons.shuffle
         } else {
-            "  "
+            self.icons.blank
         }
     }
 
@@ -226,9 +1518,9 @@ impl App {
             .map(|z| z.settings.loop_mode.as_str())
             .unwrap_or("disabled");
         match loop_mode {
-            "loop" => "🔁",
-            "loop_one" => "🔂",
-            _ => "  ",
+            "loop" => self.icons.loop_all,
+            "loop_one" => self.icons.loop_one,
+            _ => self.icons.blank,
         }
     }
 
@@ -239,26 +1531,76 @@ impl App {
             .map(|z| z.settings.auto_radio)
             .unwrap_or(false);
         if radio {
-            "📻"
+            self.icons.radio
         } else {
-            "  "
+            self.icons.blank
+        }
+    }
+
+    /// Get the volume leveling line ("Leveling: album -3.2 dB"), if the zone
+    /// reports a mode and leveling is on
+    pub fn leveling_display(&self) -> Option<String> {
+        let zone = self.current_zone()?;
+        let mode = zone.settings.volume_leveling.as_deref()?;
+        if mode == "off" {
+            return None;
+        }
+        let gain = zone.now_playing.as_ref().and_then(|np| np.leveling_gain);
+        Some(match gain {
+            Some(gain) => format!("Leveling: {} {:+.1} dB", mode, gain),
+            None => format!("Leveling: {}", mode),
+        })
+    }
+
+    /// Error shown instead of the active browse-like view's list, if any
+    pub fn list_error(&self) -> Option<&str> {
+        match self.view {
+            View::Browse => self.browse.error.as_deref(),
+            View::Search => self.search.results.error.as_deref(),
+            View::ForYou => self.for_you.list.error.as_deref(),
+            _ => None,
         }
     }
 
+    /// Get the DSP preset line ("DSP: Headphone EQ"), if the zone reports one
+    pub fn dsp_display(&self) -> Option<String> {
+        let preset = self.current_zone()?.settings.dsp_preset.as_deref()?;
+        Some(format!("DSP: {}", preset))
+    }
+
     /// Get volume display string
     pub fn volume_display(&self) -> String {
         if let Some(zone) = self.current_zone() {
             if let Some(output) = zone.outputs.first() {
-                if let Some(vol) = &output.volume {
-                    if vol.is_muted {
-                        return "🔇 Muted".to_string();
-                    } else {
-                        return format!("🔊 {:.0}%", vol.value);
-                    }
-                }
+                return match &output.volume {
+                    Some(vol) if vol.is_muted => format!("{} Muted", self.icons.muted),
+                    Some(vol) => format!("{} {}", self.icons.volume, vol.level_display()),
+                    None => format!("{} Fixed", self.icons.volume),
+                };
             }
         }
-        "🔊 --".to_string()
+        format!("{} --", self.icons.volume)
+    }
+
+    /// Whether the current track is a live stream (internet radio)
+    pub fn is_live(&self) -> bool {
+        self.current_zone().is_some_and(|zone| {
+            zone.now_playing
+                .as_ref()
+                .is_some_and(|np| np.is_live(zone.is_playing()))
+        })
+    }
+
+    /// Quality badge of what's playing: from the source format, or what the
+    /// zone's first output is fed when the CLI doesn't report the source
+    pub fn quality_badge(&self) -> Option<QualityBadge> {
+        let zone = self.current_zone()?;
+        let np = zone.now_playing.as_ref()?;
+        let fed = zone
+            .outputs
+            .first()
+            .and_then(|output| output.device.as_ref()?.format.as_ref());
+        np.quality.as_ref().or(fed)?.badge()
     }
 
     /// Get progress display (current position / duration) with interpolation
@@ -266,16 +1608,135 @@ impl App {
         if let Some(zone) = self.current_zone() {
             if let Some(np) = &zone.now_playing {
                 let current = self.interpolated_seek();
-                return format!(
-                    "{} / {}",
-                    format_duration(current),
-                    format_duration(np.length)
-                );
+                // The position is still the previous track's until it starts
+                if self.is_loading() && self.seek_drag.is_none() {
+                    return format!("{} Loading…", self.icons.loading);
+                }
+                if self.is_live() {
+                    // Elapsed listening time; there's no length to show
+                    if current > 0.0 {
+                        return format!("● LIVE  {}", format_duration(current));
+                    }
+                    return "● LIVE".to_string();
+                }
+                let position = match self.seek_drag {
+                    Some(_) => format!("Seek to {}", format_duration(current)),
+                    None if self.show_remaining => {
+                        format!("−{}", format_duration(np.length - current))
+                    }
+                    None => format_duration(current),
+                };
+                let ab = match &self.ab_repeat {
+                    Some(AbRepeat { a, b: Some(b), .. }) => {
+                        format!("  ⟲ {}–{}", format_duration(*a), format_duration(*b))
+                    }
+                    Some(AbRepeat { a, b: None, .. }) => format!("  ⟲ {}–", format_duration(*a)),
+                    None => String::new(),
+                };
+                return format!("{} / {}{}", position, format_duration(np.length), ab);
             }
         }
         "00:00 / 00:00".to_string()
     }
 
+    /// Start or move a progress bar drag to the track position under `column`
+    pub fn drag_seek(&mut self, column: u16) {
+        let area = self.hit_areas.progress;
+        let Some(length) = self
+            .current_zone()
+            .and_then(|z| z.now_playing.as_ref())
+            .map(|np| np.length)
+            .filter(|&length| length > 0.0 && area.width > 0)
+        else {
+            return;
+        };
+        let offset = column.saturating_sub(area.x).min(area.width);
+        let ratio = f64::from(offset) / f64::from(area.width);
+        self.seek_drag = Some(ratio * length);
+    }
+
+    /// End a progress bar drag, returning the position to seek to
+    ///
+    /// The position is applied locally right away so the bar doesn't jump back
+    /// until the next refresh.
+    pub fn finish_seek_drag(&mut self) -> Option<f64> {
+        let target = self.seek_drag.take()?;
+        let zone = self.current_zone_mut()?;
+        zone.now_playing.as_mut()?.seek_position = target;
+        self.mark_refreshed();
+        Some(target)
+    }
+
+    /// Mark A, then B (starting the loop), then clear; returns a notice
+    pub fn cycle_ab_repeat(&mut self) -> String {
+        let Some(np) = self.current_zone().and_then(|z| z.now_playing.as_ref()) else {
+            return "Nothing playing".to_string();
+        };
+        if self.is_live() {
+            return "Can't repeat a section of a live stream".to_string();
+        }
+        let track = resume::item_key(np);
+        let position = self.interpolated_seek();
+        match &mut self.ab_repeat {
+            Some(ab) if ab.track == track && ab.b.is_none() => {
+                if position < ab.a + 2.0 {
+                    return "B has to be at least 2 seconds after A".to_string();
+                }
+                ab.b = Some(position);
+                format!(
+                    "Repeating {}–{}",
+                    format_duration(ab.a),
+                    format_duration(position)
+                )
+            }
+            Some(ab) if ab.track == track => {
+                self.ab_repeat = None;
+                "A–B repeat off".to_string()
+            }
+            _ => {
+                self.ab_repeat = Some(AbRepeat {
+                    track,
+                    a: position,
+                    b: None,
+                    jumped_at: None,
+                });
+                format!(
+                    "A at {}, mark B to start repeating",
+                    format_duration(position)
+                )
+            }
+        }
+    }
+
+    /// Where to jump back to, when playback of the selected zone crossed B
+    ///
+    /// The points are dropped once another track plays.
+    pub fn ab_repeat_jump(&mut self) -> Option<f64> {
+        let ab = self.ab_repeat.as_ref()?;
+        let np = self.current_zone().and_then(|z| z.now_playing.as_ref());
+        if np.is_none_or(|np| resume::item_key(np) != ab.track) {
+            self.ab_repeat = None;
+            return None;
+        }
+        let b = ab.b?;
+        let settling = ab
+            .jumped_at
+            .is_some_and(|at| at.elapsed() < Duration::from_secs(2));
+        if !self.is_playing() || settling || self.interpolated_seek() < b {
+            return None;
+        }
+        let a = ab.a;
+        if let Some(ab) = &mut self.ab_repeat {
+            ab.jumped_at = Some(Instant::now());
+        }
+        // Interpolate from A straight away instead of waiting for a refresh
+        if let Some(np) = self.current_zone_mut().and_then(|z| z.now_playing.as_mut()) {
+            np.seek_position = a;
+        }
+        self.mark_refreshed();
+        Some(a)
+    }
+
     /// Get progress ratio (0.0 to 1.0) with interpolation
     pub fn progress_ratio(&self) -> f64 {
         if let Some(zone) = self.current_zone() {
@@ -299,6 +1760,54 @@ impl App {
         ("No track playing", "", "")
     }
 
+    /// Get the composer of the current track (classical metadata)
+    pub fn composer(&self) -> Option<&str> {
+        self.current_zone()?
+            .now_playing
+            .as_ref()?
+            .composer
+            .as_deref()
+    }
+
+    /// Get title and subtitle lines for Now Playing
+    ///
+    /// For classical tracks the work becomes the title and the movement the
+    /// subtitle, since a bare "II. Andante" says little on its own.
+    pub fn title_lines(&self) -> (&str, Option<&str>) {
+        if let Some(np) = self.current_zone().and_then(|z| z.now_playing.as_ref()) {
+            return match (&np.work, &np.movement) {
+                (Some(work), Some(movement)) => (work, Some(movement)),
+                (Some(work), None) if *work != np.track => (work, Some(&np.track)),
+                (_, Some(movement)) if *movement != np.track => (&np.track, Some(movement)),
+                _ => {
+                    let chapter = np.chapter_at(self.interpolated_seek());
+                    (&np.track, chapter.map(|c| c.title.as_str()))
+                }
+            };
+        }
+        (self.track_info().0, None)
+    }
+
+    /// Get release info line for the current album ("1997 · Parlophone · Rock")
+    pub fn release_info(&self) -> Option<String> {
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        let mut parts = Vec::new();
+        if let Some(year) = np.year {
+            parts.push(year.to_string());
+        }
+        if let Some(label) = &np.label {
+            parts.push(label.clone());
+        }
+        if !np.genres.is_empty() {
+            parts.push(np.genres.join(", "));
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" · "))
+        }
+    }
+
     /// Get current album art URL if changed
     pub fn album_art_url_if_changed(&self) -> Option<&str> {
         if let Some(zone) = self.current_zone() {
@@ -313,37 +1822,331 @@ impl App {
         None
     }
 
-    /// Set album art from decoded image data
-    pub fn set_album_art(&mut self, image: DynamicImage, url: String) {
-        self.album_art = Some(image);
-        self.album_art_url = Some(url);
+    /// Set album art from a decoded cover
+    pub fn set_album_art(&mut self, cover: Cover) {
+        let Some(picker) = &self.image_picker else {
+            self.pending_art = Some(cover);
+            return;
+        };
+        self.album_art = Some(picker.new_resize_protocol(cover.image));
+        self.album_art_url = Some(cover.url);
+        self.album_art_encoded = Some(cover.encoded);
+    }
+
+    /// Whether album art is on screen (Now Playing, split view, full-screen art or screensaver)
+    pub fn album_art_visible(&self) -> bool {
+        self.fullscreen_art
+            || self.screensaver.is_some()
+            || (!self.mini_player
+                && (self.view == View::NowPlaying
+                    || (self.split_view && matches!(self.view, View::Browse | View::Search))))
+    }
+
+    /// Artwork URL of a browse item: from the CLI, or built from the now
+    /// playing cover's URL, which embeds its image key
+    pub fn image_url(&self, item: &BrowseItem) -> Option<String> {
+        if let Some(url) = &item.album_art_url {
+            return Some(url.clone());
+        }
+        let key = item.image_key.as_deref()?;
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        let url = np.album_art_url.as_deref()?;
+        (!np.image_key.is_empty() && url.contains(&np.image_key))
+            .then(|| url.replace(&np.image_key, key))
+    }
+
+    /// Detail popup artwork still to be fetched (marks it as requested)
+    pub fn detail_art_url_if_needed(&mut self) -> Option<String> {
+        let detail = self.item_detail.as_mut()?;
+        if detail.art_requested || self.image_picker.is_none() {
+            return None;
+        }
+        detail.art_requested = true;
+        detail.art_url.clone()
+    }
+
+    /// Show fetched artwork, if the popup is still on that item
+    pub fn set_detail_art(&mut self, cover: Cover) {
+        let (Some(detail), Some(picker)) = (&mut self.item_detail, &self.image_picker) else {
+            return;
+        };
+        if detail.art_url.as_deref() == Some(cover.url.as_str()) {
+            detail.art = Some(picker.new_resize_protocol(cover.image));
+            self.dirty = true;
+        }
+    }
+
+    /// Drop the decoded album art, keeping only its compressed data
+    pub fn release_album_art(&mut self) {
+        if self.album_art.take().is_some() {
+            tracing::debug!("Released album art while away from Now Playing");
+        }
+    }
+
+    /// Compressed album art to decode again, once Now Playing is back on screen
+    pub fn album_art_to_restore(&self) -> Option<(String, Arc<[u8]>)> {
+        if !self.album_art_visible() || self.album_art.is_some() || self.pending_art.is_some() {
+            return None;
+        }
+        Some((self.album_art_url.clone()?, self.album_art_encoded.clone()?))
+    }
+
+    /// Whether a cover is waiting for the image picker to be queried
+    pub fn needs_image_picker(&self) -> bool {
+        self.image_picker.is_none() && self.pending_art.is_some()
+    }
+
+    /// Install the image picker and show the cover that was waiting for it
+    pub fn set_image_picker(&mut self, picker: Picker) {
+        self.image_picker = Some(picker);
+        if let Some(cover) = self.pending_art.take() {
+            self.set_album_art(cover);
+        }
+    }
+
+    /// Album art URL of the next queue track, if it isn't shown or prefetched yet
+    pub fn prefetch_art_url_if_needed(&self) -> Option<&str> {
+        // The first queue item is the track that's playing now
+        let url = self.queue.items.get(1)?.album_art_url.as_deref()?;
+        if self.album_art_url.as_deref() == Some(url)
+            || self.prefetch_art_url.as_deref() == Some(url)
+        {
+            return None;
+        }
+        Some(url)
+    }
+
+    /// Store a prefetched cover (or show it right away if its track already started)
+    pub fn set_prefetched_art(&mut self, cover: Cover) {
+        if self.album_art.is_none() && self.album_art_url.as_deref() == Some(cover.url.as_str()) {
+            self.set_album_art(cover);
+        } else {
+            self.prefetched_art = Some(cover);
+        }
+    }
+
+    /// Take the prefetched cover if it's the one for `url`
+    pub fn take_prefetched_art(&mut self, url: &str) -> Option<Cover> {
+        match self.prefetched_art.take() {
+            Some(cover) if cover.url == url => Some(cover),
+            other => {
+                self.prefetched_art = other;
+                None
+            }
+        }
     }
 
     /// Clear album art
     pub fn clear_album_art(&mut self) {
         self.album_art = None;
+        self.pending_art = None;
         self.album_art_url = None;
+        self.album_art_encoded = None;
+    }
+
+    /// Get a lyrics query if the lyrics popup is open and the track changed
+    pub fn lyrics_query_if_needed(&self) -> Option<LyricsQuery> {
+        if self.popup != Some(Popup::Lyrics) {
+            return None;
+        }
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        let query = LyricsQuery {
+            artist: np.artist.clone(),
+            track: np.track.clone(),
+            album: np.album.clone(),
+            length: np.length,
+        };
+        if self.lyrics_key.as_deref() == Some(query.key().as_str()) {
+            return None;
+        }
+        Some(query)
+    }
+
+    /// Mark a lyrics fetch as started for the given track
+    pub fn start_lyrics_fetch(&mut self, key: String) {
+        self.lyrics = None;
+        self.lyrics_key = Some(key);
+        self.lyrics_loading = true;
+        self.lyrics_scroll = 0;
+    }
+
+    /// Store fetched lyrics (ignored if the track changed meanwhile)
+    pub fn set_lyrics(&mut self, key: String, lyrics: Option<Lyrics>) {
+        if self.lyrics_key.as_deref() == Some(key.as_str()) {
+            self.lyrics = lyrics;
+            self.lyrics_loading = false;
+        }
+    }
+
+    /// Flip between the view and global help pages
+    pub fn switch_help_page(&mut self) {
+        self.help_page = match self.help_page {
+            HelpPage::View => HelpPage::Global,
+            HelpPage::Global => HelpPage::View,
+        };
+        self.help_scroll = 0;
+    }
+
+    /// Start typing a help filter, or wait for a key to look up
+    pub fn start_help_input(&mut self, input: HelpInput) {
+        self.help_input = input;
+        self.help_lookup = None;
+    }
+
+    /// Edit the help filter (`None` deletes a character)
+    pub fn edit_help_filter(&mut self, c: Option<char>) {
+        match c {
+            Some(c) => self.help_filter.push(c),
+            None => {
+                self.help_filter.pop();
+            }
+        }
+        self.help_scroll = 0;
+    }
+
+    /// Describe what a key does in the current view
+    pub fn lookup_help_key(&mut self, key: KeyEvent) {
+        self.help_lookup = Some(self.keymap.describe_key(key, self.view));
+        self.help_input = HelpInput::Browse;
     }
 
     /// Show a popup
     pub fn show_popup(&mut self, popup: Popup) {
-        if popup == Popup::ZoneSelector {
-            self.zone_selector_index = self.selected_zone_index;
+        match popup {
+            Popup::ZoneSelector => {
+                self.zone_selector_index = self.selected_zone_position().unwrap_or(0)
+            }
+            Popup::Onboarding => self.onboarding = OnboardingState::default(),
+            Popup::OpenIn => self.open_in_index = 0,
+            Popup::Lyrics => self.lyrics_scroll = 0,
+            Popup::Volume => self.volume_output_index = 0,
+            Popup::Trim => self.trim_index = 0,
+            Popup::DspPresets => {
+                self.dsp_index = self.dsp_presets.iter().position(|p| p.active).unwrap_or(0);
+            }
+            Popup::PartyUnlock => {
+                self.party.input.clear();
+                self.party.message = None;
+            }
+            Popup::ThemeSwitcher => {
+                self.themes = Theme::installed()
+                    .into_iter()
+                    .map(|theme| theme.fit(self.color_depth))
+                    .collect();
+                self.theme_index = self
+                    .themes
+                    .iter()
+                    .position(|t| t.name == self.theme.name)
+                    .unwrap_or(0);
+                self.theme_before_preview = Some(self.theme.clone());
+            }
+            Popup::CoreSwitcher => {
+                self.core_index = self
+                    .current_core()
+                    .and_then(|core| self.config.cores.iter().position(|c| c.name == core.name))
+                    .unwrap_or(0);
+            }
+            Popup::ConfirmQuit
+            | Popup::Resume
+            | Popup::ItemDetail
+            | Popup::QueueItem
+            | Popup::Outputs => {}
+            Popup::ZoneGone => {
+                if let Some(gone) = &mut self.zone_gone {
+                    gone.prompted = true;
+                }
+            }
+            Popup::Stats => self.stats_year = clock::civil_date(clock::local_day()).0,
+            Popup::Focus => {
+                self.focus.input = None;
+                self.focus.message = None;
+            }
+            Popup::Credentials => {
+                self.credentials.selected_index = 0;
+                self.credentials.input = None;
+                self.credentials.message = None;
+                self.refresh_credentials();
+            }
+            Popup::Help => {
+                self.help_page = HelpPage::View;
+                self.help_scroll = 0;
+                self.help_input = HelpInput::Browse;
+                self.help_filter.clear();
+                self.help_lookup = None;
+            }
         }
         self.popup = Some(popup);
     }
 
+    /// Re-check where each token is stored (after a save or removal)
+    pub fn refresh_credentials(&mut self) {
+        let config = &self.config.credentials;
+        self.credentials.stored = Service::ALL
+            .iter()
+            .map(|&service| credentials::location(service, config))
+            .collect();
+    }
+
     /// Close any open popup
     pub fn close_popup(&mut self) {
+        // Closing the theme switcher without confirming reverts the preview
+        if let Some(theme) = self.theme_before_preview.take() {
+            self.theme = theme;
+        }
         self.popup = None;
+        // A zone that went away while another popup was open prompts now
+        if self.zone_gone.as_ref().is_some_and(|gone| !gone.prompted) {
+            self.show_popup(Popup::ZoneGone);
+        }
+    }
+
+    /// Apply the highlighted theme so the UI re-renders in it
+    fn preview_theme(&mut self) {
+        if let Some(theme) = self.themes.get(self.theme_index) {
+            self.theme = theme.clone();
+        }
+    }
+
+    /// Keep the previewed theme and close the switcher
+    pub fn confirm_theme(&mut self) {
+        self.theme_before_preview = None;
+        self.close_popup();
     }
 
     /// Move selection up (dispatched by context)
     pub fn select_up(&mut self) {
-        if self.popup == Some(Popup::ZoneSelector) {
+        if matches!(self.popup, Some(Popup::ZoneSelector | Popup::Onboarding)) {
             if self.zone_selector_index > 0 {
                 self.zone_selector_index -= 1;
             }
+        } else if self.popup == Some(Popup::OpenIn) {
+            if self.open_in_index > 0 {
+                self.open_in_index -= 1;
+            }
+        } else if self.popup == Some(Popup::Credentials) {
+            self.credentials.selected_index = self.credentials.selected_index.saturating_sub(1);
+            self.credentials.message = None;
+        } else if self.popup == Some(Popup::Lyrics) {
+            self.lyrics_scroll = self.lyrics_scroll.saturating_sub(1);
+        } else if self.popup == Some(Popup::Help) {
+            self.help_scroll = self.help_scroll.saturating_sub(1);
+        } else if self.popup == Some(Popup::Volume) {
+            if self.volume_output_index > 0 {
+                self.volume_output_index -= 1;
+            }
+        } else if self.popup == Some(Popup::CoreSwitcher) {
+            self.core_index = self.core_index.saturating_sub(1);
+        } else if self.popup == Some(Popup::Trim) {
+            self.trim_index = self.trim_index.saturating_sub(1);
+        } else if self.popup == Some(Popup::DspPresets) {
+            self.dsp_index = self.dsp_index.saturating_sub(1);
+        } else if self.popup == Some(Popup::Focus) {
+            self.focus.selected_index = self.focus.selected_index.saturating_sub(1);
+        } else if self.popup == Some(Popup::ThemeSwitcher) {
+            if self.theme_index > 0 {
+                self.theme_index -= 1;
+                self.preview_theme();
+            }
         } else {
             match self.view {
                 View::Browse => {
@@ -356,6 +2159,21 @@ impl App {
                         self.search.results.selected_index -= 1;
                     }
                 }
+                View::Queue => {
+                    if self.queue.selected_index > 0 {
+                        self.queue.selected_index -= 1;
+                    }
+                }
+                View::Settings => {
+                    if self.settings.selected_index > 0 {
+                        self.settings.selected_index -= 1;
+                    }
+                }
+                View::ForYou => {
+                    if self.for_you.list.selected_index > 0 {
+                        self.for_you.list.selected_index -= 1;
+                    }
+                }
                 View::NowPlaying => {}
             }
         }
@@ -363,10 +2181,60 @@ impl App {
 
     /// Move selection down (dispatched by context)
     pub fn select_down(&mut self) {
-        if self.popup == Some(Popup::ZoneSelector) {
+        if matches!(self.popup, Some(Popup::ZoneSelector | Popup::Onboarding)) {
             if self.zone_selector_index < self.zones.len().saturating_sub(1) {
                 self.zone_selector_index += 1;
             }
+        } else if self.popup == Some(Popup::OpenIn) {
+            if self.open_in_index < self.config.open_in.len().saturating_sub(1) {
+                self.open_in_index += 1;
+            }
+        } else if self.popup == Some(Popup::Credentials) {
+            if self.credentials.selected_index < Service::ALL.len() - 1 {
+                self.credentials.selected_index += 1;
+            }
+            self.credentials.message = None;
+        } else if self.popup == Some(Popup::Lyrics) {
+            let lines = self
+                .lyrics
+                .as_ref()
+                .map(|l| l.text.lines().count())
+                .unwrap_or(0);
+            if (self.lyrics_scroll as usize) < lines.saturating_sub(1) {
+                self.lyrics_scroll += 1;
+            }
+        } else if self.popup == Some(Popup::Help) {
+            let rows = crate::input::help_text(self).len();
+            if (self.help_scroll as usize) < rows.saturating_sub(1) {
+                self.help_scroll += 1;
+            }
+        } else if self.popup == Some(Popup::Volume) {
+            let outputs = self.current_zone().map(|z| z.outputs.len()).unwrap_or(0);
+            if self.volume_output_index < outputs.saturating_sub(1) {
+                self.volume_output_index += 1;
+            }
+        } else if self.popup == Some(Popup::CoreSwitcher) {
+            if self.core_index < self.config.cores.len().saturating_sub(1) {
+                self.core_index += 1;
+            }
+        } else if self.popup == Some(Popup::DspPresets) {
+            if self.dsp_index < self.dsp_presets.len().saturating_sub(1) {
+                self.dsp_index += 1;
+            }
+        } else if self.popup == Some(Popup::Trim) {
+            let outputs = self.current_zone().map(|z| z.outputs.len()).unwrap_or(0);
+            if self.trim_index < outputs.saturating_sub(1) {
+                self.trim_index += 1;
+            }
+        } else if self.popup == Some(Popup::Focus) {
+            if self.focus.selected_index < FocusField::ALL.len() - 1 {
+                self.focus.selected_index += 1;
+            }
+        } else if self.popup == Some(Popup::ThemeSwitcher) {
+            if self.theme_index < self.themes.len().saturating_sub(1) {
+                self.theme_index += 1;
+                self.preview_theme();
+            }
         } else {
             match self.view {
                 View::Browse => {
@@ -381,20 +2249,373 @@ impl App {
                         self.search.results.selected_index += 1;
                     }
                 }
+                View::Queue => {
+                    if self.queue.selected_index < self.queue.items.len().saturating_sub(1) {
+                        self.queue.selected_index += 1;
+                    }
+                }
+                View::Settings => {
+                    let count = self.keymap.bindings().len();
+                    if self.settings.selected_index < count.saturating_sub(1) {
+                        self.settings.selected_index += 1;
+                    }
+                }
+                View::ForYou => {
+                    let list = &mut self.for_you.list;
+                    if list.selected_index < list.items.len().saturating_sub(1) {
+                        list.selected_index += 1;
+                    }
+                }
                 View::NowPlaying => {}
             }
         }
     }
 
+    /// Merge a fresh zone list into the current one, matching zones by id
+    ///
+    /// Unchanged zones are left untouched, the selected zone stays selected
+    /// even if the list order changes, and the returned events describe what
+    /// changed.
+    pub fn update_zones(&mut self, incoming: Vec<Zone>) -> Vec<ZoneEvent> {
+        let mut events = Vec::new();
+        // The selector keeps the zone it highlights, wherever it moves to
+        let highlighted = self
+            .zones
+            .get(self.zone_selector_index)
+            .map(|z| z.zone_id.clone());
+        let mut previous = std::mem::take(&mut self.zones);
+        // Snapshot zones only kept the selection: everything is new, as on a
+        // startup without one
+        if std::mem::take(&mut self.stale) {
+            previous.clear();
+        }
+        let filling = previous.is_empty();
+
+        for zone in incoming {
+            match previous.iter().position(|z| z.zone_id == zone.zone_id) {
+                Some(index) => {
+                    let mut existing = previous.swap_remove(index);
+                    merge_zone(&mut existing, zone, &mut events);
+                    self.zones.push(existing);
+                }
+                None => {
+                    if !filling {
+                        events.push(ZoneEvent::Added(zone.zone_id.clone()));
+                    }
+                    self.zones.push(zone);
+                }
+            }
+        }
+        events.extend(
+            previous
+                .iter()
+                .map(|z| ZoneEvent::Removed(z.zone_id.clone())),
+        );
+
+        if self.current_zone().is_some() {
+            if self.zone_gone.take().is_some() && self.popup == Some(Popup::ZoneGone) {
+                self.close_popup();
+            }
+        } else if let Some(gone) = &mut self.zone_gone {
+            // Grouping can take a refresh or two to show up
+            let found = successor(&self.zones, &gone.outputs);
+            if found != gone.successor {
+                gone.successor = found;
+                if let Some(id) = &self.selected_zone {
+                    events.push(ZoneEvent::SelectedGone(id.clone()));
+                }
+            }
+        } else {
+            let id = self.selected_zone.as_deref();
+            match previous.iter().find(|z| Some(z.zone_id.as_str()) == id) {
+                Some(zone) => {
+                    events.push(ZoneEvent::SelectedGone(zone.zone_id.clone()));
+                    let outputs: Vec<String> =
+                        zone.outputs.iter().map(|o| o.output_id.clone()).collect();
+                    self.zone_gone = Some(ZoneGone {
+                        name: zone.display_name.clone(),
+                        successor: successor(&self.zones, &outputs),
+                        outputs,
+                        prompted: false,
+                    });
+                    self.clear_album_art();
+                    if self.popup.is_none() {
+                        self.show_popup(Popup::ZoneGone);
+                    }
+                }
+                // First zones, or a snapshot's zone that was gone at startup
+                None => {
+                    self.selected_zone = self.zones.first().map(|z| z.zone_id.clone());
+                    self.clear_album_art();
+                }
+            }
+        }
+        self.zone_selector_index = highlighted
+            .and_then(|id| self.zones.iter().position(|z| z.zone_id == id))
+            .unwrap_or(self.zone_selector_index)
+            .min(self.zones.len().saturating_sub(1));
+        events
+    }
+
+    /// Remember the position in long items, and offer the saved one when an
+    /// item starts again from the top
+    pub fn track_resume(&mut self) {
+        if !self.config.resume.enabled {
+            return;
+        }
+        let min_length = self.config.resume.min_minutes as f64 * 60.0;
+        let Some(np) = self.current_zone().and_then(|z| z.now_playing.as_ref()) else {
+            return;
+        };
+        if self.is_live() || np.length < min_length {
+            self.resume_item = None;
+            return;
+        }
+        let key = resume::item_key(np);
+        let (position, length) = (np.seek_position, np.length);
+
+        if self.resume_item.as_deref() != Some(key.as_str()) {
+            self.resume.save();
+            self.resume_item = Some(key.clone());
+            let saved = self.resume.get(&key);
+            if let Some(saved) = saved.filter(|_| position < RESUME_MARGIN) {
+                if self.popup.is_none() {
+                    self.resume_offer = Some(saved);
+                    self.show_popup(Popup::Resume);
+                    self.dirty = true;
+                }
+            }
+            // Keep the saved position until the item has played a bit
+            return;
+        }
+        if self.popup == Some(Popup::Resume) {
+            return;
+        }
+        if length - position < RESUME_MARGIN {
+            self.resume.remove(&key);
+        } else if position >= RESUME_MARGIN {
+            self.resume.set(&key, position);
+        }
+    }
+
+    /// The terminal title for the selected zone, if it changed since last time
+    pub fn terminal_title_change(&mut self) -> Option<String> {
+        if !self.config.terminal_title {
+            return None;
+        }
+        let playing = self.is_playing();
+        let title = match self.current_zone().and_then(|z| z.now_playing.as_ref()) {
+            Some(np) => {
+                let icon = if playing { "▶" } else { "⏸" };
+                if np.artist.is_empty() {
+                    format!("{} {}", icon, np.track)
+                } else {
+                    format!("{} {} – {}", icon, np.artist, np.track)
+                }
+            }
+            None => "roon-tui".to_string(),
+        };
+        // Metadata must not be able to end the escape sequence early
+        let title: String = title.chars().filter(|c| !c.is_control()).collect();
+        if self.terminal_title.as_ref() == Some(&title) {
+            return None;
+        }
+        self.terminal_title = Some(title.clone());
+        Some(title)
+    }
+
+    /// The taskbar progress (OSC 9;4 state, percent) for the selected zone,
+    /// if it changed since last time
+    ///
+    /// States are 0 (none), 1 (normal), 3 (indeterminate) and 4 (paused).
+    pub fn taskbar_progress_change(&mut self) -> Option<(u8, u8)> {
+        if !self.config.taskbar_progress {
+            return None;
+        }
+        let percent = (self.progress_ratio() * 100.0).round() as u8;
+        let has_track = self.current_zone().is_some_and(|z| z.now_playing.is_some());
+        let progress = match self.playback_state() {
+            PlaybackState::Loading => (3, 0),
+            _ if !has_track || self.is_live() => (0, 0),
+            PlaybackState::Playing => (1, percent),
+            PlaybackState::Paused => (4, percent),
+            PlaybackState::Stopped => (0, 0),
+        };
+        if self.taskbar_progress == Some(progress) {
+            return None;
+        }
+        self.taskbar_progress = Some(progress);
+        Some(progress)
+    }
+
+    /// In album-shuffle mode, whether the selected zone started the last
+    /// track of its queue, so another album should be queued after it
+    /// (once per track)
+    pub fn album_shuffle_due(&mut self) -> bool {
+        if !self.album_shuffle {
+            return false;
+        }
+        let last = self
+            .current_zone()
+            .filter(|z| z.is_playing() && z.queue_items_remaining <= 1)
+            .and_then(|z| z.now_playing.as_ref())
+            .map(resume::item_key);
+        match last {
+            Some(key) if self.album_shuffle_after.as_ref() != Some(&key) => {
+                self.album_shuffle_after = Some(key);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Log and show new warnings about zones or queue items that couldn't
+    /// be parsed; the same warnings on every refresh are reported once
+    pub fn set_parse_warnings(&mut self, warnings: Vec<String>) {
+        if warnings == self.parse_warnings {
+            return;
+        }
+        for warning in &warnings {
+            tracing::warn!("{}", warning);
+        }
+        if let Some(first) = warnings.first() {
+            self.show_notice(first.clone());
+        }
+        self.parse_warnings = warnings;
+    }
+
+    /// Announce a new track in the selected zone
+    pub fn announce_track(&mut self, events: &[ZoneEvent]) {
+        let Some(zone) = self.selected_zone_position().map(|i| &self.zones[i]) else {
+            return;
+        };
+        let changed = events
+            .iter()
+            .any(|e| matches!(e, ZoneEvent::TrackChanged(id) if *id == zone.zone_id));
+        if let Some(np) = zone.now_playing.as_ref().filter(|_| changed) {
+            self.announcer.track_changed(np);
+        }
+    }
+
+    /// Count the time since the last refresh as listening for every
+    /// playing zone
+    pub fn track_listening(&mut self) {
+        let elapsed = self.listening_at.elapsed();
+        self.listening_at = Instant::now();
+        if !self.config.stats.enabled || elapsed > MAX_LISTENING_GAP {
+            return;
+        }
+        for zone in self.zones.iter().filter(|z| z.is_playing()) {
+            if let Some(np) = &zone.now_playing {
+                self.history.add(&zone.zone_id, np, elapsed);
+            }
+        }
+    }
+
+    /// Show the previous (or next) year in the stats heatmap, within the
+    /// years that have listening
+    pub fn step_stats_year(&mut self, forward: bool) {
+        let this_year = clock::civil_date(clock::local_day()).0;
+        let first_year = self.history.first_year().unwrap_or(this_year);
+        let year = if forward {
+            self.stats_year + 1
+        } else {
+            self.stats_year - 1
+        };
+        self.stats_year = year.clamp(first_year.min(this_year), this_year);
+    }
+
+    /// In follow mode, the zone that just started playing, or that joined
+    /// the list playing (if it isn't already the selected one)
+    pub fn follow_target(&self, events: &[ZoneEvent]) -> Option<String> {
+        // Following was on before party mode, which locks the zone
+        if !self.follow || self.party.on {
+            return None;
+        }
+        let current = self.current_zone().map(|z| z.zone_id.as_str());
+        events
+            .iter()
+            .rev()
+            .filter_map(|event| match event {
+                ZoneEvent::Added(id) | ZoneEvent::StateChanged(id) => Some(id),
+                _ => None,
+            })
+            .filter(|id| Some(id.as_str()) != current)
+            .filter_map(|id| self.zones.iter().find(|z| &z.zone_id == id))
+            .find(|zone| zone.is_playing())
+            .map(|zone| zone.display_name.clone())
+    }
+
     /// Select the currently highlighted zone
     pub fn select_zone(&mut self) {
-        if self.zone_selector_index < self.zones.len() {
-            self.selected_zone_index = self.zone_selector_index;
-            self.clear_album_art(); // Force reload album art for new zone
+        if let Some(zone) = self.zones.get(self.zone_selector_index) {
+            self.set_selected_zone(zone.zone_id.clone());
             self.close_popup();
         }
     }
 
+    /// The Core in use, if any are configured
+    pub fn current_core(&self) -> Option<&CoreConfig> {
+        self.config.core(self.cores.current())
+    }
+
+    /// Remember the selected zone as the one to come back to on this Core
+    pub fn remember_core_zone(&mut self) {
+        let Some(core) = self.current_core().map(|core| core.name.clone()) else {
+            return;
+        };
+        if let Some(zone) = self.current_zone().map(|z| z.display_name.clone()) {
+            self.cores.set_zone(&core, &zone);
+        }
+    }
+
+    /// Forget everything that came from the previous Core
+    pub fn reset_for_core(&mut self) {
+        self.zones.clear();
+        self.selected_zone = None;
+        self.zone_gone = None;
+        self.zone_selector_index = 0;
+        self.queue = QueueState::default();
+        self.browse = BrowseState::default();
+        self.search = SearchState::default();
+        self.for_you = ForYouState::default();
+        self.browser = Browser::default();
+        self.connected = false;
+        self.clear_album_art();
+    }
+
+    /// Select a zone by display name, returning whether it was found
+    pub fn select_zone_named(&mut self, name: &str) -> bool {
+        match self.zones.iter().find(|z| z.display_name == name) {
+            Some(zone) => {
+                self.set_selected_zone(zone.zone_id.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Build the URL for the highlighted "Open in…" service and the current track
+    pub fn selected_open_in_url(&self) -> Option<String> {
+        let service = self.config.open_in.get(self.open_in_index)?;
+        let np = self.current_zone()?.now_playing.as_ref()?;
+        Some(external::build_url(&service.url, np))
+    }
+
+    /// Follow the selected zone that went away to the zone holding its
+    /// outputs now; returns that zone's name
+    pub fn follow_successor(&mut self) -> Option<String> {
+        let id = self.zone_gone.as_ref()?.successor.clone()?;
+        let name = self
+            .zones
+            .iter()
+            .find(|z| z.zone_id == id)?
+            .display_name
+            .clone();
+        self.set_selected_zone(id);
+        Some(name)
+    }
+
     /// Get the zone name at selector index (for setting in CLI)
     pub fn get_selected_zone_name(&self) -> Option<String> {
         self.zones
@@ -405,14 +2626,70 @@ impl App {
 
 impl Default for App {
     fn default() -> Self {
-        Self::new()
+        Self::new(Config::default())
+    }
+}
+
+/// The zone holding most of `outputs` (the ids of a zone that went away)
+fn successor(zones: &[Zone], outputs: &[String]) -> Option<String> {
+    zones
+        .iter()
+        .map(|zone| {
+            let shared = zone
+                .outputs
+                .iter()
+                .filter(|o| outputs.contains(&o.output_id))
+                .count();
+            (shared, zone)
+        })
+        .filter(|(shared, _)| *shared > 0)
+        .max_by_key(|(shared, _)| *shared)
+        .map(|(_, zone)| zone.zone_id.clone())
+}
+
+/// Update the fields of `existing` that differ in `incoming`, recording events
+fn merge_zone(existing: &mut Zone, incoming: Zone, events: &mut Vec<ZoneEvent>) {
+    let id = incoming.zone_id.clone();
+    let track = |np: &Option<NowPlaying>| {
+        np.as_ref()
+            .map(|np| (np.track.clone(), np.artist.clone(), np.album.clone()))
+    };
+
+    if track(&existing.now_playing) != track(&incoming.now_playing) {
+        events.push(ZoneEvent::TrackChanged(id.clone()));
+    }
+    if existing.state != incoming.state {
+        events.push(ZoneEvent::StateChanged(id.clone()));
+        existing.state = incoming.state;
+    }
+    if existing.outputs != incoming.outputs {
+        let volumes =
+            |outputs: &[Output]| -> Vec<_> { outputs.iter().map(|o| o.volume.clone()).collect() };
+        if volumes(&existing.outputs) != volumes(&incoming.outputs) {
+            events.push(ZoneEvent::VolumeChanged(id));
+        }
+        existing.outputs = incoming.outputs;
     }
+    if existing.now_playing != incoming.now_playing {
+        existing.now_playing = incoming.now_playing;
+    }
+    if existing.display_name != incoming.display_name {
+        existing.display_name = incoming.display_name;
+    }
+    if existing.settings != incoming.settings {
+        existing.settings = incoming.settings;
+    }
+    existing.queue_items_remaining = incoming.queue_items_remaining;
+    existing.queue_time_remaining = incoming.queue_time_remaining;
 }
 
-/// Format seconds as mm:ss
-fn format_duration(secs: f64) -> String {
-    let total_secs = secs as u64;
-    let mins = total_secs / 60;
-    let secs = total_secs % 60;
-    format!("{:02}:{:02}", mins, secs)
+/// Format seconds as mm:ss, or h:mm:ss from an hour on
+pub fn format_duration(secs: f64) -> String {
+    let total_secs = secs.max(0.0) as u64;
+    let (hours, mins, secs) = (total_secs / 3600, total_secs / 60 % 60, total_secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{:02}:{:02}", mins, secs)
+    }
 }
@@ -0,0 +1,193 @@
+use anyhow::{bail, Result};
+
+use crate::config::Config;
+
+/// What the binary was asked to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Start the TUI
+    Run,
+    /// Print a diagnostic report
+    Doctor,
+    /// Print the last session's latency report
+    PerfReport,
+    /// Print usage
+    Help,
+    /// Print the version
+    Version,
+    /// Print the config, cache, state and log paths
+    PrintPaths,
+    /// Play something on a zone and exit
+    Play {
+        target: PlayTarget,
+        /// Zone to play on, instead of the CLI's active one
+        zone: Option<String>,
+    },
+}
+
+/// What `play` plays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayTarget {
+    /// A file or folder of the Core's storage
+    Path(String),
+    /// A library track by its identifier
+    Item(String),
+}
+
+/// Parsed command-line arguments
+#[derive(Debug)]
+pub struct Args {
+    pub command: Command,
+    /// Skip the background update check, whatever the config says
+    pub no_update_check: bool,
+    /// Start in the compact mini-player
+    pub mini: bool,
+    /// Config profile to layer over the main config file
+    pub profile: Option<String>,
+    /// roon CLI executable, instead of `roon_command`
+    pub roon_command: Option<String>,
+    /// Command to run the roon CLI through, instead of `roon_prefix`
+    pub roon_prefix: Option<Vec<String>>,
+    /// Extra roon CLI arguments, added to `roon_args`
+    pub roon_args: Vec<String>,
+}
+
+impl Args {
+    /// Parse `std::env::args()`
+    pub fn parse() -> Result<Self> {
+        Self::parse_from(std::env::args().skip(1))
+    }
+
+    fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut command = Command::Run;
+        let mut no_update_check = false;
+        let mut mini = false;
+        let mut profile = None;
+        let mut roon_command = None;
+        let mut roon_prefix = None;
+        let mut roon_args = Vec::new();
+        let mut play = false;
+        let (mut path, mut item, mut zone) = (None, None, None);
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(name) = arg.strip_prefix("--profile=") {
+                profile = Some(name.to_string());
+                continue;
+            }
+            // Options with a value, as `--opt value` or `--opt=value`
+            let (option, inline) = match arg.split_once('=') {
+                Some((option, value)) => (option, Some(value.to_string())),
+                None => (arg.as_str(), None),
+            };
+            if matches!(
+                option,
+                "--roon" | "--roon-prefix" | "--roon-arg" | "--path" | "--id" | "--zone"
+            ) {
+                let Some(value) = inline.or_else(|| args.next()) else {
+                    bail!("{option} needs a value\n\n{}", usage());
+                };
+                match option {
+                    "--roon" => roon_command = Some(value),
+                    "--roon-prefix" => {
+                        roon_prefix = Some(value.split_whitespace().map(String::from).collect())
+                    }
+                    "--path" => path = Some(value),
+                    "--id" => item = Some(value),
+                    "--zone" => zone = Some(value),
+                    _ => roon_args.push(value),
+                }
+                continue;
+            }
+            command = match arg.as_str() {
+                "doctor" => Command::Doctor,
+                "perf-report" => Command::PerfReport,
+                "-h" | "--help" | "help" => Command::Help,
+                "-V" | "--version" => Command::Version,
+                "--print-paths" => Command::PrintPaths,
+                "play" => {
+                    play = true;
+                    continue;
+                }
+                "--no-update-check" => {
+                    no_update_check = true;
+                    continue;
+                }
+                "--mini" => {
+                    mini = true;
+                    continue;
+                }
+                "--profile" => match args.next() {
+                    Some(name) => {
+                        profile = Some(name);
+                        continue;
+                    }
+                    None => bail!("--profile needs a name\n\n{}", usage()),
+                },
+                other => bail!("unknown argument: {other}\n\n{}", usage()),
+            };
+        }
+        if play {
+            let target = match (path, item) {
+                (Some(path), None) => PlayTarget::Path(path),
+                (None, Some(id)) => PlayTarget::Item(id),
+                _ => bail!("play needs either --path or --id\n\n{}", usage()),
+            };
+            command = Command::Play { target, zone };
+        } else if path.is_some() || item.is_some() || zone.is_some() {
+            bail!("--path, --id and --zone go with play\n\n{}", usage());
+        }
+        Ok(Self {
+            command,
+            no_update_check,
+            mini,
+            profile,
+            roon_command,
+            roon_prefix,
+            roon_args,
+        })
+    }
+
+    /// Apply the options that override config settings
+    pub fn apply(&self, config: &mut Config) {
+        if self.no_update_check {
+            config.update_check = false;
+        }
+        if let Some(command) = &self.roon_command {
+            config.roon_command = command.clone();
+        }
+        if let Some(prefix) = &self.roon_prefix {
+            config.roon_prefix = prefix.clone();
+        }
+        config.roon_args.extend(self.roon_args.iter().cloned());
+    }
+}
+
+/// Usage text for `--help`
+pub fn usage() -> String {
+    format!(
+        "roon-tui {}
+Terminal UI for Roon via roon-cli
+
+Usage: roon-tui [OPTIONS] [COMMAND]
+
+Commands:
+  doctor                 Check the roon CLI, Core connection, terminal and config
+  perf-report            Show command and render latencies of the last session
+  play --path <PATH>     Play a file or folder of the Core's storage, then exit
+  play --id <ID>         Play a library track by its identifier, then exit
+                         (either one takes --zone <NAME> to pick the zone)
+
+Options:
+      --mini             Start in the compact mini-player
+      --profile <NAME>   Layer profiles/<NAME>.toml over the config file
+      --no-update-check  Don't check for a newer release
+      --roon <PATH>      roon CLI executable (overrides roon_command)
+      --roon-prefix <CMD>
+                         Run the roon CLI through CMD, e.g. \"ssh mediaserver\"
+      --roon-arg <ARG>   Pass ARG to the roon CLI before every command
+      --print-paths      Print the config, cache, state and log paths
+  -h, --help             Print help
+  -V, --version          Print version",
+        env!("CARGO_PKG_VERSION")
+    )
+}
@@ -0,0 +1,104 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use base64::Engine;
+
+use crate::config::Osc52;
+
+/// How text reached the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// A local clipboard tool (pbcopy, wl-copy, xclip, ...)
+    Native(&'static str),
+    /// An OSC 52 escape sequence, set by the terminal itself (works over SSH)
+    Osc52,
+}
+
+/// Copy text to the clipboard: with the platform's tool, or through the
+/// terminal when running over SSH or no tool is available
+pub fn copy(text: &str, osc52: Osc52) -> Result<Method> {
+    if method(osc52) == Some(Method::Osc52) {
+        write_osc52(text)?;
+        return Ok(Method::Osc52);
+    }
+    let (program, args) = native_tool().context("no clipboard tool found")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    if !child.wait()?.success() {
+        bail!("{} failed", program);
+    }
+    Ok(Method::Native(program))
+}
+
+/// How `copy` would reach the clipboard, if at all
+pub fn method(osc52: Osc52) -> Option<Method> {
+    let native = native_tool().map(|(program, _)| Method::Native(program));
+    match osc52 {
+        Osc52::Always => Some(Method::Osc52),
+        Osc52::Never => native,
+        Osc52::Auto if is_remote() => Some(Method::Osc52),
+        Osc52::Auto => native.or(Some(Method::Osc52)),
+    }
+}
+
+/// Whether this is an SSH session (where local tools would copy on the
+/// remote machine)
+fn is_remote() -> bool {
+    ["SSH_TTY", "SSH_CONNECTION", "SSH_CLIENT"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some())
+}
+
+/// The clipboard tool for this platform and display server, if installed
+fn native_tool() -> Option<(&'static str, &'static [&'static str])> {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("clip", &[])]
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[("wl-copy", &[])]
+    } else if std::env::var_os("DISPLAY").is_some() {
+        &[
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    } else {
+        &[]
+    };
+    candidates
+        .iter()
+        .copied()
+        .find(|(program, _)| on_path(program))
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|path| {
+        std::env::split_paths(&path).any(|dir| {
+            dir.join(program).is_file() || dir.join(format!("{}.exe", program)).is_file()
+        })
+    })
+}
+
+/// Ask the terminal to set the clipboard. Inside tmux the sequence is
+/// passed through to the outer terminal.
+fn write_osc52(text: &str) -> Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = if std::env::var_os("TMUX").is_some() {
+        format!("\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", encoded)
+    } else {
+        format!("\x1b]52;c;{}\x07", encoded)
+    };
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
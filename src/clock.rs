@@ -0,0 +1,74 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Local wall-clock time `from_now` in the future, as "HH:MM"
+pub fn time_in(from_now: Duration) -> String {
+    let at = SystemTime::now() + from_now;
+    let secs = at
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default() as libc::time_t;
+
+    // SAFETY: localtime_r only writes to the tm struct we hand it
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&secs, &mut tm) }.is_null() {
+        // No timezone info: fall back to UTC
+        let day = secs % 86_400;
+        return format!("{:02}:{:02}", day / 3600, day % 3600 / 60);
+    }
+    format!("{:02}:{:02}", tm.tm_hour, tm.tm_min)
+}
+
+/// Days since the Unix epoch, in local time
+pub fn local_day() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default() as libc::time_t;
+
+    // SAFETY: as in `time_in`
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let offset = if unsafe { libc::localtime_r(&secs, &mut tm) }.is_null() {
+        0
+    } else {
+        tm.tm_gmtoff as i64
+    };
+    (secs as i64 + offset).div_euclid(86_400)
+}
+
+pub const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A day from `local_day` as "Oct 12"
+pub fn day_label(day: i64) -> String {
+    let (_, month, day) = civil_date(day);
+    format!("{} {}", MONTHS[month as usize - 1], day)
+}
+
+// Day numbers <-> proleptic Gregorian dates, after Howard Hinnant's
+// `civil_from_days` / `days_from_civil`
+
+/// (year, month 1-12, day 1-31) of a day since the epoch
+pub fn civil_date(day: i64) -> (i64, u32, u32) {
+    let z = day + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m as u32, d as u32)
+}
+
+/// Day since the epoch of a date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
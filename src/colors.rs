@@ -0,0 +1,149 @@
+use ratatui::style::Color;
+
+use crate::config::ColorSupport;
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// 24-bit RGB
+    TrueColor,
+    /// The xterm 256-color palette
+    Ansi256,
+    /// Only the 16 basic ANSI colors
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// The configured depth, or the detected one for "auto"
+    pub fn resolve(support: ColorSupport) -> Self {
+        match support {
+            ColorSupport::Auto => detect(),
+            ColorSupport::Truecolor => ColorDepth::TrueColor,
+            ColorSupport::Ansi256 => ColorDepth::Ansi256,
+            ColorSupport::Ansi16 => ColorDepth::Ansi16,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorDepth::TrueColor => "truecolor",
+            ColorDepth::Ansi256 => "256 colors",
+            ColorDepth::Ansi16 => "16 colors",
+        }
+    }
+
+    /// The closest color this depth can show
+    pub fn fit(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256(r, g, b)),
+            (ColorDepth::Ansi256, _) => color,
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(index)) => {
+                let (r, g, b) = indexed_rgb(index);
+                nearest_16(r, g, b)
+            }
+            (ColorDepth::Ansi16, _) => color,
+        }
+    }
+}
+
+/// Guess the color depth from the environment
+///
+/// `COLORTERM` is the standard way to announce 24-bit color; a few
+/// terminals only say so through `TERM` or `TERM_PROGRAM`. Unknown
+/// terminals get 256 colors, which nearly everything supports.
+fn detect() -> ColorDepth {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let (term, program) = (var("TERM"), var("TERM_PROGRAM"));
+    if matches!(var("COLORTERM").as_str(), "truecolor" | "24bit")
+        || term.ends_with("-direct")
+        || ["kitty", "ghostty", "alacritty", "foot", "wezterm"]
+            .iter()
+            .any(|name| term.contains(name))
+        || matches!(
+            program.as_str(),
+            "iTerm.app" | "WezTerm" | "ghostty" | "vscode"
+        )
+    {
+        ColorDepth::TrueColor
+    } else if term.contains("256color") || program == "Apple_Terminal" {
+        ColorDepth::Ansi256
+    } else if term.is_empty() || matches!(term.as_str(), "linux" | "xterm" | "vt100" | "ansi") {
+        ColorDepth::Ansi16
+    } else {
+        ColorDepth::Ansi256
+    }
+}
+
+/// Channel levels of the 6×6×6 color cube (indexes 16–231)
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Typical xterm values of the 16 basic colors, in index order
+const BASIC: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).unsigned_abs().pow(2);
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// Closest entry of the color cube or the grayscale ramp
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&i| CUBE[i].abs_diff(c))
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+    let cube_rgb = (CUBE[ri], CUBE[gi], CUBE[bi]);
+
+    // Grayscale ramp 232–255: 8, 18, ..., 238
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * step;
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube_rgb, (r, g, b)) {
+        232 + step
+    } else {
+        cube
+    }
+}
+
+fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    BASIC
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map_or(Color::White, |(color, _)| *color)
+}
+
+/// RGB value of a 256-palette index
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => BASIC[usize::from(index)].1,
+        16..=231 => {
+            let i = usize::from(index - 16);
+            (CUBE[i / 36], CUBE[i / 6 % 6], CUBE[i % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
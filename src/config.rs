@@ -0,0 +1,864 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Active profile, layered over the main config file (`--profile`)
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// User configuration, loaded from `~/.config/roon-tui/config.toml`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// External services offered in the "Open in…" popup
+    pub open_in: Vec<OpenService>,
+
+    /// Lyrics providers and credentials
+    pub lyrics: LyricsConfig,
+
+    /// Volume behaviour
+    pub volume: VolumeConfig,
+
+    /// Mouse support
+    pub mouse: MouseConfig,
+
+    /// Idle screensaver
+    pub screensaver: ScreensaverConfig,
+
+    /// Audio spectrum bars under the progress bar
+    pub visualizer: VisualizerConfig,
+
+    /// Pause/resume around system suspend
+    pub suspend: SuspendConfig,
+
+    /// Remembered positions in long items (podcasts, audiobooks)
+    pub resume: ResumeConfig,
+
+    /// Where integration tokens are kept
+    pub credentials: CredentialsConfig,
+
+    /// Scripts in the plugins directory
+    pub plugins: PluginsConfig,
+
+    /// Searching streaming services along with the library
+    pub search: SearchConfig,
+
+    /// Where the For You view gathers mixes and recommendations from
+    pub for_you: ForYouConfig,
+
+    /// Local weekly listening summaries
+    pub stats: StatsConfig,
+
+    /// Spoken track changes
+    pub announce: AnnounceConfig,
+
+    /// System media keys (macOS)
+    pub media_keys: MediaKeysConfig,
+
+    /// Locking destructive keys while guests pick the music
+    pub party: PartyConfig,
+
+    /// Keybinding overrides: action name -> keys (e.g. `quit = ["q", "ctrl+q"]`)
+    pub keys: BTreeMap<String, Vec<String>>,
+
+    /// Single-key bindings ("flat") or leader-key menus ("leader")
+    pub keymap: KeymapScheme,
+
+    /// Key that opens the leader menus in the leader scheme
+    pub leader: String,
+
+    /// Name of the color theme (built-in or a file in the themes directory)
+    pub theme: String,
+
+    /// Zone selected at startup
+    pub default_zone: Option<String>,
+
+    /// Hidden zones, display aliases and ordering
+    pub zones: ZonesConfig,
+
+    /// Start in follow mode: switch to whichever zone most recently started playing
+    pub follow_music: bool,
+
+    /// Start in album-shuffle mode: queue a random album when the queue runs out
+    pub album_shuffle: bool,
+
+    /// Show the selected zone's track in the terminal window title
+    pub terminal_title: bool,
+
+    /// Report track progress to the terminal (OSC 9;4) for its taskbar button
+    pub taskbar_progress: bool,
+
+    /// Show the key hint footers (generated from the active keymap)
+    pub show_hints: bool,
+
+    /// Wall-clock end time in the status bar: the track's, or the queue's too
+    pub ends_at: EndsAt,
+
+    /// Progress bar style: a solid "gauge" or a thin "braille" line
+    pub progress_bar: ProgressStyle,
+
+    /// Time line shows the "elapsed" position or the "remaining" time
+    pub time_display: TimeDisplay,
+
+    /// Count down to the next track over a track's last this many seconds
+    /// (0 turns the countdown off)
+    pub countdown_seconds: u32,
+
+    /// Glyphs for playback and mode icons: "auto", "nerd" or "emoji"
+    pub icons: IconSet,
+
+    /// Colors the terminal can show: "auto", "truecolor", "256" or "16";
+    /// theme colors are approximated below truecolor
+    pub colors: ColorSupport,
+
+    /// Copy to the clipboard with OSC 52 terminal escapes: "auto" (over SSH
+    /// or without a clipboard tool), "always" or "never"
+    pub osc52: Osc52,
+
+    /// Ask before quitting while a zone is playing
+    pub confirm_quit: bool,
+
+    /// Check GitHub for a newer release (at most once a day, off by default)
+    pub update_check: bool,
+
+    /// roon CLI executable (name on PATH or full path)
+    pub roon_command: String,
+
+    /// Command the roon CLI is run through (e.g. `["ssh", "mediaserver"]`)
+    pub roon_prefix: Vec<String>,
+
+    /// Extra arguments passed to the roon CLI before every command
+    pub roon_args: Vec<String>,
+
+    /// Seconds a roon CLI command may take before it's killed and reported
+    /// as timed out (0 waits as long as it takes)
+    pub roon_timeout_seconds: u64,
+
+    /// Roon Cores to switch between (`[[cores]]`); empty uses the roon
+    /// CLI's own Core
+    pub cores: Vec<CoreConfig>,
+
+    /// Log level for roon-tui's own messages (`RUST_LOG` takes precedence)
+    pub log_level: String,
+
+    /// Redraw rate while a track is playing (the progress bar ticks);
+    /// otherwise the UI only redraws on input and zone refreshes
+    pub max_fps: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            open_in: default_open_services(),
+            lyrics: LyricsConfig::default(),
+            volume: VolumeConfig::default(),
+            mouse: MouseConfig::default(),
+            screensaver: ScreensaverConfig::default(),
+            visualizer: VisualizerConfig::default(),
+            suspend: SuspendConfig::default(),
+            resume: ResumeConfig::default(),
+            credentials: CredentialsConfig::default(),
+            plugins: PluginsConfig::default(),
+            search: SearchConfig::default(),
+            for_you: ForYouConfig::default(),
+            stats: StatsConfig::default(),
+            announce: AnnounceConfig::default(),
+            media_keys: MediaKeysConfig::default(),
+            party: PartyConfig::default(),
+            keys: BTreeMap::new(),
+            keymap: KeymapScheme::Flat,
+            leader: "space".to_string(),
+            theme: "default".to_string(),
+            default_zone: None,
+            zones: ZonesConfig::default(),
+            follow_music: false,
+            album_shuffle: false,
+            terminal_title: false,
+            taskbar_progress: false,
+            show_hints: true,
+            ends_at: EndsAt::Track,
+            osc52: Osc52::Auto,
+            icons: IconSet::Auto,
+            colors: ColorSupport::Auto,
+            progress_bar: ProgressStyle::Gauge,
+            time_display: TimeDisplay::Elapsed,
+            countdown_seconds: 10,
+            confirm_quit: false,
+            update_check: false,
+            roon_command: "roon".to_string(),
+            roon_prefix: Vec::new(),
+            roon_args: Vec::new(),
+            roon_timeout_seconds: 10,
+            cores: Vec::new(),
+            log_level: "debug".to_string(),
+            max_fps: 20,
+        }
+    }
+}
+
+/// A Roon Core to switch between, e.g. at home and at the office
+///
+/// ```toml
+/// [[cores]]
+/// name = "Office"
+/// args = ["--core", "office.local"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CoreConfig {
+    pub name: String,
+    /// Replaces `roon_prefix` for this Core (e.g. ssh to a machine there)
+    pub prefix: Option<Vec<String>>,
+    /// Added after `roon_args` to reach this Core
+    pub args: Vec<String>,
+}
+
+/// How rebindable actions are reached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapScheme {
+    /// Every action has its own key
+    #[default]
+    Flat,
+    /// The leader key opens which-key menus whose keys run the actions
+    Leader,
+}
+
+/// Which end times the status bar shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EndsAt {
+    Off,
+    /// When the current track ends
+    #[default]
+    Track,
+    /// When the track and the rest of the queue end
+    Queue,
+}
+
+/// How the Now Playing progress bar is drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressStyle {
+    #[default]
+    Gauge,
+    /// Braille dots: half-cell resolution and a playhead marker
+    Braille,
+}
+
+/// What the time line above the progress bar counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeDisplay {
+    #[default]
+    Elapsed,
+    /// Time left in the track, as "−mm:ss"
+    Remaining,
+}
+
+/// Which glyphs the playback icons use
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// Nerd Font glyphs if the terminal seems to have them, otherwise emoji
+    #[default]
+    Auto,
+    Nerd,
+    Emoji,
+}
+
+/// Color depth to draw themes in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorSupport {
+    /// Detected from `COLORTERM`, `TERM` and `TERM_PROGRAM`
+    #[default]
+    Auto,
+    Truecolor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+/// When the clipboard goes through the terminal instead of a local tool
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Osc52 {
+    /// Over SSH, or when no clipboard tool is installed
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Zone list settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ZonesConfig {
+    /// Zones left out of every zone list: display names, or `/regex/`
+    /// patterns so new zones of the same kind stay hidden too
+    pub hidden: Vec<String>,
+
+    /// Display names: Roon zone name -> shown name (commands keep the Roon name)
+    pub aliases: BTreeMap<String, String>,
+
+    /// Pinned zone order by Roon zone name; unlisted zones follow in Roon's order
+    pub order: Vec<String>,
+
+    /// Pause the zone the selected zone was grouped into, when it's playing
+    /// there (the selected zone's outputs would play it otherwise)
+    pub pause_when_grouped: bool,
+}
+
+impl Default for ZonesConfig {
+    fn default() -> Self {
+        Self {
+            hidden: Vec::new(),
+            aliases: BTreeMap::new(),
+            order: Vec::new(),
+            pause_when_grouped: true,
+        }
+    }
+}
+
+/// Volume settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct VolumeConfig {
+    /// Adjust every output of a grouped zone together (proportionally to each
+    /// output's range) instead of only the first output
+    pub link_grouped: bool,
+
+    /// Volume set when playback starts from roon-tui on an idle zone: Roon
+    /// zone name -> level, in the outputs' own units (percent, or dB)
+    pub start_levels: BTreeMap<String, f64>,
+
+    /// Minutes a zone has to be stopped or paused to count as idle
+    pub idle_minutes: u64,
+}
+
+impl Default for VolumeConfig {
+    fn default() -> Self {
+        Self {
+            link_grouped: false,
+            start_levels: BTreeMap::new(),
+            idle_minutes: 30,
+        }
+    }
+}
+
+/// Mouse settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MouseConfig {
+    /// Capture mouse events (off by default, which keeps the terminal's own
+    /// text selection)
+    pub enabled: bool,
+    /// Rows the selection moves per scroll wheel step
+    pub scroll_rows: usize,
+}
+
+impl Default for MouseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            scroll_rows: 3,
+        }
+    }
+}
+
+/// Screensaver settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ScreensaverConfig {
+    /// Switch to the screensaver after a while without input
+    pub enabled: bool,
+    /// Minutes without input before the screensaver starts
+    pub idle_minutes: u64,
+}
+
+impl Default for ScreensaverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_minutes: 10,
+        }
+    }
+}
+
+/// System media key settings (macOS, through a compiled Swift helper)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MediaKeysConfig {
+    /// Let the keyboard's play/pause and skip keys control roon-tui
+    pub enabled: bool,
+}
+
+impl Default for MediaKeysConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// System suspend settings (logind on Linux, IOKit on macOS)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SuspendConfig {
+    /// Pause the watched zone before the system sleeps
+    pub pause: bool,
+    /// Resume it after wake (only if it was paused for the suspend)
+    pub resume: bool,
+    /// Treat locking the screen like a suspend and unlocking it like a wake
+    pub lock: bool,
+    /// Only for these zones (empty: any watched zone)
+    pub zones: Vec<String>,
+}
+
+impl SuspendConfig {
+    /// Whether suspend handling applies to a zone
+    pub fn applies_to(&self, zone_name: &str) -> bool {
+        self.zones.is_empty() || self.zones.iter().any(|z| z == zone_name)
+    }
+}
+
+/// Party mode settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartyConfig {
+    /// Passphrase that ends party mode (without one, holding the party
+    /// mode key does)
+    pub passphrase: Option<String>,
+}
+
+/// Resume settings for long-form audio
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ResumeConfig {
+    /// Remember positions and offer to resume
+    pub enabled: bool,
+    /// Only items at least this long (most music tracks are shorter)
+    pub min_minutes: u64,
+}
+
+impl Default for ResumeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_minutes: 20,
+        }
+    }
+}
+
+/// Spectrum visualizer settings
+///
+/// Audio is captured locally by cava, so this only makes sense for zones
+/// whose output plays on (or is monitored by) this machine.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct VisualizerConfig {
+    pub enabled: bool,
+    /// cava executable
+    pub command: String,
+    /// cava input method: "pulse", "pipewire", "alsa", ...
+    pub method: String,
+    /// Capture source for that method (e.g. a PulseAudio monitor, or "auto")
+    pub source: String,
+    /// Number of bars
+    pub bars: u16,
+    /// Height in rows
+    pub height: u16,
+    /// Zones playing on this machine (empty: show for every zone)
+    pub zones: Vec<String>,
+}
+
+impl Default for VisualizerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            command: "cava".to_string(),
+            method: "pulse".to_string(),
+            source: "auto".to_string(),
+            bars: 24,
+            height: 4,
+            zones: Vec::new(),
+        }
+    }
+}
+
+/// Plugin settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PluginsConfig {
+    /// Start the executables in the plugins directory
+    pub enabled: bool,
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Search settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Merge the library's results with those of the service sections
+    pub unified: bool,
+    /// Titles of the streaming service sections in Roon's search results
+    pub sections: Vec<String>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            unified: true,
+            sections: ["TIDAL", "Qobuz", "KKBOX"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// For You view settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ForYouConfig {
+    /// Titles of the levels listing mixes and recommendations, looked for
+    /// on the root level and in each streaming service section (the
+    /// `[search] sections`)
+    pub sections: Vec<String>,
+}
+
+impl Default for ForYouConfig {
+    fn default() -> Self {
+        Self {
+            sections: ["Daily Mixes", "My Mixes", "For You", "Recommended"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Listening statistics settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StatsConfig {
+    /// Record listening time per week
+    pub enabled: bool,
+    /// Show last week's summary at the first start of a new week
+    pub startup_summary: bool,
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            startup_summary: true,
+        }
+    }
+}
+
+/// Text-to-speech announcements of track changes in the selected zone
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AnnounceConfig {
+    pub enabled: bool,
+    /// TTS program and arguments; the text is written to its stdin
+    pub command: Vec<String>,
+    /// What to say (`{track}`, `{artist}` and `{album}` are filled in)
+    pub format: String,
+    /// Wait this long after a track change, so rapid skips aren't announced
+    pub delay_ms: u64,
+}
+
+impl Default for AnnounceConfig {
+    fn default() -> Self {
+        let command: &[&str] = if cfg!(target_os = "macos") {
+            &["say"]
+        } else {
+            &["espeak", "--stdin"]
+        };
+        Self {
+            enabled: false,
+            command: command.iter().map(|s| s.to_string()).collect(),
+            format: "Now playing {track} by {artist}".to_string(),
+            delay_ms: 1500,
+        }
+    }
+}
+
+/// Token storage settings
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CredentialsConfig {
+    /// Fall back to a plaintext `credentials.toml` when there's no OS keyring
+    pub plaintext: bool,
+}
+
+/// Lyrics lookup settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LyricsConfig {
+    /// Providers to try, in order: "local", "lrclib", "genius"
+    pub providers: Vec<String>,
+
+    /// Directory of `Artist - Track.lrc` / `.txt` files for the local provider
+    pub local_dir: Option<String>,
+
+    /// Genius API access token (the Genius provider is skipped without one)
+    ///
+    /// Still read for existing configs; new tokens go to the keyring.
+    pub genius_token: Option<String>,
+}
+
+impl Default for LyricsConfig {
+    fn default() -> Self {
+        Self {
+            providers: vec![
+                "local".to_string(),
+                "lrclib".to_string(),
+                "genius".to_string(),
+            ],
+            local_dir: None,
+            genius_token: None,
+        }
+    }
+}
+
+/// An external service that can be opened for the current track
+///
+/// The URL is a template: `{artist}`, `{track}` and `{album}` are replaced
+/// with the URL-encoded values of the track currently playing.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenService {
+    pub name: String,
+    pub url: String,
+}
+
+impl OpenService {
+    fn new(name: &str, url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            url: url.to_string(),
+        }
+    }
+}
+
+fn default_open_services() -> Vec<OpenService> {
+    vec![
+        OpenService::new("Last.fm (artist)", "https://www.last.fm/music/{artist}"),
+        OpenService::new(
+            "MusicBrainz (artist)",
+            "https://musicbrainz.org/search?type=artist&query={artist}",
+        ),
+        OpenService::new("song.link", "https://song.link/search?q={artist}%20{track}"),
+    ]
+}
+
+impl Config {
+    /// Path of the config file (if a config directory exists on this platform)
+    pub fn path() -> Option<PathBuf> {
+        crate::paths::config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Use a named profile: `profiles/<name>.toml` overrides the main file,
+    /// and settings changed in the app are saved to it (call before loading)
+    pub fn set_profile(name: &str) {
+        let _ = PROFILE.set(name.to_string());
+    }
+
+    /// Name of the active profile
+    pub fn profile() -> Option<&'static str> {
+        PROFILE.get().map(String::as_str)
+    }
+
+    /// Path of the active profile's file
+    pub fn profile_path() -> Option<PathBuf> {
+        let name = Self::profile()?;
+        crate::paths::config_dir().map(|dir| dir.join("profiles").join(format!("{name}.toml")))
+    }
+
+    /// Whether a config file exists (no file means this is the first run)
+    pub fn exists() -> bool {
+        Self::path().is_some_and(|path| path.exists())
+    }
+
+    /// Load the config file plus environment overrides, falling back to
+    /// defaults if the file is missing or invalid
+    ///
+    /// An invalid file's error is handed back to be logged, since the log
+    /// level itself comes from the config.
+    pub fn load() -> (Self, Option<anyhow::Error>) {
+        let (mut config, error) = match Self::load_checked() {
+            Ok(config) => (config.unwrap_or_default(), None),
+            Err(e) => (Self::default(), Some(e)),
+        };
+        config.apply_env();
+        (config, error)
+    }
+
+    /// Full roon CLI invocation: prefix, executable and extra arguments,
+    /// with a Core's own prefix and arguments when one is given
+    pub fn roon_invocation(&self, core: Option<&CoreConfig>) -> Vec<String> {
+        let mut invocation = core
+            .and_then(|core| core.prefix.clone())
+            .unwrap_or_else(|| self.roon_prefix.clone());
+        invocation.push(self.roon_command.clone());
+        invocation.extend(self.roon_args.iter().cloned());
+        if let Some(core) = core {
+            invocation.extend(core.args.iter().cloned());
+        }
+        invocation
+    }
+
+    /// The Core with this name, or the first one configured
+    pub fn core(&self, name: Option<&str>) -> Option<&CoreConfig> {
+        name.and_then(|name| self.cores.iter().find(|core| core.name == name))
+            .or_else(|| self.cores.first())
+    }
+
+    /// Apply `ROON_TUI_*` environment variables on top of the file, for
+    /// deployments where editing it is awkward (containers, services)
+    fn apply_env(&mut self) {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+        if let Some(zone) = var("ROON_TUI_ZONE") {
+            self.default_zone = Some(zone);
+        }
+        if let Some(theme) = var("ROON_TUI_THEME") {
+            self.theme = theme;
+        }
+        if let Some(level) = var("ROON_TUI_LOG_LEVEL") {
+            self.log_level = level;
+        }
+        if let Some(command) = var("ROON_TUI_ROON_COMMAND") {
+            self.roon_command = command;
+        }
+        if let Some(prefix) = var("ROON_TUI_ROON_PREFIX") {
+            self.roon_prefix = prefix.split_whitespace().map(String::from).collect();
+        }
+    }
+
+    /// Load the config file (with the active profile on top), returning
+    /// `None` if there is none and an error if invalid
+    pub fn load_checked() -> Result<Option<Self>> {
+        let base = Self::path()
+            .map(|path| read_table(&path))
+            .transpose()?
+            .flatten();
+        let profile = Self::profile_path()
+            .map(|path| read_table(&path))
+            .transpose()?
+            .flatten();
+
+        let table = match (base, profile) {
+            (None, None) => return Ok(None),
+            (Some(table), None) | (None, Some(table)) => table,
+            (Some(mut base), Some(profile)) => {
+                merge_tables(&mut base, profile);
+                base
+            }
+        };
+        let files: Vec<String> = [Self::path(), Self::profile_path()]
+            .into_iter()
+            .flatten()
+            .filter(|path| path.exists())
+            .map(|path| path.display().to_string())
+            .collect();
+        toml::Value::Table(table)
+            .try_into()
+            .map(Some)
+            .with_context(|| format!("Invalid config file {}", files.join(" + ")))
+    }
+
+    /// Write keybinding overrides to the `[keys]` table of the config file
+    pub fn save_keys(keys: &BTreeMap<String, Vec<String>>) -> Result<()> {
+        Self::update_file(|table| {
+            if keys.is_empty() {
+                table.remove("keys");
+            } else {
+                table.insert("keys".to_string(), toml::Value::try_from(keys)?);
+            }
+            Ok(())
+        })
+    }
+
+    /// Write the selected theme name to the config file
+    pub fn save_theme(name: &str) -> Result<()> {
+        Self::update_file(|table| {
+            table.insert("theme".to_string(), toml::Value::from(name));
+            Ok(())
+        })
+    }
+
+    /// Write the startup zone to the config file (creating it if needed)
+    pub fn save_default_zone(zone: Option<&str>) -> Result<()> {
+        Self::update_file(|table| {
+            if let Some(zone) = zone {
+                table.insert("default_zone".to_string(), toml::Value::from(zone));
+            }
+            Ok(())
+        })
+    }
+
+    /// Write the pinned zone order to the `[zones]` table
+    pub fn save_zone_order(order: &[String]) -> Result<()> {
+        Self::update_file(|table| {
+            let zones = table
+                .entry("zones")
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            let zones = zones.as_table_mut().context("`zones` is not a table")?;
+            zones.insert("order".to_string(), toml::Value::try_from(order)?);
+            Ok(())
+        })
+    }
+
+    /// Apply an edit to the config file's top-level table and write it back
+    ///
+    /// Other settings in the file are kept (comments and formatting are not).
+    fn update_file(edit: impl FnOnce(&mut toml::Table) -> Result<()>) -> Result<()> {
+        let path = Self::profile_path()
+            .or_else(Self::path)
+            .context("no config directory on this platform")?;
+        // A file that can't be read is left alone rather than overwritten
+        // with only the edited setting
+        let mut table = read_table(&path)?.unwrap_or_default();
+
+        edit(&mut table)?;
+
+        crate::paths::write_file(&path, toml::to_string(&table)?.as_bytes())
+    }
+}
+
+/// Read a TOML file's table, `None` if the file doesn't exist (and an
+/// error if it can't be read)
+fn read_table(path: &std::path::Path) -> Result<Option<toml::Table>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => contents
+            .parse()
+            .map(Some)
+            .with_context(|| format!("Invalid config file {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Can't read config file {}", path.display())),
+    }
+}
+
+/// Layer `overrides` onto `base`: nested tables are merged key by key,
+/// anything else is replaced
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(value)) => {
+                merge_tables(existing, value);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
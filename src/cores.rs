@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// What's remembered about the configured Cores
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct CoreState {
+    /// Name of the Core in use
+    current: Option<String>,
+    /// Core name -> zone last selected there
+    zones: BTreeMap<String, String>,
+}
+
+/// The Core in use and the zone last selected on each one, persisted as
+/// one JSON file so a restart comes back to them
+pub struct Cores {
+    path: Option<PathBuf>,
+    state: CoreState,
+}
+
+impl Cores {
+    /// Load the saved state (nothing remembered if there's none yet)
+    pub fn load() -> Self {
+        let path = crate::paths::state_dir().map(|dir| dir.join("cores.json"));
+        let state = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path, state }
+    }
+
+    /// Name of the Core last switched to
+    pub fn current(&self) -> Option<&str> {
+        self.state.current.as_deref()
+    }
+
+    /// Zone last selected on a Core
+    pub fn zone(&self, core: &str) -> Option<&str> {
+        self.state.zones.get(core).map(String::as_str)
+    }
+
+    pub fn set_current(&mut self, core: &str) {
+        self.state.current = Some(core.to_string());
+        self.save();
+    }
+
+    /// Remember the zone selected on a Core
+    pub fn set_zone(&mut self, core: &str, zone: &str) {
+        if self.zone(core) != Some(zone) {
+            self.state.zones.insert(core.to_string(), zone.to_string());
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = crate::paths::write_state(path, &self.state);
+        if let Err(e) = result {
+            tracing::warn!("Failed to save Core selection: {}", e);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::CredentialsConfig;
+
+/// Keyring service name the tokens are filed under
+const KEYRING_SERVICE: &str = "roon-tui";
+
+/// Integrations that need a token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Service {
+    Genius,
+    LastFm,
+    ListenBrainz,
+}
+
+impl Service {
+    pub const ALL: [Service; 3] = [Service::Genius, Service::LastFm, Service::ListenBrainz];
+
+    /// Account name in the keyring and key in the plaintext file
+    pub fn name(self) -> &'static str {
+        match self {
+            Service::Genius => "genius",
+            Service::LastFm => "lastfm",
+            Service::ListenBrainz => "listenbrainz",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Service::Genius => "Genius (lyrics)",
+            Service::LastFm => "Last.fm",
+            Service::ListenBrainz => "ListenBrainz",
+        }
+    }
+}
+
+/// Where a token is kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Store {
+    Keyring,
+    /// `credentials.toml`, only with `[credentials] plaintext = true`
+    Plaintext,
+}
+
+/// Look up a service's token: the OS keyring first, then the plaintext file
+pub fn get(service: Service, config: &CredentialsConfig) -> Option<String> {
+    locate(service, config).map(|(token, _)| token)
+}
+
+/// Where a service's token is stored, if anywhere
+pub fn location(service: Service, config: &CredentialsConfig) -> Option<Store> {
+    locate(service, config).map(|(_, store)| store)
+}
+
+fn locate(service: Service, config: &CredentialsConfig) -> Option<(String, Store)> {
+    if let Some(token) = keyring::get(service.name()) {
+        return Some((token, Store::Keyring));
+    }
+    if config.plaintext {
+        if let Some(token) = read_file().remove(service.name()) {
+            return Some((token, Store::Plaintext));
+        }
+    }
+    None
+}
+
+/// Store a token in the keyring, or in the plaintext file if there's no
+/// keyring and that was opted into
+pub fn set(service: Service, token: &str, config: &CredentialsConfig) -> Result<Store> {
+    match keyring::set(service.name(), token) {
+        Ok(()) => Ok(Store::Keyring),
+        Err(e) if config.plaintext => {
+            tracing::warn!(
+                "Keyring unavailable ({:#}), storing {} in a file",
+                e,
+                service.name()
+            );
+            let mut tokens = read_file();
+            tokens.insert(service.name().to_string(), token.to_string());
+            write_file(&tokens)?;
+            Ok(Store::Plaintext)
+        }
+        Err(e) => {
+            Err(e.context("no keyring (set `plaintext = true` under [credentials] to use a file)"))
+        }
+    }
+}
+
+/// Remove a token from wherever it's stored
+pub fn delete(service: Service, config: &CredentialsConfig) -> Result<()> {
+    match location(service, config) {
+        Some(Store::Keyring) => keyring::delete(service.name()),
+        Some(Store::Plaintext) => {
+            let mut tokens = read_file();
+            tokens.remove(service.name());
+            write_file(&tokens)
+        }
+        None => Ok(()),
+    }
+}
+
+fn file_path() -> Option<PathBuf> {
+    crate::paths::config_dir().map(|dir| dir.join("credentials.toml"))
+}
+
+fn read_file() -> BTreeMap<String, String> {
+    file_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Write the plaintext file, readable by the owner only
+fn write_file(tokens: &BTreeMap<String, String>) -> Result<()> {
+    let path = file_path().context("no config directory")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let contents = toml::to_string(tokens)?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&path)?.write_all(contents.as_bytes())?;
+    Ok(())
+}
+
+/// OS keyring through its command-line tool: `secret-tool` (libsecret) on
+/// Linux, `security` on macOS
+mod keyring {
+    use super::*;
+
+    pub fn get(account: &str) -> Option<String> {
+        let output = lookup_command(account).output().ok()?;
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !token.is_empty()).then_some(token)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn lookup_command(account: &str) -> Command {
+        let mut cmd = Command::new("security");
+        cmd.args([
+            "find-generic-password",
+            "-s",
+            KEYRING_SERVICE,
+            "-a",
+            account,
+            "-w",
+        ]);
+        cmd.stderr(Stdio::null());
+        cmd
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn lookup_command(account: &str) -> Command {
+        let mut cmd = Command::new("secret-tool");
+        cmd.args(["lookup", "service", KEYRING_SERVICE, "account", account]);
+        cmd.stderr(Stdio::null());
+        cmd
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn set(account: &str, token: &str) -> Result<()> {
+        // A trailing `-w` without a value makes `security` prompt for the
+        // token (and again to confirm it), so it goes in on stdin and never
+        // shows up in `ps`
+        let input = format!("{token}\n{token}\n");
+        run(
+            Command::new("security").args([
+                "add-generic-password",
+                "-U",
+                "-s",
+                KEYRING_SERVICE,
+                "-a",
+                account,
+                "-w",
+            ]),
+            Some(input.as_str()),
+        )
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn set(account: &str, token: &str) -> Result<()> {
+        // The token goes in on stdin so it never shows up in `ps`
+        let label = format!("roon-tui {}", account);
+        run(
+            Command::new("secret-tool").args([
+                "store",
+                "--label",
+                &label,
+                "service",
+                KEYRING_SERVICE,
+                "account",
+                account,
+            ]),
+            Some(token),
+        )
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn delete(account: &str) -> Result<()> {
+        run(
+            Command::new("security").args([
+                "delete-generic-password",
+                "-s",
+                KEYRING_SERVICE,
+                "-a",
+                account,
+            ]),
+            None,
+        )
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn delete(account: &str) -> Result<()> {
+        run(
+            Command::new("secret-tool").args([
+                "clear",
+                "service",
+                KEYRING_SERVICE,
+                "account",
+                account,
+            ]),
+            None,
+        )
+    }
+
+    fn run(cmd: &mut Command, stdin: Option<&str>) -> Result<()> {
+        let mut child = cmd
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("{:?} not found", cmd.get_program()))?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(())
+    }
+}
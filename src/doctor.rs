@@ -0,0 +1,193 @@
+use std::path::Path;
+
+use crossterm::style::Stylize;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use ratatui_image::picker::{Picker, ProtocolType};
+
+use crate::cli::Args;
+use crate::clipboard::{self, Method};
+use crate::colors::ColorDepth;
+use crate::config::Config;
+use crate::cores::Cores;
+use crate::paths;
+use crate::roon;
+
+/// Outcome of a single check
+enum Status {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// Run all diagnostic checks and print a colored report
+///
+/// Returns whether every check passed (warnings don't count as failures).
+pub fn run(args: &Args) -> bool {
+    println!("{}\n", "roon-tui doctor".bold());
+    let mut config = Config::load().0;
+    args.apply(&mut config);
+    let cores = Cores::load();
+    let core = config.core(cores.current());
+    roon::set_command(&config.roon_invocation(core));
+    roon::set_timeout(std::time::Duration::from_secs(config.roon_timeout_seconds));
+    // With several Cores configured, the one checked is named
+    let core_label = core.map_or("Roon Core".to_string(), |core| {
+        format!("Core {}", core.name)
+    });
+    let mut passed = true;
+    let mut report = |status: Status, label: &str, detail: String| {
+        let icon = match status {
+            Status::Ok => "✓".green(),
+            Status::Warn => "!".yellow(),
+            Status::Fail => {
+                passed = false;
+                "✗".red()
+            }
+        };
+        println!("  {} {:<20} {}", icon, label, detail);
+    };
+
+    // roon CLI
+    match roon::detect() {
+        Ok(Some(cli)) if cli.version.is_some() => report(Status::Ok, "roon CLI", cli.summary()),
+        Ok(Some(cli)) => report(
+            Status::Warn,
+            "roon CLI",
+            format!("{}, commands are probed as used", cli.summary()),
+        ),
+        Ok(None) => report(
+            Status::Fail,
+            "roon CLI",
+            "not found (is `roon` on your PATH, or `roon_command` set?)".to_string(),
+        ),
+        Err(e) => report(Status::Fail, "roon CLI", e.to_string()),
+    }
+
+    // Core reachability
+    match roon::get_zones() {
+        Ok((zones, warnings)) if warnings.is_empty() => {
+            report(Status::Ok, &core_label, format!("{} zones", zones.len()))
+        }
+        Ok((zones, warnings)) => report(
+            Status::Warn,
+            &core_label,
+            format!(
+                "{} zones; {} (is roon-tui older than the CLI?)",
+                zones.len(),
+                warnings.join("; ")
+            ),
+        ),
+        Err(e) => report(
+            Status::Fail,
+            &core_label,
+            format!("{e:#} (is `roon daemon` running and authorized?)"),
+        ),
+    }
+
+    // Terminal graphics (the query needs raw mode to read the reply)
+    let picker = enable_raw_mode().ok().and_then(|_| {
+        let picker = Picker::from_query_stdio().ok();
+        let _ = disable_raw_mode();
+        picker
+    });
+    match picker.map(|p| p.protocol_type()) {
+        Some(ProtocolType::Halfblocks) | None => report(
+            Status::Warn,
+            "Album art",
+            "no graphics protocol detected, using half blocks".to_string(),
+        ),
+        Some(protocol) => report(Status::Ok, "Album art", format!("{protocol:?} protocol")),
+    }
+
+    // Colors (what the environment claims; the terminal can't be asked)
+    match ColorDepth::resolve(config.colors) {
+        ColorDepth::TrueColor => report(Status::Ok, "Colors", "truecolor".to_string()),
+        depth => report(
+            Status::Warn,
+            "Colors",
+            format!(
+                "{}, theme colors are approximated (set `colors` if that's wrong)",
+                depth.label()
+            ),
+        ),
+    }
+
+    // Clipboard (OSC 52 can't be probed: the terminal may ignore it silently)
+    match clipboard::method(config.osc52) {
+        Some(Method::Native(program)) => report(Status::Ok, "Clipboard", program.to_string()),
+        Some(Method::Osc52) => report(
+            Status::Ok,
+            "Clipboard",
+            "OSC 52 (the terminal must allow clipboard writes)".to_string(),
+        ),
+        None => report(
+            Status::Warn,
+            "Clipboard",
+            "no clipboard tool found and `osc52 = \"never\"`".to_string(),
+        ),
+    }
+
+    // Config file
+    let config_path = Config::path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+    match Config::load_checked() {
+        Ok(Some(_)) => report(Status::Ok, "Config", config_path),
+        Ok(None) => report(
+            Status::Ok,
+            "Config",
+            format!("{config_path} (not created, using defaults)"),
+        ),
+        Err(e) => report(Status::Fail, "Config", format!("{e:#}")),
+    }
+    if let Some(path) = Config::profile_path() {
+        if path.exists() {
+            report(Status::Ok, "Profile", path.display().to_string());
+        } else {
+            report(
+                Status::Warn,
+                "Profile",
+                format!("{} (not found, using the main config)", path.display()),
+            );
+        }
+    }
+
+    // Writable directories
+    for (label, dir) in [
+        ("Cache dir", paths::cache_dir()),
+        ("State dir", paths::state_dir()),
+    ] {
+        match dir {
+            Some(dir) => match check_writable(&dir) {
+                Ok(()) => report(Status::Ok, label, dir.display().to_string()),
+                Err(e) => report(Status::Fail, label, format!("{}: {e}", dir.display())),
+            },
+            None => report(
+                Status::Warn,
+                label,
+                "not available on this platform".to_string(),
+            ),
+        }
+    }
+    let log_file = paths::log_file();
+    let log_dir = log_file.parent().unwrap_or(Path::new("/"));
+    match check_writable(log_dir) {
+        Ok(()) => report(Status::Ok, "Log file", log_file.display().to_string()),
+        Err(e) => report(
+            Status::Fail,
+            "Log file",
+            format!("{}: {e}", log_file.display()),
+        ),
+    }
+
+    println!();
+    passed
+}
+
+/// Check that a directory exists (creating it if needed) and accepts new files
+fn check_writable(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".roon-tui-doctor");
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(probe)
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use std::process::{Command, Stdio};
+
+use crate::roon::NowPlaying;
+
+/// Build a service URL by filling `{artist}`, `{track}` and `{album}` placeholders
+pub fn build_url(template: &str, np: &NowPlaying) -> String {
+    let encode = |s: &str| utf8_percent_encode(s, NON_ALPHANUMERIC).to_string();
+    template
+        .replace("{artist}", &encode(&np.artist))
+        .replace("{track}", &encode(&np.track))
+        .replace("{album}", &encode(&np.album))
+}
+
+/// Open a URL with the system opener (xdg-open, open, or start)
+pub fn open_url(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+
+    // Detach from the terminal so the opener can't write over the TUI
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Reap the opener in the background so it doesn't linger as a zombie
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
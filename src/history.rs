@@ -0,0 +1,230 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock;
+use crate::roon::NowPlaying;
+
+/// How often listening time is written out
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A track counts as played after this much listening (seconds)
+const PLAYED_AFTER: f64 = 30.0;
+
+/// Weeks of history kept
+const KEEP_WEEKS: i64 = 52;
+
+/// Listening totals of one week
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Week {
+    seconds: f64,
+    tracks: u32,
+    /// Artist -> seconds listened
+    artists: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Stored {
+    /// Monday (days since the epoch, local time) -> totals
+    weeks: BTreeMap<i64, Week>,
+    /// Day (days since the epoch, local time) -> seconds listened, for the
+    /// heatmap (kept for good; it's one number a day)
+    days: BTreeMap<i64, f64>,
+    /// Last week whose summary was shown at startup
+    summary_shown: Option<i64>,
+    /// Album key -> tracks played, for sorting discographies (kept for good)
+    albums: BTreeMap<String, u32>,
+}
+
+/// One week's listening, for display
+#[derive(Debug)]
+pub struct WeekSummary {
+    /// Monday of the week (days since the epoch)
+    pub start: i64,
+    pub seconds: f64,
+    pub tracks: u32,
+    pub top_artist: Option<String>,
+}
+
+impl WeekSummary {
+    /// "7h 12m · 96 tracks · top artist: …"
+    pub fn text(&self) -> String {
+        let minutes = (self.seconds / 60.0) as u64;
+        let mut text = format!(
+            "{}h {:02}m · {} track{}",
+            minutes / 60,
+            minutes % 60,
+            self.tracks,
+            if self.tracks == 1 { "" } else { "s" }
+        );
+        if let Some(artist) = &self.top_artist {
+            text.push_str(&format!(" · top artist: {}", artist));
+        }
+        text
+    }
+}
+
+/// Weekly listening time per artist, aggregated from what the zones play
+///
+/// Only totals are kept, in one JSON file in the state directory; nothing
+/// is sent anywhere.
+pub struct ListeningHistory {
+    path: Option<PathBuf>,
+    stored: Stored,
+    /// Zone id -> (item key, seconds listened) of what it's playing
+    current: HashMap<String, (String, f64)>,
+    changed: bool,
+    saved_at: Instant,
+}
+
+impl ListeningHistory {
+    /// Load saved totals (empty if there are none yet)
+    pub fn load() -> Self {
+        let path = crate::paths::state_dir().map(|dir| dir.join("listening.json"));
+        let stored = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            stored,
+            current: HashMap::new(),
+            changed: false,
+            saved_at: Instant::now(),
+        }
+    }
+
+    /// Add `elapsed` of listening to what a zone is playing
+    pub fn add(&mut self, zone_id: &str, np: &NowPlaying, elapsed: Duration) {
+        let key = crate::resume::item_key(np);
+        let entry = self
+            .current
+            .entry(zone_id.to_string())
+            .or_insert_with(|| (key.clone(), 0.0));
+        if entry.0 != key {
+            *entry = (key, 0.0);
+        }
+        let before = entry.1;
+        entry.1 += elapsed.as_secs_f64();
+        let counted = before < PLAYED_AFTER && entry.1 >= PLAYED_AFTER;
+
+        *self.stored.days.entry(clock::local_day()).or_default() += elapsed.as_secs_f64();
+        let week = self.stored.weeks.entry(this_week()).or_default();
+        week.seconds += elapsed.as_secs_f64();
+        if counted {
+            week.tracks += 1;
+            if !np.album.is_empty() {
+                *self
+                    .stored
+                    .albums
+                    .entry(album_key(&np.artist, &np.album))
+                    .or_default() += 1;
+            }
+        }
+        if !np.artist.is_empty() {
+            *week.artists.entry(np.artist.clone()).or_default() += elapsed.as_secs_f64();
+        }
+        self.changed = true;
+        if self.saved_at.elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    /// Weeks with any listening, most recent first
+    pub fn weeks(&self) -> Vec<WeekSummary> {
+        self.stored
+            .weeks
+            .iter()
+            .rev()
+            .filter(|(_, week)| week.seconds >= 60.0)
+            .map(|(&start, week)| WeekSummary {
+                start,
+                seconds: week.seconds,
+                tracks: week.tracks,
+                top_artist: week
+                    .artists
+                    .iter()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map(|(artist, _)| artist.clone()),
+            })
+            .collect()
+    }
+
+    /// Seconds listened on a day
+    pub fn day(&self, day: i64) -> f64 {
+        self.stored.days.get(&day).copied().unwrap_or_default()
+    }
+
+    /// Tracks played of an artist's album
+    pub fn album_plays(&self, artist: &str, album: &str) -> u32 {
+        let plays = self.stored.albums.get(&album_key(artist, album));
+        plays.copied().unwrap_or_default()
+    }
+
+    /// First year with any listening
+    pub fn first_year(&self) -> Option<i64> {
+        let (&day, _) = self.stored.days.first_key_value()?;
+        Some(clock::civil_date(day).0)
+    }
+
+    /// Whether last week's summary is yet to be shown at startup (it then
+    /// counts as shown)
+    pub fn take_summary_due(&mut self) -> bool {
+        let last_week = this_week() - 7;
+        if self.stored.summary_shown >= Some(last_week)
+            || !self.weeks().iter().any(|w| w.start == last_week)
+        {
+            return false;
+        }
+        self.stored.summary_shown = Some(last_week);
+        self.changed = true;
+        true
+    }
+
+    /// Write changes to disk, dropping weeks past `KEEP_WEEKS`
+    pub fn save(&mut self) {
+        self.saved_at = Instant::now();
+        let Some(path) = &self.path else {
+            return;
+        };
+        if !self.changed {
+            return;
+        }
+        let oldest = this_week() - KEEP_WEEKS * 7;
+        self.stored.weeks.retain(|&start, _| start >= oldest);
+        let result = crate::paths::write_state(path, &self.stored);
+        match result {
+            Ok(()) => self.changed = false,
+            Err(e) => tracing::warn!("Failed to save listening history: {}", e),
+        }
+    }
+}
+
+/// Key of an album in the play counts, matching either case
+fn album_key(artist: &str, album: &str) -> String {
+    format!("{}\n{}", artist, album).to_lowercase()
+}
+
+/// Monday of the current week (days since the epoch, local time)
+pub fn this_week() -> i64 {
+    week_of(clock::local_day())
+}
+
+/// Monday of the week holding `day`
+pub fn week_of(day: i64) -> i64 {
+    // The epoch was a Thursday
+    day - (day + 3).rem_euclid(7)
+}
+
+/// Label of a week in the stats popup
+pub fn week_label(start: i64) -> String {
+    match this_week() - start {
+        0 => "This week".to_string(),
+        7 => "Last week".to_string(),
+        _ => format!("Week of {}", clock::day_label(start)),
+    }
+}
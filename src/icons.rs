@@ -0,0 +1,93 @@
+use std::process::Command;
+
+use crate::config::IconSet;
+
+/// Glyphs for playback state, modes and volume
+#[derive(Debug)]
+pub struct Icons {
+    pub playing: &'static str,
+    pub paused: &'static str,
+    pub stopped: &'static str,
+    pub loading: &'static str,
+    pub shuffle: &'static str,
+    pub loop_all: &'static str,
+    pub loop_one: &'static str,
+    pub radio: &'static str,
+    pub volume: &'static str,
+    pub muted: &'static str,
+    /// Placeholder as wide as a mode icon, for modes that are off
+    pub blank: &'static str,
+}
+
+/// Emoji and symbols most fonts have (mode icons are double-width)
+pub const EMOJI: Icons = Icons {
+    playing: "▶",
+    paused: "⏸",
+    stopped: "⏹",
+    loading: "⏳",
+    shuffle: "🔀",
+    loop_all: "🔁",
+    loop_one: "🔂",
+    radio: "📻",
+    volume: "🔊",
+    muted: "🔇",
+    blank: "  ",
+};
+
+/// Nerd Font glyphs (single-width, and monochrome so they follow the theme)
+pub const NERD_FONT: Icons = Icons {
+    playing: "\u{f04b}",
+    paused: "\u{f04c}",
+    stopped: "\u{f04d}",
+    loading: "\u{f252}",
+    shuffle: "\u{f074}",
+    loop_all: "\u{f0456}",
+    loop_one: "\u{f0458}",
+    radio: "\u{f0439}",
+    volume: "\u{f028}",
+    muted: "\u{f026}",
+    blank: " ",
+};
+
+/// Icons for the configured set, probing for a Nerd Font with "auto"
+pub fn resolve(set: IconSet) -> &'static Icons {
+    let nerd = match set {
+        IconSet::Nerd => true,
+        IconSet::Emoji => false,
+        IconSet::Auto => has_nerd_font(),
+    };
+    if nerd {
+        &NERD_FONT
+    } else {
+        &EMOJI
+    }
+}
+
+/// Whether the terminal can draw Nerd Font glyphs: it bundles the symbols
+/// (WezTerm, Ghostty, Kitty), or a Nerd Font is installed
+///
+/// An installed font may not be the terminal's, so this is a best guess;
+/// `icons = "emoji"` overrides it.
+fn has_nerd_font() -> bool {
+    let term = std::env::var("TERM").unwrap_or_default();
+    let program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+    if matches!(term.as_str(), "xterm-kitty" | "xterm-ghostty")
+        || matches!(program.as_str(), "WezTerm" | "ghostty")
+    {
+        return true;
+    }
+    if cfg!(target_os = "macos") {
+        let dirs = [dirs::font_dir(), Some("/Library/Fonts".into())];
+        return dirs.into_iter().flatten().any(|dir| {
+            std::fs::read_dir(dir).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|e| e.file_name().to_string_lossy().contains("NerdFont"))
+            })
+        });
+    }
+    Command::new("fc-list")
+        .args([":", "family"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("Nerd Font"))
+}
@@ -1,6 +1,8 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 
-use crate::app::{App, Popup, View};
+use crate::app::{App, HelpInput, HelpPage, Popup, View};
+use crate::keymap::{Binding, LeaderMenu, Scope};
 
 /// Action to perform based on input
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,10 +11,88 @@ pub enum Action {
     PlayPause,
     ShowHelp,
     ShowZoneSelector,
+    ShowCoreSwitcher,
+    ShowDspPresets,
+    SelectDspPreset,
+    ShowOpenIn,
+    OpenInService,
+    ShowLyrics,
+    ShowVolume,
+    ToggleVolumeLink,
+    ShowThemeSwitcher,
+    ConfirmTheme,
+    ShowStats,
+    CopyTrackInfo,
+    BrowseCurrentAlbum,
+    BrowseGenres,
+    BrowseComposers,
+    ShowItemDetail,
+    DedupeQueue,
+    ToggleParty,
+    PartyChar(char),
+    PartyBackspace,
+    PartySubmit,
+    PlaySimilar,
+    SortDiscography,
+    SurpriseMe,
+    ToggleAlbumShuffle,
+    CycleAbRepeat,
+    ToggleTimeDisplay,
+    StatsPrevYear,
+    StatsNextYear,
+    ToggleMetrics,
+    ToggleSplitView,
+    ToggleMiniPlayer,
+    ToggleZenMode,
+    ToggleFollow,
+    HelpSwitchPage,
+    HelpStartFilter,
+    HelpFilterChar(char),
+    HelpFilterBackspace,
+    HelpFilterDone,
+    HelpFilterClear,
+    HelpStartLookup,
+    HelpLookup(KeyEvent),
+    OpenLeaderMenu(LeaderMenu),
+    ForceQuit,
+    MoveZoneUp,
+    ToggleOutputMute,
+    ShowTrim,
+    ShowOutputs,
+    FollowSuccessorZone,
+    TrimUp,
+    TrimDown,
+    MoveZoneDown,
+    ShowFullscreenArt,
+    CloseFullscreenArt,
+    WakeScreensaver,
+    ResumePlayback,
+    /// An action registered by a plugin (index into `Plugins::actions`)
+    RunPlugin(usize),
+    ShowCredentials,
+    CredentialsEdit,
+    CredentialsChar(char),
+    CredentialsBackspace,
+    CredentialsSubmit,
+    CredentialsCancel,
+    CredentialsDelete,
+    ShowFocus,
+    FocusEdit,
+    FocusChar(char),
+    FocusBackspace,
+    FocusSubmit,
+    FocusCancel,
+    FocusClear,
+    FocusApply,
+    // Onboarding
+    OnboardingNext,
+    OnboardingRetry,
+    OnboardingSkip,
     ClosePopup,
     SelectUp,
     SelectDown,
     SelectZone,
+    SelectCore,
     // Playback controls
     NextTrack,
     PrevTrack,
@@ -22,139 +102,270 @@ pub enum Action {
     VolumeUp,
     VolumeDown,
     ToggleMute,
+    CycleLeveling,
     // View switching
     SwitchToNowPlaying,
     SwitchToBrowse,
     SwitchToSearch,
+    SwitchToQueue,
+    SwitchToSettings,
+    SwitchToForYou,
+    // Keybinding editor
+    StartRebind,
+    RebindKey(KeyEvent),
+    CancelRebind,
+    ResetBinding,
     // Browse/search navigation
     BrowseSelect,
     BrowseBack,
+    BrowseRefresh,
+    // Error screen recovery
+    ErrorRetry,
+    ErrorRoot,
+    OpenLog,
     SearchChar(char),
     SearchBackspace,
     SearchSubmit,
     SearchActivate,
+    // For You
+    ForYouPlay,
+    ForYouQueue,
+    // Mouse
+    ScrollUp(usize),
+    ScrollDown(usize),
+    /// Press or drag on the progress bar at a column
+    SeekDrag(u16),
+    SeekRelease,
     None,
 }
 
 /// Handle key events and return the action to perform
 pub fn handle_key(key: KeyEvent, app: &App) -> Action {
+    // Any key wakes the screensaver without doing anything else
+    if app.screensaver.is_some() {
+        return Action::WakeScreensaver;
+    }
+
     // Handle popups first
     if let Some(popup) = &app.popup {
-        return handle_popup_key(key, popup);
+        return handle_popup_key(key, popup, app);
+    }
+
+    // Any key leaves full-screen album art
+    if app.fullscreen_art {
+        return Action::CloseFullscreenArt;
     }
 
     // Check for Ctrl+C to quit
     if key.modifiers.contains(KeyModifiers::CONTROL) {
         if let KeyCode::Char('c') = key.code {
-            return Action::Quit;
+            return Action::ForceQuit;
         }
     }
 
-    // Dispatch by view
-    match app.view {
-        View::NowPlaying => handle_now_playing_key(key),
-        View::Browse => handle_browse_key(key),
-        View::Search => handle_search_key(key, app),
+    // Keybinding editor waiting for a new key
+    if app.view == View::Settings && app.settings.capturing {
+        return match key.code {
+            KeyCode::Esc => Action::CancelRebind,
+            _ => Action::RebindKey(key),
+        };
+    }
+
+    // Search text input takes every key
+    if app.view == View::Search && app.search.input_active {
+        return handle_search_input_key(key);
+    }
+
+    // Leader scheme: the leader opens the menus, which run the bindings
+    if let Some(menu) = app.leader_menu {
+        return app.keymap.leader_action(menu, key, app.view);
+    }
+    if app.keymap.is_leader(key) {
+        return Action::OpenLeaderMenu(LeaderMenu::Root);
     }
+
+    // View-specific fixed keys first, then the (rebindable) keymap
+    let view_action = match app.view {
+        View::NowPlaying => Action::None,
+        view if app.list_error().is_some() => handle_error_key(key, view),
+        view => view_action(key, view),
+    };
+    if view_action != Action::None || app.keymap.uses_leader() {
+        return view_action;
+    }
+
+    // Plugin keys never shadow built-in ones
+    app.keymap
+        .action_for(key, app.view)
+        .or_else(|| app.plugins.action_for(key).map(Action::RunPlugin))
+        .unwrap_or(Action::None)
 }
 
-/// Handle keys in Now Playing view
-fn handle_now_playing_key(key: KeyEvent) -> Action {
-    match key.code {
-        // Global
-        KeyCode::Char('q') => Action::Quit,
-        KeyCode::Char('?') => Action::ShowHelp,
-        KeyCode::Char('z') => Action::ShowZoneSelector,
-        // Playback
-        KeyCode::Char(' ') => Action::PlayPause,
-        KeyCode::Char('n') => Action::NextTrack,
-        KeyCode::Char('p') => Action::PrevTrack,
-        KeyCode::Char('s') => Action::ToggleShuffle,
-        KeyCode::Char('l') => Action::CycleLoop,
-        KeyCode::Char('r') => Action::ToggleRadio,
-        KeyCode::Char('+') | KeyCode::Char('=') => Action::VolumeUp,
-        KeyCode::Char('-') => Action::VolumeDown,
-        KeyCode::Char('m') => Action::ToggleMute,
-        // View switching
-        KeyCode::Char('1') => Action::SwitchToNowPlaying,
-        KeyCode::Char('2') => Action::SwitchToBrowse,
-        KeyCode::Char('3') | KeyCode::Char('/') => Action::SwitchToSearch,
+/// Handle mouse events: the wheel scrolls lists, or changes volume over the
+/// Now Playing volume display, and dragging on the progress bar seeks
+pub fn handle_mouse(event: MouseEvent, app: &App) -> Action {
+    if app.screensaver.is_some() {
+        return Action::WakeScreensaver;
+    }
+
+    if app.view == View::Settings && app.settings.capturing {
+        return Action::None;
+    }
+
+    let position = Position::new(event.column, event.row);
+    let now_playing = app.popup.is_none() && app.view == View::NowPlaying && !app.mini_player;
+    let over_volume = now_playing && app.hit_areas.volume.contains(position);
+    let over_progress = now_playing && app.hit_areas.progress.contains(position);
+    let over_time = now_playing && app.hit_areas.time.contains(position);
+    let dragging = app.seek_drag.is_some();
+    let rows = app.config.mouse.scroll_rows;
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) if over_progress => Action::SeekDrag(event.column),
+        MouseEventKind::Down(MouseButton::Left) if over_time => Action::ToggleTimeDisplay,
+        MouseEventKind::Drag(MouseButton::Left) if dragging => Action::SeekDrag(event.column),
+        MouseEventKind::Up(MouseButton::Left) if dragging => Action::SeekRelease,
+        MouseEventKind::ScrollUp if over_volume => Action::VolumeUp,
+        MouseEventKind::ScrollDown if over_volume => Action::VolumeDown,
+        MouseEventKind::ScrollUp => Action::ScrollUp(rows),
+        MouseEventKind::ScrollDown => Action::ScrollDown(rows),
         _ => Action::None,
     }
 }
 
-/// Handle keys in Browse view
+/// What a view's fixed keys do with a key press (`Action::None` for keys
+/// left to the keymap)
+pub fn view_action(key: KeyEvent, view: View) -> Action {
+    match view {
+        View::NowPlaying => Action::None,
+        View::Browse => handle_browse_key(key),
+        View::Search => handle_search_key(key),
+        View::Queue => handle_queue_key(key),
+        View::Settings => handle_settings_key(key),
+        View::ForYou => handle_for_you_key(key),
+    }
+}
+
+/// Handle navigation keys in Browse view
 fn handle_browse_key(key: KeyEvent) -> Action {
     match key.code {
-        // Global
-        KeyCode::Char('q') => Action::Quit,
-        KeyCode::Char('?') => Action::ShowHelp,
-        KeyCode::Char('z') => Action::ShowZoneSelector,
-        // Navigation
         KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
         KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
         KeyCode::Enter => Action::BrowseSelect,
+        KeyCode::Char('i') => Action::ShowItemDetail,
+        KeyCode::Char('f') => Action::ShowFocus,
+        KeyCode::Char('r') if key.modifiers.is_empty() => Action::PlaySimilar,
+        KeyCode::Char('o') => Action::SortDiscography,
         KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') => Action::BrowseBack,
-        // Playback
-        KeyCode::Char(' ') => Action::PlayPause,
-        KeyCode::Char('n') => Action::NextTrack,
-        KeyCode::Char('p') => Action::PrevTrack,
-        KeyCode::Char('+') | KeyCode::Char('=') => Action::VolumeUp,
-        KeyCode::Char('-') => Action::VolumeDown,
-        KeyCode::Char('m') => Action::ToggleMute,
-        // View switching
-        KeyCode::Char('1') => Action::SwitchToNowPlaying,
-        KeyCode::Char('2') => Action::SwitchToBrowse,
-        KeyCode::Char('3') | KeyCode::Char('/') => Action::SwitchToSearch,
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Action::BrowseRefresh
+        }
         _ => Action::None,
     }
 }
 
-/// Handle keys in Search view
-fn handle_search_key(key: KeyEvent, app: &App) -> Action {
-    if app.search.input_active {
-        // Text input mode
+/// Handle keys on the error screen of a browse-like view: recovery keys,
+/// then the view's own
+fn handle_error_key(key: KeyEvent, view: View) -> Action {
+    if key.modifiers.is_empty() {
         match key.code {
-            KeyCode::Esc => Action::BrowseBack,
-            KeyCode::Enter => Action::SearchSubmit,
-            KeyCode::Backspace => Action::SearchBackspace,
-            KeyCode::Char(c) => Action::SearchChar(c),
-            _ => Action::None,
+            KeyCode::Char('r') => return Action::ErrorRetry,
+            KeyCode::Char('b') => return Action::ErrorRoot,
+            KeyCode::Char('d') => return Action::OpenLog,
+            _ => {}
         }
-    } else {
-        // Result navigation mode
-        match key.code {
-            // Global
-            KeyCode::Char('q') => Action::Quit,
-            KeyCode::Char('?') => Action::ShowHelp,
-            KeyCode::Char('z') => Action::ShowZoneSelector,
-            // Navigation
-            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
-            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
-            KeyCode::Enter => Action::BrowseSelect,
-            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('h') => Action::BrowseBack,
-            KeyCode::Char('/') => Action::SearchActivate,
-            // Playback
-            KeyCode::Char(' ') => Action::PlayPause,
-            KeyCode::Char('n') => Action::NextTrack,
-            KeyCode::Char('p') => Action::PrevTrack,
-            KeyCode::Char('+') | KeyCode::Char('=') => Action::VolumeUp,
-            KeyCode::Char('-') => Action::VolumeDown,
-            KeyCode::Char('m') => Action::ToggleMute,
-            // View switching
-            KeyCode::Char('1') => Action::SwitchToNowPlaying,
-            KeyCode::Char('2') => Action::SwitchToBrowse,
-            KeyCode::Char('3') => Action::SwitchToSearch,
-            _ => Action::None,
+    }
+    match view {
+        View::Search => handle_search_key(key),
+        View::ForYou => handle_for_you_key(key),
+        _ => handle_browse_key(key),
+    }
+}
+
+/// Handle keys in Search view while typing a query
+fn handle_search_input_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc => Action::BrowseBack,
+        KeyCode::Enter => Action::SearchSubmit,
+        KeyCode::Backspace => Action::SearchBackspace,
+        KeyCode::Char(c) => Action::SearchChar(c),
+        _ => Action::None,
+    }
+}
+
+/// Handle navigation keys in Search view results
+fn handle_search_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('/') => Action::SearchActivate,
+        _ => handle_browse_key(key),
+    }
+}
+
+/// Handle navigation keys in Queue view
+fn handle_queue_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+        KeyCode::Char('i') | KeyCode::Enter => Action::ShowItemDetail,
+        KeyCode::Char('D') => Action::DedupeQueue,
+        KeyCode::Esc => Action::SwitchToNowPlaying,
+        _ => Action::None,
+    }
+}
+
+/// Handle keys in the For You view
+fn handle_for_you_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+        KeyCode::Enter => Action::ForYouPlay,
+        KeyCode::Char('a') => Action::ForYouQueue,
+        KeyCode::Char('i') => Action::ShowItemDetail,
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Action::BrowseRefresh
         }
+        KeyCode::Esc => Action::SwitchToNowPlaying,
+        _ => Action::None,
+    }
+}
+
+/// Handle keys in the Settings (keybinding editor) view
+fn handle_settings_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+        KeyCode::Enter => Action::StartRebind,
+        KeyCode::Backspace | KeyCode::Delete => Action::ResetBinding,
+        KeyCode::Char('c') => Action::ShowCredentials,
+        KeyCode::Esc => Action::SwitchToNowPlaying,
+        _ => Action::None,
     }
 }
 
 /// Handle keys when a popup is shown
-fn handle_popup_key(key: KeyEvent, popup: &Popup) -> Action {
+fn handle_popup_key(key: KeyEvent, popup: &Popup, app: &App) -> Action {
     match popup {
+        Popup::Help if app.help_input == HelpInput::Lookup => Action::HelpLookup(key),
+        Popup::Help if app.help_input == HelpInput::Filter => match key.code {
+            KeyCode::Esc => Action::HelpFilterClear,
+            KeyCode::Enter => Action::HelpFilterDone,
+            KeyCode::Backspace => Action::HelpFilterBackspace,
+            KeyCode::Char(c) => Action::HelpFilterChar(c),
+            _ => Action::None,
+        },
         Popup::Help => match key.code {
+            KeyCode::Esc if !app.help_filter.is_empty() => Action::HelpFilterClear,
+            KeyCode::Char('/') => Action::HelpStartFilter,
+            KeyCode::Char('r') => Action::HelpStartLookup,
             KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Tab
+            | KeyCode::BackTab
+            | KeyCode::Char('h')
+            | KeyCode::Char('l')
+            | KeyCode::Left
+            | KeyCode::Right => Action::HelpSwitchPage,
             _ => Action::None,
         },
         Popup::ZoneSelector => match key.code {
@@ -162,38 +373,283 @@ fn handle_popup_key(key: KeyEvent, popup: &Popup) -> Action {
             KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
             KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
             KeyCode::Enter => Action::SelectZone,
+            KeyCode::Char('K') => Action::MoveZoneUp,
+            KeyCode::Char('J') => Action::MoveZoneDown,
+            _ => Action::None,
+        },
+        Popup::CoreSwitcher => match key.code {
+            KeyCode::Esc => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Enter => Action::SelectCore,
+            _ => Action::None,
+        },
+        Popup::OpenIn => match key.code {
+            KeyCode::Esc | KeyCode::Char('o') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Enter => Action::OpenInService,
+            _ => Action::None,
+        },
+        Popup::Volume => match key.code {
+            KeyCode::Esc | KeyCode::Char('v') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Char('+') | KeyCode::Char('=') => Action::VolumeUp,
+            KeyCode::Char('-') => Action::VolumeDown,
+            KeyCode::Char('g') => Action::ToggleVolumeLink,
+            KeyCode::Char('m') => Action::ToggleOutputMute,
+            KeyCode::Char('t') => Action::ShowTrim,
+            _ => Action::None,
+        },
+        Popup::PartyUnlock => match key.code {
+            KeyCode::Esc => Action::ClosePopup,
+            KeyCode::Enter => Action::PartySubmit,
+            KeyCode::Backspace => Action::PartyBackspace,
+            KeyCode::Char(c) => Action::PartyChar(c),
+            _ => Action::None,
+        },
+        Popup::DspPresets => match key.code {
+            KeyCode::Esc | KeyCode::Char('d') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Enter => Action::SelectDspPreset,
+            _ => Action::None,
+        },
+        Popup::ZoneGone => match key.code {
+            KeyCode::Enter | KeyCode::Char('f') => Action::FollowSuccessorZone,
+            KeyCode::Char('z') => Action::ShowZoneSelector,
+            KeyCode::Esc => Action::ClosePopup,
+            _ => Action::None,
+        },
+        Popup::Outputs => match key.code {
+            KeyCode::Esc | KeyCode::Char('O') => Action::ClosePopup,
+            _ => Action::None,
+        },
+        Popup::Trim => match key.code {
+            KeyCode::Esc | KeyCode::Char('V') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Char('l') | KeyCode::Right | KeyCode::Char('+') | KeyCode::Char('=') => {
+                Action::TrimUp
+            }
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Char('-') => Action::TrimDown,
+            _ => Action::None,
+        },
+        Popup::Onboarding => match key.code {
+            KeyCode::Esc => Action::OnboardingSkip,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Char('r') => Action::OnboardingRetry,
+            KeyCode::Enter => Action::OnboardingNext,
+            _ => Action::None,
+        },
+        Popup::ThemeSwitcher => match key.code {
+            KeyCode::Esc => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Enter => Action::ConfirmTheme,
+            _ => Action::None,
+        },
+        Popup::ConfirmQuit => match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Action::ForceQuit,
+            KeyCode::Char('n') | KeyCode::Esc => Action::ClosePopup,
+            _ => Action::None,
+        },
+        Popup::Focus if app.focus.input.is_some() => match key.code {
+            KeyCode::Esc => Action::FocusCancel,
+            KeyCode::Enter => Action::FocusSubmit,
+            KeyCode::Backspace => Action::FocusBackspace,
+            KeyCode::Char(c) => Action::FocusChar(c),
+            _ => Action::None,
+        },
+        Popup::Focus => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Enter => Action::FocusEdit,
+            KeyCode::Char('d') | KeyCode::Delete => Action::FocusClear,
+            KeyCode::Char('a') => Action::FocusApply,
+            _ => Action::None,
+        },
+        Popup::Credentials if app.credentials.input.is_some() => match key.code {
+            KeyCode::Esc => Action::CredentialsCancel,
+            KeyCode::Enter => Action::CredentialsSubmit,
+            KeyCode::Backspace => Action::CredentialsBackspace,
+            KeyCode::Char(c) => Action::CredentialsChar(c),
+            _ => Action::None,
+        },
+        Popup::Credentials => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
+            KeyCode::Enter => Action::CredentialsEdit,
+            KeyCode::Char('d') | KeyCode::Delete => Action::CredentialsDelete,
+            _ => Action::None,
+        },
+        Popup::Resume => match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => Action::ResumePlayback,
+            KeyCode::Char('n') | KeyCode::Esc => Action::ClosePopup,
+            _ => Action::None,
+        },
+        Popup::ItemDetail | Popup::QueueItem => match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('i') => {
+                Action::ClosePopup
+            }
+            _ => Action::None,
+        },
+        Popup::Stats => match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => Action::ClosePopup,
+            KeyCode::Char('h') | KeyCode::Left => Action::StatsPrevYear,
+            KeyCode::Char('l') | KeyCode::Right => Action::StatsNextYear,
+            _ => Action::None,
+        },
+        Popup::Lyrics => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('y') => Action::ClosePopup,
+            KeyCode::Char('j') | KeyCode::Down => Action::SelectDown,
+            KeyCode::Char('k') | KeyCode::Up => Action::SelectUp,
             _ => Action::None,
         },
     }
 }
 
-/// Get help text for keybindings
-pub fn help_text() -> Vec<(&'static str, &'static str)> {
-    vec![
-        ("Navigation", ""),
-        ("1", "Now Playing view"),
-        ("2", "Browse library"),
-        ("3 / /", "Search library"),
-        ("z", "Select zone"),
-        ("?", "Show / hide help"),
-        ("q", "Quit"),
-        ("", ""),
-        ("Playback", ""),
-        ("Space", "Play / Pause"),
-        ("n", "Next track"),
-        ("p", "Previous track"),
-        ("s", "Toggle shuffle"),
-        ("l", "Cycle loop mode"),
-        ("r", "Toggle radio"),
-        ("", ""),
-        ("Volume", ""),
-        ("+ / =", "Volume up"),
-        ("-", "Volume down"),
-        ("m", "Toggle mute"),
-        ("", ""),
-        ("Browse / Search", ""),
+/// Fixed (non-rebindable) keys of a view, for the help popup
+fn view_keys(view: View) -> &'static [(&'static str, &'static str)] {
+    const BROWSE: &[(&str, &str)] = &[
         ("j/k", "Navigate up / down"),
         ("Enter", "Select / drill in"),
+        ("i", "Item details"),
+        ("r", "Play similar (start a radio)"),
+        ("f", "Focus: filter albums"),
+        ("o", "Sort an artist's albums"),
         ("Esc/Bksp", "Go back"),
-    ]
+        ("Ctrl+r", "Reload list"),
+        ("r/b/d", "After an error: retry / root / debug log"),
+    ];
+    const SEARCH: &[(&str, &str)] = &[
+        ("/", "Edit search query"),
+        ("j/k", "Navigate up / down"),
+        ("Enter", "Select / drill in"),
+        ("i", "Item details"),
+        ("r", "Play similar (start a radio)"),
+        ("f", "Focus: filter albums"),
+        ("Esc/Bksp", "Go back"),
+        ("Ctrl+r", "Reload list"),
+        ("r/b/d", "After an error: retry / root / debug log"),
+    ];
+    match view {
+        View::NowPlaying => &[],
+        View::Browse => BROWSE,
+        View::Search => SEARCH,
+        View::Queue => &[
+            ("j/k", "Navigate up / down"),
+            ("i/Enter", "Track details"),
+            ("D", "Remove duplicate tracks"),
+            ("Esc", "Back to Now Playing"),
+        ],
+        View::Settings => &[
+            ("j/k", "Navigate up / down"),
+            ("Enter", "Rebind action"),
+            ("Bksp", "Reset to default"),
+            ("Esc", "Back to Now Playing"),
+        ],
+        View::ForYou => &[
+            ("j/k", "Navigate up / down"),
+            ("Enter", "Play now"),
+            ("a", "Add to queue"),
+            ("i", "Item details"),
+            ("Ctrl+r", "Fetch again"),
+            ("Esc", "Back to Now Playing"),
+        ],
+    }
+}
+
+/// Help popup rows for the current page: (key, description), where an empty
+/// description marks a section header and an empty row a gap between sections
+///
+/// The first page covers the active view (its fixed keys and view-specific
+/// bindings), the second every global binding. Keys come from the keymap.
+pub fn help_text(app: &App) -> Vec<(String, String)> {
+    let mut rows = Vec::new();
+    let bindings = app.keymap.bindings().iter();
+    let filter = app.help_filter.to_lowercase();
+    let bindings: Vec<&Binding> = if !filter.is_empty() {
+        // Filtering searches every binding, on both pages
+        let fixed = view_keys(app.view)
+            .iter()
+            .filter(|(_, d)| d.to_lowercase().contains(&filter));
+        rows.extend(fixed.map(|(k, d)| (k.to_string(), d.to_string())));
+        bindings
+            .filter(|b| b.name.contains(&filter) || b.description.to_lowercase().contains(&filter))
+            .collect()
+    } else {
+        match app.help_page {
+            HelpPage::View => {
+                let fixed = view_keys(app.view);
+                if !fixed.is_empty() {
+                    rows.push((app.view.title().to_string(), String::new()));
+                    rows.extend(fixed.iter().map(|(k, d)| (k.to_string(), d.to_string())));
+                }
+                bindings
+                    .filter(|b| b.scope != Scope::Global && b.scope.contains(app.view))
+                    .collect()
+            }
+            HelpPage::Global => bindings.filter(|b| b.scope == Scope::Global).collect(),
+        }
+    };
+
+    let mut section = "";
+    for binding in bindings {
+        if binding.section != section {
+            section = binding.section;
+            if !rows.is_empty() {
+                rows.push((String::new(), String::new()));
+            }
+            rows.push((section.to_string(), String::new()));
+        }
+        let keys = app.keymap.keys_display(&binding.action);
+        let keys = if keys.is_empty() {
+            "—".to_string()
+        } else {
+            keys
+        };
+        rows.push((keys, binding.description.to_string()));
+    }
+
+    // Plugin actions work everywhere, so they go with the global keys
+    let plugin_actions: Vec<_> = app
+        .plugins
+        .actions
+        .iter()
+        .filter(|a| {
+            if filter.is_empty() {
+                app.help_page == HelpPage::Global
+            } else {
+                a.description.to_lowercase().contains(&filter)
+            }
+        })
+        .collect();
+    if !plugin_actions.is_empty() {
+        if !rows.is_empty() {
+            rows.push((String::new(), String::new()));
+        }
+        rows.push(("Plugins".to_string(), String::new()));
+        for action in plugin_actions {
+            let keys = action
+                .key
+                .as_ref()
+                .map_or("—".to_string(), ToString::to_string);
+            rows.push((keys, action.description.clone()));
+        }
+    }
+
+    if rows.is_empty() {
+        let empty = if filter.is_empty() {
+            "No view-specific keys"
+        } else {
+            "No matching keys"
+        };
+        rows.push((empty.to_string(), String::new()));
+    }
+    rows
 }
@@ -0,0 +1,597 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::View;
+use crate::input::{self, Action};
+use crate::plugins::PluginAction;
+
+/// A single key press (code + modifiers), as written in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub const fn plain(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::plain(KeyCode::Char(c))
+    }
+
+    /// Parse a key like "q", "space", "ctrl+n" or "f5"
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        // Split off modifier prefixes, but keep a lone "+" as the key itself
+        while let Some((prefix, key)) = rest.split_once('+').filter(|(_, k)| !k.is_empty()) {
+            match prefix.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => return None,
+            }
+            rest = key;
+        }
+
+        let code = match rest.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "insert" => KeyCode::Insert,
+            "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name if name.len() > 1 && name.starts_with('f') => KeyCode::F(name[1..].parse().ok()?),
+            _ => {
+                let mut chars = rest.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                KeyCode::Char(c)
+            }
+        };
+        Some(Self { code, modifiers }.normalized())
+    }
+
+    /// Whether the key survives being written to the config file and read
+    /// back (media keys and the like have no name there)
+    fn round_trips(self) -> bool {
+        Self::parse(&self.to_string()) == Some(self)
+    }
+
+    /// Shift is implied by the character itself ('?' vs '/'), so drop it
+    fn normalized(mut self) -> Self {
+        if let KeyCode::Char(_) = self.code {
+            self.modifiers.remove(KeyModifiers::SHIFT);
+        }
+        self
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            modifiers: event.modifiers,
+        }
+        .normalized()
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "backtab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            other => write!(f, "{other:?}"),
+        }
+    }
+}
+
+/// Views in which a binding is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// Every view (outside of text input)
+    Global,
+    /// Only the Now Playing view
+    NowPlaying,
+}
+
+impl Scope {
+    pub fn contains(self, view: View) -> bool {
+        match self {
+            Scope::Global => true,
+            Scope::NowPlaying => view == View::NowPlaying,
+        }
+    }
+
+    fn overlaps(self, other: Scope) -> bool {
+        self == Scope::Global || other == Scope::Global || self == other
+    }
+}
+
+/// Keys with a fixed meaning in list views (navigation), which can't be rebound
+const RESERVED: &[Key] = &[
+    Key::char('j'),
+    Key::char('k'),
+    Key::char('h'),
+    Key::plain(KeyCode::Up),
+    Key::plain(KeyCode::Down),
+    Key::plain(KeyCode::Enter),
+    Key::plain(KeyCode::Esc),
+    Key::plain(KeyCode::Backspace),
+    Key::plain(KeyCode::Delete),
+];
+
+/// A rebindable action with its config name and description
+pub struct Binding {
+    pub action: Action,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Help popup section ("Playback", "Volume", ...)
+    pub section: &'static str,
+    pub scope: Scope,
+    pub keys: Vec<Key>,
+}
+
+/// Why a rebind was refused
+#[derive(Debug)]
+pub enum RebindError {
+    /// Key has no name in the config file, so it couldn't be saved
+    Unsupported(Key),
+    /// Key is used for list navigation
+    Reserved(Key),
+    /// Key is already bound to another action in an overlapping scope
+    Conflict(Key, &'static str),
+    /// Key has a fixed meaning in a view the binding works in
+    Fixed(Key, View),
+    /// Key runs a plugin's action (which a binding would shadow)
+    Plugin(Key, String),
+}
+
+impl fmt::Display for RebindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RebindError::Unsupported(key) => write!(f, "'{key}' can't be bound"),
+            RebindError::Reserved(key) => write!(f, "'{key}' is reserved for navigation"),
+            RebindError::Conflict(key, description) => {
+                write!(f, "'{key}' is already bound to \"{description}\"")
+            }
+            RebindError::Fixed(key, view) => {
+                write!(f, "'{key}' is a fixed key in {}", view.title())
+            }
+            RebindError::Plugin(key, description) => {
+                write!(f, "'{key}' is already bound to \"{description}\" (plugin)")
+            }
+        }
+    }
+}
+
+/// Menus of the leader scheme: the key after the leader opens a section
+const LEADER_MENUS: &[(char, &str)] = &[
+    ('g', "Navigation"),
+    ('p', "Playback"),
+    ('v', "Volume"),
+    ('n', "Now Playing"),
+    ('a', "Appearance"),
+];
+
+/// Actions run directly by the key after the leader
+const LEADER_ACTIONS: &[(char, Action)] = &[
+    ('z', Action::ShowZoneSelector),
+    ('?', Action::ShowHelp),
+    ('q', Action::Quit),
+];
+
+/// An open which-key menu of the leader scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderMenu {
+    /// Just pressed the leader: pick a menu or a direct action
+    Root,
+    /// A bindings section, entered with the section's own keys
+    Section(&'static str),
+}
+
+/// Runtime-mutable mapping from keys to actions
+pub struct Keymap {
+    bindings: Vec<Binding>,
+    /// Leader key when the leader scheme is active (bindings then only work
+    /// inside its menus)
+    leader: Option<Key>,
+}
+
+impl Keymap {
+    /// Build the keymap from defaults plus `[keys]` overrides from config
+    pub fn new(overrides: &BTreeMap<String, Vec<String>>) -> Self {
+        let mut keymap = Self::default();
+        for (name, keys) in overrides {
+            let Some(binding) = keymap.bindings.iter_mut().find(|b| b.name == name) else {
+                tracing::warn!("Unknown action in [keys]: {}", name);
+                continue;
+            };
+            let parsed: Vec<Key> = keys
+                .iter()
+                .filter_map(|k| {
+                    let key = Key::parse(k);
+                    if key.is_none() {
+                        tracing::warn!("Invalid key for {}: {:?}", name, k);
+                    }
+                    key
+                })
+                .collect();
+            // An empty list unbinds the action, but one where nothing parsed
+            // is a mistake: keep the defaults then
+            if parsed.is_empty() && !keys.is_empty() {
+                tracing::warn!("No valid keys for {}, keeping the defaults", name);
+                continue;
+            }
+            binding.keys = parsed;
+        }
+        keymap
+    }
+
+    /// Switch to the leader scheme with the given leader key ("space")
+    pub fn set_leader(&mut self, key: &str) {
+        self.leader = Key::parse(key);
+        if self.leader.is_none() {
+            tracing::warn!("Invalid leader key {:?}, using single-key bindings", key);
+        }
+    }
+
+    /// Whether the leader scheme is active
+    pub fn uses_leader(&self) -> bool {
+        self.leader.is_some()
+    }
+
+    /// Whether a key press is the leader key
+    pub fn is_leader(&self, key: KeyEvent) -> bool {
+        self.leader == Some(Key::from(key))
+    }
+
+    /// Action for a key press inside an open leader menu
+    pub fn leader_action(&self, menu: LeaderMenu, key: KeyEvent, view: View) -> Action {
+        let key = Key::from(key);
+        match menu {
+            LeaderMenu::Root => {
+                let KeyCode::Char(c) = key.code else {
+                    return Action::None;
+                };
+                if let Some((_, section)) = LEADER_MENUS.iter().find(|(k, _)| *k == c) {
+                    return Action::OpenLeaderMenu(LeaderMenu::Section(section));
+                }
+                LEADER_ACTIONS
+                    .iter()
+                    .find(|(k, _)| *k == c)
+                    .map(|(_, action)| action.clone())
+                    .unwrap_or(Action::None)
+            }
+            LeaderMenu::Section(section) => self
+                .bindings
+                .iter()
+                .find(|b| b.section == section && b.scope.contains(view) && b.keys.contains(&key))
+                .map(|b| b.action.clone())
+                .unwrap_or(Action::None),
+        }
+    }
+
+    /// Entries of a leader menu for the which-key popup: (keys, description)
+    pub fn leader_items(&self, menu: LeaderMenu, view: View) -> Vec<(String, String)> {
+        match menu {
+            LeaderMenu::Root => {
+                let menus = LEADER_MENUS
+                    .iter()
+                    .map(|(k, section)| (k.to_string(), format!("{section}…")));
+                let actions = LEADER_ACTIONS
+                    .iter()
+                    .map(|(k, action)| (k.to_string(), action_info(action).1.to_string()));
+                menus.chain(actions).collect()
+            }
+            LeaderMenu::Section(section) => self
+                .bindings
+                .iter()
+                .filter(|b| b.section == section && b.scope.contains(view))
+                .map(|b| (self.keys_display(&b.action), b.description.to_string()))
+                .collect(),
+        }
+    }
+
+    /// All rebindable actions, in display order
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Look up the action for a key press in the given view
+    pub fn action_for(&self, key: KeyEvent, view: View) -> Option<Action> {
+        self.binding_for(Key::from(key), view)
+            .map(|b| b.action.clone())
+    }
+
+    fn binding_for(&self, key: Key, view: View) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|b| b.scope.contains(view) && b.keys.contains(&key))
+    }
+
+    /// What a key press does in the given view, for the help popup's lookup
+    pub fn describe_key(&self, key: KeyEvent, view: View) -> String {
+        let key = Key::from(key);
+        match self.binding_for(key, view) {
+            Some(binding) => format!("{key} → {}", binding.description),
+            None if RESERVED.contains(&key) => format!("{key} → navigation (fixed)"),
+            None => format!("{key} isn't bound in this view"),
+        }
+    }
+
+    /// Keys bound to an action, formatted for display ("+ / =")
+    pub fn keys_display(&self, action: &Action) -> String {
+        self.bindings
+            .iter()
+            .find(|b| &b.action == action)
+            .map(|b| {
+                b.keys
+                    .iter()
+                    .map(Key::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" / ")
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replace the keys of the binding at `index` with a single new key,
+    /// unless another binding, a fixed view key or a plugin action has it
+    pub fn rebind(
+        &mut self,
+        index: usize,
+        key: Key,
+        plugin_actions: &[PluginAction],
+    ) -> Result<(), RebindError> {
+        if !key.round_trips() {
+            return Err(RebindError::Unsupported(key));
+        }
+        if RESERVED.contains(&key) {
+            return Err(RebindError::Reserved(key));
+        }
+        let scope = self.bindings[index].scope;
+        let conflict = self
+            .bindings
+            .iter()
+            .enumerate()
+            .find(|(i, b)| *i != index && b.scope.overlaps(scope) && b.keys.contains(&key));
+        if let Some((_, other)) = conflict {
+            return Err(RebindError::Conflict(key, other.description));
+        }
+        let event = KeyEvent::new(key.code, key.modifiers);
+        let fixed = View::ALL
+            .into_iter()
+            .find(|&view| scope.contains(view) && input::view_action(event, view) != Action::None);
+        if let Some(view) = fixed {
+            return Err(RebindError::Fixed(key, view));
+        }
+        // Plugin actions work in every view
+        if let Some(action) = plugin_actions.iter().find(|a| a.key == Some(key)) {
+            return Err(RebindError::Plugin(key, action.description.clone()));
+        }
+        self.bindings[index].keys = vec![key];
+        Ok(())
+    }
+
+    /// Restore the default keys of the binding at `index`
+    pub fn reset(&mut self, index: usize) {
+        let defaults = Self::default();
+        self.bindings[index].keys = defaults.bindings[index].keys.clone();
+    }
+
+    /// Bindings that differ from the defaults, in config file form
+    pub fn overrides(&self) -> BTreeMap<String, Vec<String>> {
+        let defaults = Self::default();
+        self.bindings
+            .iter()
+            .zip(defaults.bindings.iter())
+            .filter(|(b, d)| b.keys != d.keys)
+            .map(|(b, _)| {
+                let keys = b.keys.iter().map(Key::to_string).collect();
+                (b.name.to_string(), keys)
+            })
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use Scope::{Global, NowPlaying};
+
+        Self {
+            bindings: vec![
+                // Navigation
+                bind(Global, Action::SwitchToNowPlaying, &["1"]),
+                bind(Global, Action::SwitchToBrowse, &["2"]),
+                bind(Global, Action::SwitchToSearch, &["3", "/"]),
+                bind(Global, Action::SwitchToQueue, &["4"]),
+                bind(Global, Action::SwitchToSettings, &["5"]),
+                bind(Global, Action::SwitchToForYou, &["6"]),
+                bind(Global, Action::BrowseGenres, &["G"]),
+                bind(Global, Action::BrowseComposers, &["W"]),
+                bind(Global, Action::ShowZoneSelector, &["z"]),
+                bind(Global, Action::ShowCoreSwitcher, &["C"]),
+                bind(Global, Action::ToggleFollow, &["F"]),
+                bind(Global, Action::ShowStats, &["S"]),
+                bind(Global, Action::ShowOutputs, &["O"]),
+                bind(Global, Action::ToggleParty, &["P"]),
+                bind(Global, Action::ShowHelp, &["?"]),
+                bind(Global, Action::Quit, &["q"]),
+                // Playback
+                bind(Global, Action::PlayPause, &["space"]),
+                bind(Global, Action::NextTrack, &["n"]),
+                bind(Global, Action::PrevTrack, &["p"]),
+                bind(NowPlaying, Action::ToggleShuffle, &["s"]),
+                bind(NowPlaying, Action::CycleLoop, &["l"]),
+                bind(NowPlaying, Action::ToggleRadio, &["r"]),
+                bind(Global, Action::SurpriseMe, &["R"]),
+                bind(NowPlaying, Action::ToggleAlbumShuffle, &["a"]),
+                bind(NowPlaying, Action::CycleAbRepeat, &["x"]),
+                // Volume
+                bind(Global, Action::VolumeUp, &["+", "="]),
+                bind(Global, Action::VolumeDown, &["-"]),
+                bind(Global, Action::ToggleMute, &["m"]),
+                bind(Global, Action::ShowVolume, &["v"]),
+                bind(Global, Action::ShowTrim, &["V"]),
+                bind(Global, Action::CycleLeveling, &["L"]),
+                // Now Playing
+                bind(NowPlaying, Action::ShowOpenIn, &["o"]),
+                bind(NowPlaying, Action::ShowLyrics, &["y"]),
+                bind(NowPlaying, Action::ShowFullscreenArt, &["f"]),
+                bind(NowPlaying, Action::CopyTrackInfo, &["c"]),
+                bind(NowPlaying, Action::BrowseCurrentAlbum, &["b"]),
+                bind(NowPlaying, Action::ToggleTimeDisplay, &["T"]),
+                bind(NowPlaying, Action::ShowDspPresets, &["d"]),
+                // Appearance
+                bind(Global, Action::ShowThemeSwitcher, &["t"]),
+                bind(Global, Action::ToggleSplitView, &["|"]),
+                bind(Global, Action::ToggleMiniPlayer, &["M"]),
+                bind(Global, Action::ToggleZenMode, &["Z"]),
+                bind(Global, Action::ToggleMetrics, &["f12"]),
+            ],
+            leader: None,
+        }
+    }
+}
+
+/// Build a default binding (default key strings are known to parse)
+fn bind(scope: Scope, action: Action, keys: &[&str]) -> Binding {
+    let (name, description) = action_info(&action);
+    Binding {
+        section: action_section(&action),
+        action,
+        name,
+        description,
+        scope,
+        keys: keys.iter().filter_map(|k| Key::parse(k)).collect(),
+    }
+}
+
+/// Help section of a rebindable action
+fn action_section(action: &Action) -> &'static str {
+    match action {
+        Action::PlayPause
+        | Action::NextTrack
+        | Action::PrevTrack
+        | Action::ToggleShuffle
+        | Action::CycleLoop
+        | Action::ToggleRadio
+        | Action::SurpriseMe
+        | Action::ToggleAlbumShuffle
+        | Action::CycleAbRepeat => "Playback",
+        Action::VolumeUp
+        | Action::VolumeDown
+        | Action::ToggleMute
+        | Action::ShowVolume
+        | Action::ShowTrim
+        | Action::CycleLeveling => "Volume",
+        Action::ShowOpenIn
+        | Action::ShowDspPresets
+        | Action::ShowLyrics
+        | Action::ShowFullscreenArt
+        | Action::CopyTrackInfo
+        | Action::BrowseCurrentAlbum
+        | Action::ToggleTimeDisplay => "Now Playing",
+        Action::ShowThemeSwitcher
+        | Action::ToggleSplitView
+        | Action::ToggleMiniPlayer
+        | Action::ToggleZenMode
+        | Action::ToggleMetrics => "Appearance",
+        _ => "Navigation",
+    }
+}
+
+/// Config name and description of a rebindable action
+fn action_info(action: &Action) -> (&'static str, &'static str) {
+    match action {
+        Action::SwitchToNowPlaying => ("now_playing", "Now Playing view"),
+        Action::SwitchToBrowse => ("browse", "Browse library"),
+        Action::SwitchToSearch => ("search", "Search library"),
+        Action::SwitchToQueue => ("queue", "Play queue"),
+        Action::SwitchToSettings => ("settings", "Settings"),
+        Action::SwitchToForYou => ("for_you", "For You: mixes and recommendations"),
+        Action::BrowseGenres => ("genres", "Browse genres"),
+        Action::BrowseComposers => ("composers", "Browse composers"),
+        Action::ShowZoneSelector => ("zones", "Select zone"),
+        Action::ShowCoreSwitcher => ("cores", "Switch Roon Core"),
+        Action::ToggleFollow => ("follow", "Follow the music across zones"),
+        Action::ShowStats => ("stats", "Listening stats"),
+        Action::ToggleParty => ("party", "Party mode (lock quit and zone switching)"),
+        Action::ShowHelp => ("help", "Show / hide help"),
+        Action::Quit => ("quit", "Quit"),
+        Action::PlayPause => ("play_pause", "Play / Pause"),
+        Action::NextTrack => ("next", "Next track"),
+        Action::PrevTrack => ("prev", "Previous track"),
+        Action::ToggleShuffle => ("shuffle", "Toggle shuffle"),
+        Action::CycleLoop => ("loop", "Cycle loop mode"),
+        Action::ToggleRadio => ("radio", "Toggle radio"),
+        Action::SurpriseMe => ("surprise", "Play a random album"),
+        Action::ToggleAlbumShuffle => ("album_shuffle", "Toggle album shuffle"),
+        Action::CycleAbRepeat => ("ab_repeat", "A–B repeat: mark A, mark B, clear"),
+        Action::VolumeUp => ("volume_up", "Volume up"),
+        Action::VolumeDown => ("volume_down", "Volume down"),
+        Action::ToggleMute => ("mute", "Toggle mute"),
+        Action::ShowVolume => ("volume", "Per-output volume"),
+        Action::ShowDspPresets => ("dsp", "Switch DSP preset"),
+        Action::ShowTrim => ("trim", "Trims of grouped outputs"),
+        Action::ShowOutputs => ("outputs", "Output devices and formats"),
+        Action::CycleLeveling => ("leveling", "Cycle volume leveling"),
+        Action::ShowOpenIn => ("open_in", "Open in external service"),
+        Action::ShowLyrics => ("lyrics", "Show lyrics"),
+        Action::ShowFullscreenArt => ("fullscreen_art", "Full-screen album art"),
+        Action::CopyTrackInfo => ("copy", "Copy track info"),
+        Action::BrowseCurrentAlbum => ("browse_album", "Browse the playing album"),
+        Action::ToggleTimeDisplay => ("time_display", "Elapsed / remaining time"),
+        Action::ShowThemeSwitcher => ("theme", "Switch theme"),
+        Action::ToggleMetrics => ("metrics", "Performance metrics"),
+        Action::ToggleSplitView => ("split_view", "Split Browse / Now Playing"),
+        Action::ToggleMiniPlayer => ("mini_player", "Mini-player"),
+        Action::ToggleZenMode => ("zen_mode", "Zen mode"),
+        _ => ("", ""),
+    }
+}
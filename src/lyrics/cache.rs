@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use super::{Lyrics, LyricsQuery};
+
+/// On-disk lyrics cache, one JSON file per track
+pub struct LyricsCache {
+    dir: PathBuf,
+}
+
+impl LyricsCache {
+    /// Open (and create) the cache directory, if the platform has one
+    pub fn open() -> Option<Self> {
+        let dir = crate::paths::cache_dir()?.join("lyrics");
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            tracing::warn!("Lyrics cache disabled: {}", e);
+            return None;
+        }
+        Some(Self { dir })
+    }
+
+    pub fn get(&self, query: &LyricsQuery) -> Option<Lyrics> {
+        let contents = std::fs::read_to_string(self.path(query)).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn put(&self, query: &LyricsQuery, lyrics: &Lyrics) {
+        if let Err(e) = crate::paths::write_state(&self.path(query), lyrics) {
+            tracing::warn!("Failed to cache lyrics: {}", e);
+        }
+    }
+
+    fn path(&self, query: &LyricsQuery) -> PathBuf {
+        self.dir.join(format!("{}.json", file_stem(&query.key())))
+    }
+}
+
+/// Longest file stem, in bytes, leaving room for the extension and the
+/// temp file's additions within the usual 255-byte file name limit
+const MAX_STEM: usize = 200;
+
+/// Make a track key safe to use as a file name
+///
+/// Keys too long for a file name are cut short and end in a hash of the
+/// whole key, so tracks sharing a long prefix still get files of their own.
+pub fn file_stem(key: &str) -> String {
+    let stem: String = key
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    if stem.len() <= MAX_STEM {
+        return stem;
+    }
+    let suffix = format!("-{:016x}", fnv1a(key));
+    let mut end = MAX_STEM - suffix.len();
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &stem[..end], suffix)
+}
+
+/// 64-bit FNV-1a, which unlike std's hasher stays the same across Rust
+/// releases, so cached files keep their names
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
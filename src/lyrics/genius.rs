@@ -0,0 +1,175 @@
+use anyhow::Result;
+use serde::Deserialize;
+
+use super::{FetchFuture, Lyrics, LyricsProvider, LyricsQuery};
+
+const SEARCH_URL: &str = "https://api.genius.com/search";
+
+/// Genius — requires an API access token; lyrics are scraped from the song page
+pub struct GeniusProvider {
+    http: reqwest::Client,
+    token: String,
+}
+
+impl GeniusProvider {
+    pub fn new(http: reqwest::Client, token: String) -> Self {
+        Self { http, token }
+    }
+
+    /// Find the song page URL for the best matching search hit
+    async fn song_url(&self, query: &LyricsQuery) -> Result<Option<String>> {
+        let q = format!("{} {}", query.artist, query.track);
+        let body = self
+            .http
+            .get(SEARCH_URL)
+            .query(&[("q", q.as_str())])
+            .bearer_auth(&self.token)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let search: SearchResponse = serde_json::from_str(&body)?;
+
+        let artist = query.artist.to_lowercase();
+        let url = search
+            .response
+            .hits
+            .into_iter()
+            .filter(|hit| hit.kind == "song")
+            .find(|hit| {
+                let primary = hit.result.primary_artist.name.to_lowercase();
+                artist.contains(&primary) || primary.contains(&artist)
+            })
+            .map(|hit| hit.result.url);
+        Ok(url)
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    response: SearchHits,
+}
+
+#[derive(Deserialize)]
+struct SearchHits {
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Deserialize)]
+struct SearchHit {
+    #[serde(rename = "type")]
+    kind: String,
+    result: SongResult,
+}
+
+#[derive(Deserialize)]
+struct SongResult {
+    url: String,
+    primary_artist: Artist,
+}
+
+#[derive(Deserialize)]
+struct Artist {
+    name: String,
+}
+
+impl LyricsProvider for GeniusProvider {
+    fn name(&self) -> &'static str {
+        "genius"
+    }
+
+    fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> FetchFuture<'a> {
+        Box::pin(async move {
+            let Some(url) = self.song_url(query).await? else {
+                return Ok(None);
+            };
+            let html = self
+                .http
+                .get(&url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+
+            let text = extract_lyrics(&html);
+            if text.trim().is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Lyrics {
+                source: "Genius".to_string(),
+                text,
+            }))
+        })
+    }
+}
+
+/// Pull the text out of the `data-lyrics-container` blocks of a Genius song page
+fn extract_lyrics(html: &str) -> String {
+    const MARKER: &str = "data-lyrics-container=\"true\"";
+
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(MARKER) {
+        rest = &rest[start + MARKER.len()..];
+        // Skip to the end of the opening tag
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+        rest = &rest[open_end + 1..];
+
+        // Walk until the matching </div>, tracking nested divs
+        let mut depth = 1;
+        let mut i = 0;
+        while i < rest.len() && depth > 0 {
+            let tail = &rest[i..];
+            if tail.starts_with("<div") {
+                depth += 1;
+            } else if tail.starts_with("</div") {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            i += tail.chars().next().map(char::len_utf8).unwrap_or(1);
+        }
+
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&html_to_text(&rest[..i]));
+        rest = &rest[i..];
+    }
+    text
+}
+
+/// Convert a lyrics HTML fragment to plain text
+fn html_to_text(fragment: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    let mut tag = String::new();
+    for c in fragment.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                if tag.starts_with("br") {
+                    out.push('\n');
+                }
+            }
+            c if in_tag => tag.push(c),
+            c => out.push(c),
+        }
+    }
+
+    out.replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#x27;", "'")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+}
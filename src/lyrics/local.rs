@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use super::cache::file_stem;
+use super::{strip_lrc, FetchFuture, Lyrics, LyricsProvider, LyricsQuery};
+
+/// Reads `Artist - Track.lrc` (or `.txt`) files from a local directory
+pub struct LocalProvider {
+    dir: PathBuf,
+}
+
+impl LocalProvider {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn read(&self, query: &LyricsQuery) -> Result<Option<Lyrics>> {
+        let stem = file_stem(&query.key());
+        for ext in ["lrc", "txt"] {
+            let path = self.dir.join(format!("{stem}.{ext}"));
+            if path.is_file() {
+                let contents = std::fs::read_to_string(&path)?;
+                let text = if ext == "lrc" {
+                    strip_lrc(&contents)
+                } else {
+                    contents
+                };
+                return Ok(Some(Lyrics {
+                    source: "local".to_string(),
+                    text,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl LyricsProvider for LocalProvider {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> FetchFuture<'a> {
+        Box::pin(async move { self.read(query) })
+    }
+
+    fn cached(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+use super::{strip_lrc, FetchFuture, Lyrics, LyricsProvider, LyricsQuery};
+
+const API_URL: &str = "https://lrclib.net/api/get";
+
+/// LRCLIB — free lyrics database, no API key required
+pub struct LrclibProvider {
+    http: reqwest::Client,
+}
+
+impl LrclibProvider {
+    pub fn new(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LrclibResponse {
+    plain_lyrics: Option<String>,
+    synced_lyrics: Option<String>,
+}
+
+impl LyricsProvider for LrclibProvider {
+    fn name(&self) -> &'static str {
+        "lrclib"
+    }
+
+    fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> FetchFuture<'a> {
+        Box::pin(async move {
+            let duration = (query.length.round() as u64).to_string();
+            let mut params = vec![
+                ("artist_name", query.artist.as_str()),
+                ("track_name", query.track.as_str()),
+                ("album_name", query.album.as_str()),
+            ];
+            if query.length > 0.0 {
+                params.push(("duration", duration.as_str()));
+            }
+
+            let response = self.http.get(API_URL).query(&params).send().await?;
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            let body: LrclibResponse =
+                serde_json::from_str(&response.error_for_status()?.text().await?)?;
+
+            let text = body
+                .plain_lyrics
+                .or_else(|| body.synced_lyrics.map(|s| strip_lrc(&s)));
+            Ok(text.map(|text| Lyrics {
+                source: "LRCLIB".to_string(),
+                text,
+            }))
+        })
+    }
+}
@@ -0,0 +1,160 @@
+mod cache;
+mod genius;
+mod local;
+mod lrclib;
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::config::LyricsConfig;
+use cache::LyricsCache;
+
+/// Track identity used to look up lyrics
+#[derive(Debug, Clone, PartialEq)]
+pub struct LyricsQuery {
+    pub artist: String,
+    pub track: String,
+    pub album: String,
+    pub length: f64,
+}
+
+impl LyricsQuery {
+    /// Stable key identifying the track (for cache files and change detection)
+    pub fn key(&self) -> String {
+        format!("{} - {}", self.artist, self.track)
+    }
+}
+
+/// Lyrics text and the provider it came from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lyrics {
+    pub source: String,
+    pub text: String,
+}
+
+/// Boxed future returned by providers (keeps the trait object-safe)
+pub type FetchFuture<'a> = Pin<Box<dyn Future<Output = Result<Option<Lyrics>>> + Send + 'a>>;
+
+/// A source of lyrics
+///
+/// `Ok(None)` means the provider has no lyrics for the track, so the next
+/// provider in the chain is tried; errors are logged and also fall through.
+pub trait LyricsProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn fetch<'a>(&'a self, query: &'a LyricsQuery) -> FetchFuture<'a>;
+
+    /// Whether results go through the disk cache; a provider that reads
+    /// local files is asked before the cache instead, so edits show up
+    fn cached(&self) -> bool {
+        true
+    }
+}
+
+/// Provider chain with a disk cache in front of the remote providers
+pub struct LyricsFetcher {
+    providers: Vec<Box<dyn LyricsProvider>>,
+    cache: Option<LyricsCache>,
+}
+
+impl LyricsFetcher {
+    /// Build the provider chain from config, skipping providers that lack settings
+    pub fn from_config(
+        config: &LyricsConfig,
+        genius_token: Option<String>,
+        http: reqwest::Client,
+    ) -> Self {
+        let mut providers: Vec<Box<dyn LyricsProvider>> = Vec::new();
+        for name in &config.providers {
+            match name.as_str() {
+                "local" => {
+                    if let Some(dir) = &config.local_dir {
+                        providers.push(Box::new(local::LocalProvider::new(expand_home(dir))));
+                    }
+                }
+                "lrclib" => providers.push(Box::new(lrclib::LrclibProvider::new(http.clone()))),
+                "genius" => {
+                    if let Some(token) = &genius_token {
+                        providers.push(Box::new(genius::GeniusProvider::new(
+                            http.clone(),
+                            token.clone(),
+                        )));
+                    }
+                }
+                other => tracing::warn!("Unknown lyrics provider: {}", other),
+            }
+        }
+
+        Self {
+            providers,
+            cache: LyricsCache::open(),
+        }
+    }
+
+    /// Look up lyrics with the uncached providers, then in the cache, then
+    /// with each cached provider in order
+    pub async fn fetch(&self, query: &LyricsQuery) -> Option<Lyrics> {
+        for provider in self.providers.iter().filter(|p| !p.cached()) {
+            if let Some(lyrics) = Self::ask(provider.as_ref(), query).await {
+                return Some(lyrics);
+            }
+        }
+
+        if let Some(lyrics) = self.cache.as_ref().and_then(|c| c.get(query)) {
+            return Some(lyrics);
+        }
+
+        for provider in self.providers.iter().filter(|p| p.cached()) {
+            if let Some(lyrics) = Self::ask(provider.as_ref(), query).await {
+                if let Some(cache) = &self.cache {
+                    cache.put(query, &lyrics);
+                }
+                return Some(lyrics);
+            }
+        }
+        None
+    }
+
+    async fn ask(provider: &dyn LyricsProvider, query: &LyricsQuery) -> Option<Lyrics> {
+        match provider.fetch(query).await {
+            Ok(Some(lyrics)) => {
+                tracing::debug!("Lyrics for {} from {}", query.key(), provider.name());
+                Some(lyrics)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Lyrics provider {} failed: {}", provider.name(), e);
+                None
+            }
+        }
+    }
+}
+
+/// Strip `[mm:ss.xx]` timestamps and `[ar:…]` metadata tags from LRC text
+pub fn strip_lrc(text: &str) -> String {
+    text.lines()
+        .filter_map(|line| {
+            let mut rest = line.trim();
+            while let Some(end) = rest.strip_prefix('[').and_then(|r| r.find(']')) {
+                // Metadata tags like [ar:Artist] carry no lyric text
+                if !rest[1..].starts_with(|c: char| c.is_ascii_digit()) {
+                    return None;
+                }
+                rest = rest[end + 2..].trim_start();
+            }
+            Some(rest.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Expand a leading `~/` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
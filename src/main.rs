@@ -1,43 +1,197 @@
+mod announce;
 mod app;
+mod cli;
+mod clipboard;
+mod clock;
+mod colors;
+mod config;
+mod cores;
+mod credentials;
+mod doctor;
+mod external;
+mod history;
+mod icons;
 mod input;
+mod keymap;
+mod lyrics;
+mod media_keys;
+mod metrics;
+mod paths;
+mod plugins;
+mod resume;
 mod roon;
+mod snapshot;
+mod suspend;
+mod swift;
+mod theme;
+mod trims;
 mod ui;
+mod update;
+mod visualizer;
+mod zones;
 
 use std::fs::File;
 use std::io;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, Event, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    style::Print,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
+use image::imageops::FilterType;
+use image::DynamicImage;
 use ratatui::prelude::*;
+use ratatui_image::picker::Picker;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use app::{App, Popup, View};
-use input::{handle_key, Action};
+use app::{
+    App, Cover, HelpInput, ItemDetail, Merged, OnboardingStep, Popup, Screensaver, View, ZoneEvent,
+};
+use cli::{Args, Command, PlayTarget};
+use config::Config;
+use cores::Cores;
+use input::{handle_key, handle_mouse, Action};
+use lyrics::{Lyrics, LyricsFetcher};
+use media_keys::{MediaKey, MediaKeys};
+use plugins::{PluginMsg, Plugins};
+use roon::{BrowseItem, BrowsePath, BrowseRoot, MergedCategory, Origin, Status};
+use snapshot::ZoneSnapshot;
+use suspend::{PowerEvent, SuspendMonitor};
+use visualizer::Visualizer;
 
 /// Message for album art loading
 enum AlbumArtMsg {
-    Loaded(image::DynamicImage, String),
+    Loaded(Cover),
+    /// Cover of the upcoming queue track, fetched ahead of time
+    Prefetched(Cover),
+    /// Artwork for the item detail popup
+    Detail(Cover),
+}
+
+/// How long the app must be idle outside Now Playing before album art is released
+const ART_RELEASE_IDLE: Duration = Duration::from_secs(30);
+
+/// Message for lyrics loading (track key, lyrics if found)
+struct LyricsMsg(String, Option<Lyrics>);
+
+/// Point roon commands at the configured CLI and Core, for the commands
+/// that run without the TUI
+fn use_roon_cli(args: &Args) {
+    let mut config = Config::load().0;
+    args.apply(&mut config);
+    let core = config.core(Cores::load().current()).cloned();
+    roon::set_command(&config.roon_invocation(core.as_ref()));
+    roon::set_timeout(Duration::from_secs(config.roon_timeout_seconds));
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Setup logging to file (TUI apps can't log to stdout/stderr)
-    let log_file = File::create("/tmp/roon-tui.log").ok();
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    let profile = args.profile.clone().or_else(|| {
+        std::env::var("ROON_TUI_PROFILE")
+            .ok()
+            .filter(|p| !p.is_empty())
+    });
+    if let Some(name) = &profile {
+        Config::set_profile(name);
+    }
+
+    match &args.command {
+        Command::Run => {}
+        Command::Doctor => std::process::exit(if doctor::run(&args) { 0 } else { 1 }),
+        Command::PerfReport => {
+            match metrics::last_report() {
+                Some(report) => print!("{report}"),
+                None => println!("No report yet: it's written when roon-tui exits"),
+            }
+            return Ok(());
+        }
+        Command::Help => {
+            println!("{}", cli::usage());
+            return Ok(());
+        }
+        Command::PrintPaths => {
+            paths::print();
+            return Ok(());
+        }
+        Command::Play { target, zone } => {
+            use_roon_cli(&args);
+            let played = match zone.as_deref() {
+                Some(zone) => roon::set_zone(zone),
+                None => Ok(()),
+            }
+            .and_then(|_| match target {
+                PlayTarget::Path(path) => roon::play_path(path),
+                PlayTarget::Item(id) => roon::play_item(id),
+            });
+            if let Err(e) = played {
+                eprintln!("Error: {e:#}");
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Version => {
+            println!("roon-tui {}", env!("CARGO_PKG_VERSION"));
+            use_roon_cli(&args);
+            match roon::detect() {
+                Ok(Some(cli)) => println!("roon CLI: {}", cli.summary()),
+                Ok(None) => println!("roon CLI: not found"),
+                Err(e) => println!("roon CLI: {}", e),
+            }
+            return Ok(());
+        }
+    }
+
+    // Config first: it picks the log level (first run: no config file yet,
+    // so start the onboarding flow)
+    let first_run = !Config::exists();
+    let (mut config, config_error) = Config::load();
+    args.apply(&mut config);
+    let cores = Cores::load();
+    roon::set_command(&config.roon_invocation(config.core(cores.current())));
+    roon::set_timeout(Duration::from_secs(config.roon_timeout_seconds));
+
+    // Setup logging to file
+    let log_path = paths::log_file();
+    let log_file = log_path
+        .parent()
+        .map(std::fs::create_dir_all)
+        .transpose()
+        .and_then(|_| File::create(&log_path))
+        .ok();
     if let Some(file) = log_file {
         tracing_subscriber::registry()
             .with(
                 tracing_subscriber::EnvFilter::try_from_default_env()
-                    .unwrap_or_else(|_| "roon_tui=debug".into()),
+                    .unwrap_or_else(|_| format!("roon_tui={}", config.log_level).into()),
             )
             .with(tracing_subscriber::fmt::layer().with_writer(file))
             .init();
     }
+    if let Some(e) = config_error {
+        tracing::warn!("{:#}", e);
+    }
+
+    // Before the terminal is taken over, so a too-old CLI is reported plainly
+    // (a missing one is left to the onboarding and status bar)
+    if let Err(e) = roon::detect() {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
 
     // Setup terminal
     enable_raw_mode()?;
@@ -45,139 +199,1620 @@ async fn main() -> Result<()> {
     execute!(stdout, EnterAlternateScreen)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
+    if config.terminal_title {
+        // Save the current title on the terminal's title stack (xterm)
+        execute!(terminal.backend_mut(), Print("\x1b[22;0t"))?;
+    }
 
     // Create app
-    let mut app = App::new();
+    let mut app = App::new(config);
+    app.mini_player = args.mini;
+    if app.config.mouse.enabled {
+        execute!(terminal.backend_mut(), EnableMouseCapture)?;
+    }
+    if first_run {
+        app.show_popup(Popup::Onboarding);
+    } else if app.config.stats.enabled
+        && app.config.stats.startup_summary
+        && app.history.take_summary_due()
+    {
+        app.show_popup(Popup::Stats);
+    }
 
     // Run app
     let result = run_app(&mut terminal, &mut app).await;
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
+    if app.config.terminal_title {
+        execute!(terminal.backend_mut(), Print("\x1b[23;0t"))?;
+    }
+    if app.config.taskbar_progress {
+        execute!(terminal.backend_mut(), Print(taskbar_progress(0, 0)))?;
+    }
+
+    if let Err(err) = result {
+        eprintln!("Error: {err:?}");
+    }
+
+    Ok(())
+}
+
+async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    let mut last_poll = Instant::now();
+    let mut last_input = Instant::now();
+    let poll_interval = Duration::from_secs(1);
+    let frame_interval = Duration::from_millis(1000 / u64::from(app.config.max_fps.max(1)));
+
+    // Shared HTTP client (album art, lyrics, update check) so connections are
+    // reused and a stalled server can't hang a fetch forever
+    let http = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .read_timeout(Duration::from_secs(10))
+        .build()?;
+
+    // Channel for album art loading, plus the in-flight fetches so they can be
+    // cancelled once their track is no longer current (e.g. rapid skipping)
+    let (art_tx, mut art_rx) = mpsc::channel::<AlbumArtMsg>(1);
+    let mut art_task: Option<JoinHandle<()>> = None;
+    let mut prefetch_task: Option<JoinHandle<()>> = None;
+
+    // Channel and provider chain for lyrics loading
+    let (lyrics_tx, mut lyrics_rx) = mpsc::channel::<LyricsMsg>(1);
+    let mut lyrics_fetcher = build_lyrics_fetcher(&app.config, &http);
+
+    // Key polling runs on a blocking thread, kept across loop passes so
+    // there's never more than one
+    let mut key_poll: Option<JoinHandle<io::Result<bool>>> = None;
+
+    // Image picker query, started when the first cover arrives and no key
+    // poll is running. Polling waits until it has returned (it gives up by
+    // itself after a second) so the terminal's reply isn't read as key
+    // presses, nor key presses as the reply.
+    let mut picker_query: Option<JoinHandle<Picker>> = None;
+
+    // Background update check (at most one request a day, cached on disk)
+    let (update_tx, mut update_rx) = mpsc::channel::<String>(1);
+    if app.config.update_check {
+        let http = http.clone();
+        tokio::spawn(async move {
+            if let Some(latest) = update::check(&http).await {
+                let _ = update_tx.send(latest).await;
+            }
+        });
+    }
+
+    // Spectrum bars from cava, if configured (killed when dropped on exit)
+    let (spectrum_tx, mut spectrum_rx) = mpsc::channel::<Vec<u8>>(1);
+    let _visualizer = if app.config.visualizer.enabled {
+        match Visualizer::spawn(
+            &app.config.visualizer,
+            app.config.max_fps,
+            spectrum_tx.clone(),
+        ) {
+            Ok(visualizer) => Some(visualizer),
+            Err(e) => {
+                tracing::warn!("Visualizer unavailable: {:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // System sleep/wake and screen lock notifications, if pausing or
+    // resuming is configured; sleep waits for the pause
+    let (power_tx, mut power_rx) = mpsc::channel::<PowerEvent>(1);
+    let suspend = &app.config.suspend;
+    let mut suspend_monitor = if suspend.pause || suspend.resume {
+        match SuspendMonitor::spawn(power_tx.clone(), suspend.pause) {
+            Ok(monitor) => Some(monitor),
+            Err(e) => {
+                tracing::warn!("Suspend monitor unavailable: {:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let mut paused_for = None;
+
+    // Keyboard media keys, even while the terminal isn't focused (macOS)
+    let (media_tx, mut media_rx) = mpsc::channel::<MediaKey>(4);
+    if app.config.media_keys.enabled && cfg!(target_os = "macos") {
+        match MediaKeys::spawn(media_tx) {
+            Ok(media_keys) => app.media_keys = media_keys,
+            Err(e) => tracing::warn!("Media keys unavailable: {:#}", e),
+        }
+    }
+
+    // Scripts from the plugins directory (killed when dropped on exit)
+    let (plugin_tx, mut plugin_rx) = mpsc::channel::<PluginMsg>(16);
+    if app.config.plugins.enabled {
+        app.plugins = Plugins::load(plugin_tx);
+    }
+
+    // Show the zones as last seen while the first refresh is in flight
+    if let Some(snapshot) = ZoneSnapshot::load() {
+        if app.restore_snapshot(snapshot) {
+            terminal.draw(|frame| ui::draw(frame, app))?;
+        }
+    }
+
+    // Initial data fetch
+    refresh_zones(app);
+    if app.popup == Some(Popup::Onboarding) {
+        check_onboarding_connection(app);
+    }
+
+    // Switch to the zone last used on this Core, or the configured startup zone
+    let last_zone = app
+        .current_core()
+        .and_then(|core| app.cores.zone(&core.name))
+        .map(str::to_string);
+    if let Some(name) = last_zone.or_else(|| app.config.default_zone.clone()) {
+        if app.select_zone_named(&name) {
+            if let Err(e) = roon::set_zone(&name) {
+                tracing::error!("Failed to set default zone: {}", e);
+            }
+        } else {
+            tracing::warn!("Default zone {:?} not found", name);
+        }
+    }
+
+    loop {
+        // Draw UI, but only when something changed (saves bytes over SSH)
+        if app.dirty {
+            let frame_start = Instant::now();
+            terminal.draw(|frame| ui::draw(frame, app))?;
+            app.metrics.record_frame(frame_start.elapsed());
+            app.dirty = false;
+        }
+        app.metrics.record_iteration();
+
+        // Tick at the frame rate while playing or loading, otherwise sleep until the next
+        // zone refresh (input and loaded data still wake the loop right away)
+        let tick = if app.is_playing() || app.is_loading() {
+            frame_interval
+        } else {
+            poll_interval.saturating_sub(last_poll.elapsed())
+        };
+
+        if key_poll.is_none() && picker_query.is_none() {
+            key_poll = Some(tokio::task::spawn_blocking(move || event::poll(tick)));
+        }
+
+        // Wait for events with timeout (this prevents CPU spinning)
+        tokio::select! {
+            _ = tokio::time::sleep(tick) => {}
+
+            // Check for keyboard input
+            _ = async { key_poll.as_mut().expect("key poll running").await },
+                if key_poll.is_some() =>
+            {
+                key_poll = None;
+                if event::poll(Duration::from_millis(0))? {
+                    match event::read()? {
+                        Event::Key(key) if key.kind == KeyEventKind::Press => {
+                            last_input = Instant::now();
+                            let action = handle_key(key, app);
+                            handle_action(action, app);
+                            app.dirty = true;
+                        }
+                        Event::Mouse(mouse) => {
+                            let action = handle_mouse(mouse, app);
+                            if action != Action::None {
+                                last_input = Instant::now();
+                                handle_action(action, app);
+                                app.dirty = true;
+                            }
+                        }
+                        Event::Resize(..) => app.dirty = true,
+                        _ => {}
+                    }
+                }
+            }
+
+            // Check for loaded album art
+            Some(msg) = art_rx.recv() => {
+                match msg {
+                    AlbumArtMsg::Loaded(cover) => {
+                        // Ignore covers for a track that's no longer current
+                        if app.album_art_url.as_deref() == Some(cover.url.as_str()) {
+                            app.set_album_art(cover);
+                        }
+                    }
+                    AlbumArtMsg::Prefetched(cover) => {
+                        app.set_prefetched_art(cover);
+                    }
+                    AlbumArtMsg::Detail(cover) => app.set_detail_art(cover),
+                }
+                app.dirty = true;
+            }
+
+            // Check for loaded lyrics
+            Some(LyricsMsg(key, lyrics)) = lyrics_rx.recv() => {
+                app.set_lyrics(key, lyrics);
+                app.dirty = true;
+            }
+
+            // Image picker query finished
+            picker = async { picker_query.as_mut().expect("picker query running").await },
+                if picker_query.is_some() =>
+            {
+                picker_query = None;
+                let picker = picker.unwrap_or_else(|_| Picker::from_fontsize((10, 20)));
+                app.set_image_picker(picker);
+                app.dirty = true;
+            }
+
+            // New visualizer frame (only redraw while it's on screen)
+            Some(bars) = spectrum_rx.recv() => {
+                if app.visualizer_visible() {
+                    app.spectrum = bars;
+                    app.dirty = true;
+                }
+            }
+
+            // System going to sleep or waking up, screen locking or unlocking
+            Some(event) = power_rx.recv() => {
+                handle_power_event(event, app, &mut paused_for);
+                if let Some(monitor) = &mut suspend_monitor {
+                    match event {
+                        PowerEvent::Sleep => monitor.ready_to_sleep(),
+                        PowerEvent::Wake => monitor.woke(),
+                        PowerEvent::Lock | PowerEvent::Unlock => {}
+                    }
+                }
+            }
+
+            // Media key pressed
+            Some(key) = media_rx.recv() => {
+                handle_media_key(key, app);
+                app.dirty = true;
+            }
+
+            // Action registration or status text from a plugin
+            Some(msg) = plugin_rx.recv() => {
+                if app.plugins.handle(msg) {
+                    app.dirty = true;
+                }
+            }
+
+            // Check for a newer release
+            Some(latest) = update_rx.recv() => {
+                app.update_available = Some(latest);
+                app.dirty = true;
+            }
+
+            // Timeout for UI refresh (smooth progress bar)
+            _ = tokio::time::sleep(tick) => {}
+        }
+
+        // The progress bar and the metrics overlay change on every tick
+        if app.is_playing() || app.is_loading() || app.show_metrics {
+            app.dirty = true;
+        }
+
+        // Start the screensaver once idle, then keep it drifting
+        let screensaver = &app.config.screensaver;
+        if screensaver.enabled
+            && app.screensaver.is_none()
+            && last_input.elapsed() >= Duration::from_secs(screensaver.idle_minutes * 60)
+        {
+            app.screensaver = Some(Screensaver::new());
+            app.dirty = true;
+        }
+        app.tick_screensaver();
+        app.expire_notice();
+
+        // Loop the A–B section: jump back once playback crosses B
+        if let Some(a) = app.ab_repeat_jump() {
+            if let Err(e) = roon::seek(a) {
+                tracing::error!("Failed to jump back to A: {}", e);
+            }
+        }
+
+        // Periodically refresh zone data
+        if last_poll.elapsed() >= poll_interval {
+            refresh_zones(app);
+            last_poll = Instant::now();
+        }
+        app.announcer.tick();
+
+        // Covers can be 3000×3000; never keep more pixels than the terminal shows
+        let max_size = album_art_max_size(app, terminal.size()?);
+
+        // Check if album art needs fetching
+        if let Some(url) = app.album_art_url_if_changed() {
+            let url = url.to_string();
+
+            // Mark as loading by setting the URL
+            app.album_art_url = Some(url.clone());
+
+            if let Some(cover) = app.take_prefetched_art(&url) {
+                app.set_album_art(cover);
+            } else {
+                // Spawn async task to fetch album art
+                let tx = art_tx.clone();
+                let http = http.clone();
+                let task = tokio::spawn(async move {
+                    if let Some(cover) = fetch_album_art(&http, url, max_size).await {
+                        let _ = tx.send(AlbumArtMsg::Loaded(cover)).await;
+                    }
+                });
+                if let Some(stale) = art_task.replace(task) {
+                    stale.abort();
+                }
+            }
+
+            // The track changed, so find out what's up next
+            refresh_queue(app);
+        }
+
+        // Prefetch the upcoming track's cover so the next change is instant
+        if let Some(url) = app.prefetch_art_url_if_needed() {
+            let url = url.to_string();
+            app.prefetch_art_url = Some(url.clone());
+
+            let tx = art_tx.clone();
+            let http = http.clone();
+            let task = tokio::spawn(async move {
+                if let Some(cover) = fetch_album_art(&http, url, max_size).await {
+                    let _ = tx.send(AlbumArtMsg::Prefetched(cover)).await;
+                }
+            });
+            if let Some(stale) = prefetch_task.replace(task) {
+                stale.abort();
+            }
+        }
+
+        // Artwork for the item detail popup
+        if let Some(url) = app.detail_art_url_if_needed() {
+            let tx = art_tx.clone();
+            let http = http.clone();
+            tokio::spawn(async move {
+                if let Some(cover) = fetch_album_art(&http, url, max_size).await {
+                    let _ = tx.send(AlbumArtMsg::Detail(cover)).await;
+                }
+            });
+        }
+
+        // Free decoded album art while it's off screen and nothing is happening,
+        // then rebuild it from the compressed data when Now Playing returns
+        if !app.album_art_visible() && last_input.elapsed() >= ART_RELEASE_IDLE {
+            app.release_album_art();
+        }
+        if let Some((url, encoded)) = app.album_art_to_restore() {
+            if art_task.as_ref().is_none_or(|task| task.is_finished()) {
+                let tx = art_tx.clone();
+                art_task = Some(tokio::spawn(async move {
+                    if let Some(cover) = decode_album_art(url, encoded, max_size).await {
+                        let _ = tx.send(AlbumArtMsg::Loaded(cover)).await;
+                    }
+                }));
+            }
+        }
+
+        // Query the terminal's graphics support once there's a cover to show
+        // and nothing else is reading the terminal
+        if app.needs_image_picker() && picker_query.is_none() && key_poll.is_none() {
+            picker_query = Some(tokio::task::spawn_blocking(|| {
+                Picker::from_query_stdio().unwrap_or_else(|e| {
+                    tracing::warn!("Terminal graphics query failed, using half blocks: {}", e);
+                    Picker::from_fontsize((10, 20))
+                })
+            }));
+        }
+
+        // A token was entered or removed: rebuild the provider chain
+        if app.credentials.changed {
+            app.credentials.changed = false;
+            lyrics_fetcher = build_lyrics_fetcher(&app.config, &http);
+        }
+
+        // Check if lyrics need fetching (only while the lyrics popup is open)
+        if let Some(query) = app.lyrics_query_if_needed() {
+            let key = query.key();
+            let tx = lyrics_tx.clone();
+            let fetcher = Arc::clone(&lyrics_fetcher);
+
+            app.start_lyrics_fetch(key.clone());
+
+            tokio::spawn(async move {
+                tracing::debug!("Fetching lyrics: {}", key);
+                let lyrics = fetcher.fetch(&query).await;
+                let _ = tx.send(LyricsMsg(key, lyrics)).await;
+            });
+        }
+
+        if app.should_quit {
+            app.remember_core_zone();
+            app.snapshot().save();
+            app.resume.save();
+            app.history.save();
+            metrics::save_report(&app.metrics);
+            return Ok(());
+        }
+    }
+}
+
+/// Lyrics provider chain; the Genius token comes from the config (older
+/// setups) or the credential store
+fn build_lyrics_fetcher(config: &Config, http: &reqwest::Client) -> Arc<LyricsFetcher> {
+    let genius_token = config
+        .lyrics
+        .genius_token
+        .clone()
+        .or_else(|| credentials::get(credentials::Service::Genius, &config.credentials));
+    Arc::new(LyricsFetcher::from_config(
+        &config.lyrics,
+        genius_token,
+        http.clone(),
+    ))
+}
+
+/// Run the action of a media key; play and pause only act when they'd
+/// change something
+fn handle_media_key(key: MediaKey, app: &mut App) {
+    let action = match key {
+        MediaKey::PlayPause => Action::PlayPause,
+        MediaKey::Play if !app.is_playing() => Action::PlayPause,
+        MediaKey::Pause if app.is_playing() => Action::PlayPause,
+        MediaKey::Play | MediaKey::Pause => return,
+        MediaKey::Next => Action::NextTrack,
+        MediaKey::Previous => Action::PrevTrack,
+    };
+    handle_action(action, app);
+}
+
+/// Pause the watched zone before suspend (or a screen lock) and resume it
+/// after wake (or unlock), as configured
+///
+/// `paused_for` is what paused it, so only the matching wake or unlock
+/// resumes it.
+fn handle_power_event(event: PowerEvent, app: &mut App, paused_for: &mut Option<PowerEvent>) {
+    let config = &app.config.suspend;
+    if matches!(event, PowerEvent::Lock | PowerEvent::Unlock) && !config.lock {
+        return;
+    }
+    let applies = app
+        .current_zone()
+        .is_some_and(|zone| config.applies_to(&zone.display_name));
+    match event {
+        PowerEvent::Sleep | PowerEvent::Lock => {
+            if config.pause && applies && app.is_playing() {
+                tracing::info!("Pausing {} for {:?}", app.current_zone_name(), event);
+                match roon::playpause() {
+                    Ok(()) => *paused_for = Some(event),
+                    Err(e) => tracing::error!("Failed to pause for {:?}: {}", event, e),
+                }
+            }
+        }
+        PowerEvent::Wake | PowerEvent::Unlock => {
+            let cause = match event {
+                PowerEvent::Wake => PowerEvent::Sleep,
+                _ => PowerEvent::Lock,
+            };
+            if *paused_for == Some(cause) {
+                *paused_for = None;
+                if config.resume && !app.is_playing() {
+                    tracing::info!("Resuming {} after {:?}", app.current_zone_name(), event);
+                    if let Err(e) = roon::playpause() {
+                        tracing::error!("Failed to resume after {:?}: {}", event, e);
+                    }
+                }
+            }
+        }
+    }
+    refresh_zones(app);
+}
+
+/// ConEmu / Windows Terminal progress sequence (OSC 9;4), shown on the
+/// terminal's taskbar button
+fn taskbar_progress(state: u8, percent: u8) -> String {
+    format!("\x1b]9;4;{};{}\x07", state, percent)
+}
+
+/// Refresh zone data from roon CLI
+fn refresh_zones(app: &mut App) {
+    // Only fetch the queue while it's on screen, or to name the next track
+    // in the countdown
+    let with_queue = app.view == View::Queue || app.countdown().is_some();
+
+    match roon::status(with_queue) {
+        Ok(Status {
+            mut zones,
+            queue,
+            warnings,
+        }) => {
+            app.set_parse_warnings(warnings);
+            zones.retain(|zone| !app.zone_rules.is_hidden(&zone.display_name));
+            app.zone_rules.sort(&mut zones);
+            if app.zones != zones || !app.connected {
+                app.dirty = true;
+            }
+            let events = app.update_zones(zones);
+            for (group, changes) in app.regrouped_trims() {
+                tracing::info!("Restoring trims of {}", group);
+                for (output, delta) in changes {
+                    if let Err(e) = roon::volume(&output, &format!("{:+}", delta)) {
+                        tracing::error!("Failed to restore the trim of {}: {}", output, e);
+                    }
+                }
+                app.show_notice(format!("Restored trims of {}", group));
+            }
+            for event in &events {
+                tracing::debug!("Zone event: {:?}", event);
+            }
+            notify_plugins(app, &events);
+            if events
+                .iter()
+                .any(|e| matches!(e, ZoneEvent::SelectedGone(_)))
+            {
+                pause_grouped_zone(app);
+            }
+            let zone = app.selected_zone_position().map(|i| &app.zones[i]);
+            app.media_keys.update(
+                zone.and_then(|z| z.now_playing.as_ref()),
+                zone.is_some_and(|z| z.is_playing()),
+            );
+            if let Some(name) = app.follow_target(&events) {
+                tracing::info!("Following playback to zone {}", name);
+                if let Err(e) = roon::set_zone(&name) {
+                    tracing::error!("Failed to set zone: {}", e);
+                }
+                app.select_zone_named(&name);
+                app.dirty = true;
+            }
+            app.announce_track(&events);
+            if let Some(title) = app.terminal_title_change() {
+                let _ = execute!(io::stdout(), SetTitle(title));
+            }
+            if let Some((state, percent)) = app.taskbar_progress_change() {
+                let _ = execute!(io::stdout(), Print(taskbar_progress(state, percent)));
+            }
+            if app.album_shuffle_due() {
+                queue_random_album(app);
+            }
+            app.track_resume();
+            app.track_listening();
+            app.note_playing();
+            app.connected = true;
+            app.error = None;
+            app.mark_refreshed();
+            tracing::debug!("Refreshed {} zones", app.zones.len());
+
+            match queue {
+                Some(items) => app.set_queue(items),
+                None if with_queue => refresh_queue(app),
+                None => {}
+            }
+        }
+        Err(e) => {
+            // Once per outage, not on every poll that times out again
+            if e.is::<roon::Timeout>() && app.error.as_deref() != Some(&e.to_string()) {
+                app.show_notice(format!("{}, is the Core reachable?", e));
+            }
+            app.connected = false;
+            app.error = Some(e.to_string());
+            app.dirty = true;
+            tracing::error!("Failed to get zones: {}", e);
+        }
+    }
+}
+
+/// Reconnect the roon CLI to another configured Core, coming back to the
+/// zone last selected there
+fn switch_core(app: &mut App, index: usize) {
+    let Some(core) = app.config.cores.get(index).cloned() else {
+        return;
+    };
+    if app
+        .current_core()
+        .is_some_and(|current| current.name == core.name)
+    {
+        return;
+    }
+    app.remember_core_zone();
+    tracing::info!("Switching to Core {}", core.name);
+    roon::set_command(&app.config.roon_invocation(Some(&core)));
+    app.cores.set_current(&core.name);
+    app.reset_for_core();
+    refresh_zones(app);
+    if !app.connected {
+        app.show_notice(format!("Couldn't reach {}", core.name));
+        return;
+    }
+    if let Some(zone) = app.cores.zone(&core.name).map(str::to_string) {
+        if app.select_zone_named(&zone) {
+            if let Err(e) = roon::set_zone(&zone) {
+                tracing::error!("Failed to set zone: {}", e);
+            }
+        }
+    }
+    app.show_notice(format!("Connected to {}", core.name));
+}
+
+/// Open Browse at the library's albums narrowed by the Focus filters
+fn apply_focus(app: &mut App) {
+    let query = match app.focus.query() {
+        Ok(query) if query.is_empty() => {
+            app.focus.message = Some("Set at least one filter".to_string());
+            return;
+        }
+        Ok(query) => query,
+        Err(message) => {
+            app.focus.message = Some(message);
+            return;
+        }
+    };
+    let summary = query.summary();
+    match app.browser.open(BrowseRoot::Focus(query)) {
+        Ok((path, result)) => {
+            app.browse.reset();
+            app.browse.path = Some(path);
+            app.browse.set_items(result.into_list());
+            app.browse.breadcrumbs = vec![format!("Focus: {}", summary)];
+            app.view = View::Browse;
+            app.close_popup();
+        }
+        Err(e) => {
+            tracing::error!("Failed to apply Focus {}: {}", summary, e);
+            app.focus.message = Some(format!("{e:#} (does the roon CLI support `focus`?)"));
+        }
+    }
+}
+
+/// Open Browse straight at a library section, e.g. "Genres"
+fn open_library_section(app: &mut App, title: &str) {
+    match app.browser.library_section(title) {
+        Ok(Some((path, result, breadcrumbs))) => {
+            app.browse.reset();
+            app.browse.path = Some(path);
+            app.browse.set_items(result.into_list());
+            app.browse.breadcrumbs = breadcrumbs;
+            app.view = View::Browse;
+        }
+        Ok(None) => app.show_notice(format!("No {} in the library", title)),
+        Err(e) => {
+            tracing::error!("Failed to open {}: {}", title, e);
+            app.show_notice(format!("Couldn't open {}: {}", title, e));
+        }
+    }
+}
+
+/// Gather the For You mixes and recommendations, keeping the selection
+/// where it was; `fresh` asks the CLI again instead of reusing cached levels
+fn refresh_for_you(app: &mut App, fresh: bool) {
+    let titles = app.config.for_you.sections.clone();
+    let services = app.config.search.sections.clone();
+    let list = &mut app.for_you.list;
+    match app.browser.for_you(&titles, &services, fresh) {
+        Ok((items, origins)) => {
+            list.selected_index = list.selected_index.min(items.len().saturating_sub(1));
+            list.error =
+                (items.is_empty()).then(|| format!("Nothing found under {}", titles.join(", ")));
+            list.items = items;
+            app.for_you.origins = origins;
+        }
+        Err(e) => {
+            tracing::error!("Failed to fetch For You: {}", e);
+            list.error = Some(e.to_string());
+        }
+    }
+    app.for_you.fetched_at = Some(Instant::now());
+}
+
+/// Run "Play Now" or "Queue" on the selected For You item
+fn run_for_you_action(app: &mut App, action: &str) {
+    let index = app.for_you.list.selected_index;
+    let (Some(item), Some((path, at))) = (
+        app.for_you.list.items.get(index),
+        app.for_you.origins.get(index).cloned(),
+    ) else {
+        return;
+    };
+    let title = item.title.clone();
+    if action == "Play Now" {
+        set_start_volume(app.start_volume_changes());
+    }
+    match app.browser.run_action(&path, at, action) {
+        Ok(true) => {
+            app.show_notice(action_notice(action, Some(&title)));
+            refresh_zones(app);
+        }
+        Ok(false) => app.show_notice(format!("\"{}\" has no {} action", title, action)),
+        Err(e) => {
+            tracing::error!("Failed to run {} on {}: {}", action, title, e);
+            app.show_notice(format!("Couldn't play {}: {}", title, e));
+        }
+    }
+}
+
+/// Queue a random album after the last track (album-shuffle mode)
+fn queue_random_album(app: &mut App) {
+    match app.browser.random_album("Queue") {
+        Ok(Some((album, _))) => {
+            tracing::info!("Album shuffle queued {}", album);
+            app.show_notice(format!("Up next: {}", album));
+        }
+        Ok(None) => app.show_notice("No albums in the library"),
+        Err(e) => {
+            tracing::error!("Failed to queue a random album: {}", e);
+            app.show_notice(format!("Couldn't queue a random album: {}", e));
+        }
+    }
+}
+
+/// Run a search into the search results: unified (library and service
+/// sections merged by category) when configured and the results have
+/// sections, otherwise plain
+fn open_search(app: &mut App, query: &str) -> Result<()> {
+    let search = &app.config.search;
+    let results = &mut app.search.results;
+    if search.unified {
+        match app.browser.unified_search(query, &search.sections) {
+            Ok(Some((path, categories))) => {
+                results.path = Some(path);
+                results.set_items(categories.iter().map(category_item).collect());
+                app.search.merged = Some(Merged::Categories(categories));
+                return Ok(());
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Unified search failed, searching plainly: {}", e),
+        }
+    }
+    let (path, result) = app.browser.open(BrowseRoot::Search(query.to_string()))?;
+    if let Some(title) = &result.title {
+        results.breadcrumbs = vec![title.clone()];
+    }
+    results.set_items(result.into_list());
+    for (index, item) in results.items.iter_mut().enumerate() {
+        if item.hint.as_deref() == Some("list") {
+            item.title = counted(&item.title, app.browser.count(&path, index));
+        }
+    }
+    results.path = Some(path);
+    Ok(())
+}
+
+/// A category title with its number of entries, when known ("Albums (128)")
+fn counted(title: &str, count: Option<usize>) -> String {
+    match count {
+        Some(count) => format!("{} ({})", title, count),
+        None => title.to_string(),
+    }
+}
+
+/// List entry of a merged category, with its sources as the subtitle
+fn category_item(category: &MergedCategory) -> BrowseItem {
+    let sources: Vec<&str> = category.sources.iter().map(|(s, _)| s.as_str()).collect();
+    BrowseItem {
+        item_key: None,
+        title: counted(&category.title, category.count),
+        subtitle: Some(sources.join(" · ")),
+        image_key: None,
+        hint: Some("list".to_string()),
+        album_art_url: None,
+        year: None,
+    }
+}
+
+/// Open the detail popup for the selected Browse or Search item, fetching
+/// what it holds: the number of entries of a list, or the actions of an
+/// action list (plain actions would run, so they aren't fetched)
+///
+/// In the Queue view the queue item already carries its details.
+fn show_item_detail(app: &mut App) {
+    if app.view == View::Queue {
+        if app.queue.items.get(app.queue.selected_index).is_some() {
+            app.show_popup(Popup::QueueItem);
+        }
+        return;
+    }
+    let Some((item, level)) = selected_browse_item(app) else {
+        return;
+    };
+
+    let mut detail = ItemDetail::new(app.image_url(&item), item);
+    let fetch = matches!(detail.item.hint.as_deref(), Some("list" | "action_list"));
+    if let Some((path, index)) = level.filter(|_| fetch) {
+        match app.browser.select(&path, index) {
+            Ok(result) if detail.item.hint.as_deref() == Some("action_list") => {
+                detail.actions = result.items.into_iter().map(|i| i.title).collect();
+            }
+            Ok(result) => detail.count = Some(result.count.unwrap_or(result.items.len())),
+            Err(e) => {
+                tracing::error!("Failed to fetch item details: {}", e);
+                detail.error = Some(e.to_string());
+            }
+        }
+    }
+    app.item_detail = Some(detail);
+    app.show_popup(Popup::ItemDetail);
+}
+
+/// The selected item of a browse-like view (Browse, Search, For You) and
+/// where it's selected in the browse tree (None for merged categories,
+/// which only exist in the UI)
+fn selected_browse_item(app: &App) -> Option<(BrowseItem, Option<Origin>)> {
+    let (state, merged) = match app.view {
+        View::Browse => (&app.browse, None),
+        View::Search => (&app.search.results, app.search.merged.as_ref()),
+        View::ForYou => {
+            let index = app.for_you.list.selected_index;
+            let item = app.for_you.list.items.get(index)?.clone();
+            return Some((item, app.for_you.origins.get(index).cloned()));
+        }
+        _ => return None,
+    };
+    let index = state.selected_index;
+    let item = state.items.get(index)?.clone();
+    let level = match merged {
+        Some(Merged::Items(_, origins)) => origins.get(index).cloned(),
+        Some(Merged::Categories(_)) => None,
+        None => state
+            .path
+            .clone()
+            .map(|path| (path, state.level_index(index))),
+    };
+    Some((item, level))
+}
+
+/// Start a radio seeded by the selected browse item, through its "Start
+/// Radio" action, without playing the item itself first
+fn play_similar(app: &mut App) {
+    let Some((item, level)) = selected_browse_item(app) else {
+        return;
+    };
+    let Some((path, index)) = level else {
+        app.show_notice(format!("\"{}\" has no Start Radio action", item.title));
+        return;
+    };
+    set_start_volume(app.start_volume_changes());
+    match app.browser.run_action(&path, index, "Start Radio") {
+        Ok(true) => {
+            app.show_notice(action_notice("Start Radio", Some(&item.title)));
+            refresh_zones(app);
+        }
+        Ok(false) => app.show_notice(format!("\"{}\" has no Start Radio action", item.title)),
+        Err(e) => {
+            tracing::error!("Failed to start a radio from {}: {}", item.title, e);
+            app.show_notice(format!("Couldn't start a radio: {}", e));
+        }
+    }
+}
+
+/// Load the rest of a partly loaded Browse or Search level in place of its
+/// "Show more" entry, keeping the selection on the first new entry
+fn show_more(app: &mut App, path: &BrowsePath, is_search: bool) {
+    let result = app.browser.load_all(path);
+    let state = if is_search {
+        &mut app.search.results
+    } else {
+        &mut app.browse
+    };
+    match result {
+        Ok(level) => state.set_items(level.into_list()),
+        Err(e) => {
+            tracing::error!("Failed to load more items: {}", e);
+            state.error = Some(e.to_string());
+        }
+    }
+    sort_discography(app);
+}
+
+/// Whether Browse or Search shows an artist's page (merged search levels
+/// never are one)
+fn shows_discography(app: &App) -> bool {
+    match app.view {
+        View::Browse => app.browse.is_discography(),
+        View::Search => app.search.merged.is_none() && app.search.results.is_discography(),
+        _ => false,
+    }
+}
+
+/// Put an artist's albums in the chosen order (other levels are left alone)
+fn sort_discography(app: &mut App) {
+    if !shows_discography(app) {
+        return;
+    }
+    let history = &app.history;
+    let state = match app.view {
+        View::Browse => &mut app.browse,
+        _ => &mut app.search.results,
+    };
+    let artist = state.breadcrumbs.last().cloned().unwrap_or_default();
+    state.sort_albums(app.discography_sort, |album| {
+        history.album_plays(&artist, &album.title)
+    });
+}
+
+/// Enter an item of a merged search level
+fn select_merged(app: &mut App, index: usize) {
+    let Some(merged) = app.search.merged.clone() else {
+        return;
+    };
+    let is_show_more = app
+        .search
+        .results
+        .items
+        .get(index)
+        .is_some_and(BrowseItem::is_show_more);
+    let result = match &merged {
+        Merged::Categories(categories) => {
+            let Some(category) = categories.get(index) else {
+                return;
+            };
+            app.browser
+                .merged_items(category, false)
+                .map(|(items, origins)| {
+                    let title = category.title.clone();
+                    let merged = Merged::Items(category.clone(), origins);
+                    app.search
+                        .enter_from_merged(Some(merged), items, None, title);
+                })
+        }
+        Merged::Items(category, _) if is_show_more => {
+            app.browser
+                .merged_items(category, true)
+                .map(|(items, origins)| {
+                    app.search.merged = Some(Merged::Items(category.clone(), origins));
+                    app.search.results.set_items(items);
+                })
+        }
+        Merged::Items(_, origins) => {
+            let Some((parent, i)) = origins.get(index).cloned() else {
+                return;
+            };
+            match app.browser.select(&parent, i) {
+                Ok(result) if result.action.as_deref() == Some("message") => {
+                    // Play action executed - switch to Now Playing
+                    app.view = View::NowPlaying;
+                    refresh_zones(app);
+                    Ok(())
+                }
+                Ok(result) => {
+                    let title = result
+                        .title
+                        .clone()
+                        .unwrap_or_else(|| app.search.results.items[index].title.clone());
+                    let path = Some(parent.child(i));
+                    app.search
+                        .enter_from_merged(None, result.into_list(), path, title);
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            }
+        }
+    };
+    if let Err(e) = result {
+        tracing::error!("Failed to select item: {}", e);
+        app.search.results.error = Some(e.to_string());
+    }
+}
+
+/// Forward track and state changes (and a new selected zone) to plugins
+fn notify_plugins(app: &mut App, events: &[ZoneEvent]) {
+    for event in events {
+        if let (ZoneEvent::SelectedGone(_), Some(gone)) = (event, &app.zone_gone) {
+            let successor = gone
+                .successor
+                .as_ref()
+                .and_then(|id| app.zones.iter().find(|z| &z.zone_id == id))
+                .map(|z| z.display_name.clone());
+            let zone = gone.name.clone();
+            app.plugins.zone_gone(&zone, successor.as_deref());
+            continue;
+        }
+        let (ZoneEvent::TrackChanged(id) | ZoneEvent::StateChanged(id)) = event else {
+            continue;
+        };
+        let Some(zone) = app.zones.iter().find(|z| &z.zone_id == id) else {
+            continue;
+        };
+        let plugin_event = match (event, &zone.now_playing) {
+            (ZoneEvent::TrackChanged(_), Some(np)) => plugins::Event::TrackChanged {
+                zone: &zone.display_name,
+                artist: &np.artist,
+                track: &np.track,
+                album: &np.album,
+            },
+            (ZoneEvent::StateChanged(_), _) => plugins::Event::StateChanged {
+                zone: &zone.display_name,
+                state: zone.state.as_str(),
+            },
+            _ => continue,
+        };
+        app.plugins.send(&plugin_event);
+    }
+    if let Some(zone) = app.current_zone() {
+        let name = zone.display_name.clone();
+        app.plugins.zone_selected(&name);
+    }
+    if app.plugins.take_redraw() {
+        app.dirty = true;
+    }
+}
+
+/// Auto-pause after the selected zone was grouped away: the zone that took
+/// over its outputs is paused if it's playing there
+fn pause_grouped_zone(app: &mut App) {
+    // Party mode keeps the music going and the zone where it is
+    if !app.config.zones.pause_when_grouped || app.party.on {
+        return;
+    }
+    let Some(gone) = &app.zone_gone else {
+        return;
+    };
+    let Some(zone) = gone
+        .successor
+        .as_ref()
+        .and_then(|id| app.zones.iter().find(|z| &z.zone_id == id))
+        .filter(|z| z.is_playing())
+    else {
+        return;
+    };
+    let (name, gone_name) = (zone.display_name.clone(), gone.name.clone());
+    tracing::info!("Pausing {}, {} was grouped into it", name, gone_name);
+    match roon::set_zone(&name).and_then(|_| roon::playpause()) {
+        Ok(()) => app.show_notice(format!(
+            "Paused {}: {} was grouped into it",
+            name, gone_name
+        )),
+        Err(e) => {
+            tracing::error!("Failed to pause {}: {}", name, e);
+            app.show_notice(format!("Couldn't pause {}: {}", name, e));
+        }
+    }
+}
+
+/// Download and decode album art, downscaled to at most `max_size` pixels
+async fn fetch_album_art(http: &reqwest::Client, url: String, max_size: u32) -> Option<Cover> {
+    tracing::debug!("Fetching album art: {}", url);
+    let bytes = match http.get(&url).send().await {
+        Ok(response) => response.bytes().await.ok()?,
+        Err(e) => {
+            tracing::warn!("Failed to fetch album art: {}", e);
+            return None;
+        }
+    };
+    decode_album_art(url, Arc::from(bytes.as_ref()), max_size).await
+}
+
+/// Decode (and downscale) album art off the async runtime
+async fn decode_album_art(url: String, encoded: Arc<[u8]>, max_size: u32) -> Option<Cover> {
+    let decoded = tokio::task::spawn_blocking(move || {
+        image::load_from_memory(&encoded).map(|image| Cover {
+            url,
+            image: downscale(image, max_size),
+            encoded,
+        })
+    })
+    .await;
+    match decoded {
+        Ok(Ok(cover)) => {
+            tracing::debug!("Loaded album art");
+            Some(cover)
+        }
+        _ => {
+            tracing::warn!("Failed to decode album art image");
+            None
+        }
+    }
+}
+
+/// Largest useful album art size in pixels: the art is square, so it can't
+/// be bigger than the terminal's shorter side
+fn album_art_max_size(app: &App, terminal: Size) -> u32 {
+    let (font_width, font_height) = app
+        .image_picker
+        .as_ref()
+        .map(|picker| picker.font_size())
+        .unwrap_or((10, 20));
+    let width = u32::from(terminal.width) * u32::from(font_width);
+    let height = u32::from(terminal.height) * u32::from(font_height);
+    width.min(height).max(1)
+}
+
+/// Shrink an image to fit within `max_size` × `max_size`, keeping its aspect ratio
+fn downscale(image: DynamicImage, max_size: u32) -> DynamicImage {
+    if image.width() <= max_size && image.height() <= max_size {
+        return image;
+    }
+    image.resize(max_size, max_size, FilterType::Triangle)
+}
+
+/// Refresh the play queue from roon CLI
+fn refresh_queue(app: &mut App) {
+    match roon::queue() {
+        Ok(items) => app.set_queue(items),
+        Err(e) => {
+            app.queue.error = Some(e.to_string());
+            app.dirty = true;
+            tracing::error!("Failed to get queue: {}", e);
+        }
+    }
+}
 
-    if let Err(err) = result {
-        eprintln!("Error: {err:?}");
+/// Remove tracks queued more than once, keeping the first of each
+fn dedupe_queue(app: &mut App) {
+    refresh_queue(app);
+    let duplicates = app.queue_duplicates();
+    if duplicates.is_empty() {
+        app.show_notice("No duplicates in the queue");
+        return;
+    }
+    let mut removed = 0;
+    for id in &duplicates {
+        match roon::queue_remove(*id) {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                tracing::error!("Failed to remove queue item {}: {}", id, e);
+                app.show_notice(format!("Couldn't remove duplicates: {}", e));
+                break;
+            }
+        }
+    }
+    if removed > 0 {
+        let s = if removed == 1 { "" } else { "s" };
+        app.show_notice(format!("Removed {} duplicate{}", removed, s));
     }
+    refresh_queue(app);
+}
 
-    Ok(())
+/// Bring an idle zone to its configured start level before playback starts
+fn set_start_volume(changes: Vec<(String, f64)>) {
+    for (output, delta) in changes {
+        tracing::info!("Setting {} to its start volume", output);
+        if let Err(e) = roon::volume(&output, &format!("{:+}", delta)) {
+            tracing::error!("Failed to set the start volume of {}: {}", output, e);
+        }
+    }
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
-    let mut last_poll = Instant::now();
-    let poll_interval = Duration::from_secs(1);
+/// Volume change per key press, in percent of each output's range
+const VOLUME_STEP: f64 = 5.0;
 
-    // Channel for album art loading
-    let (art_tx, mut art_rx) = mpsc::channel::<AlbumArtMsg>(1);
+/// Trim change per key press, in percent of the output's range
+const TRIM_STEP: f64 = 1.0;
 
-    // Initial data fetch
+/// Move the trim popup's selected output up or down against the group's
+/// reference, and save the group's trims
+fn nudge_trim(app: &mut App, step: f64) {
+    let (output, delta) = match app.trim_step(step) {
+        Ok(change) => change,
+        Err(message) => return app.show_notice(message),
+    };
+    if let Err(e) = roon::volume(&output, &format!("{:+}", delta)) {
+        tracing::error!("Failed to trim {}: {}", output, e);
+        app.show_notice(format!("Couldn't trim {}: {}", output, e));
+        return;
+    }
     refresh_zones(app);
+    app.remember_trims();
+    let trim = app.current_zone().and_then(|zone| {
+        let index = zone.outputs.iter().position(|o| o.display_name == output)?;
+        trims::offsets(&zone.outputs)[index]
+    });
+    if let Some(trim) = trim {
+        app.show_notice(format!("{} trim {}", output, trims::display(trim)));
+    }
+}
 
-    loop {
-        // Draw UI
-        terminal.draw(|frame| ui::draw(frame, app))?;
-
-        // Wait for events with timeout (this prevents CPU spinning)
-        tokio::select! {
-            // Check for keyboard input
-            _ = tokio::task::spawn_blocking(|| event::poll(Duration::from_millis(100))) => {
-                if event::poll(Duration::from_millis(0))? {
-                    if let Event::Key(key) = event::read()? {
-                        if key.kind == KeyEventKind::Press {
-                            let action = handle_key(key, app);
-                            handle_action(action, app);
-                        }
-                    }
-                }
-            }
+/// Send a relative volume change to the outputs it applies to
+fn adjust_volume(app: &mut App, step: f64) {
+    let steps = app.volume_steps(step);
+    if steps.is_empty() {
+        return;
+    }
+    let mut failed = None;
+    for (output, delta) in &steps {
+        if let Err(e) = roon::volume(output, &format!("{:+}", delta)) {
+            tracing::error!("Failed to change volume of {}: {}", output, e);
+            failed = Some(e);
+        }
+    }
+    refresh_zones(app);
+    if let Some(e) = failed {
+        app.show_notice(format!("Couldn't change the volume: {}", e));
+        return;
+    }
+    // The new level of the first output adjusted
+    let level = app
+        .current_zone()
+        .and_then(|zone| zone.outputs.iter().find(|o| o.display_name == steps[0].0))
+        .and_then(|output| output.volume.as_ref())
+        .map(|volume| volume.level_display());
+    if let Some(level) = level {
+        app.show_notice(format!("Volume {}", level));
+    }
+}
 
-            // Check for loaded album art
-            Some(msg) = art_rx.recv() => {
-                match msg {
-                    AlbumArtMsg::Loaded(image, url) => {
-                        app.set_album_art(image, url);
-                    }
-                }
-            }
+/// Actions party mode locks: quitting, switching zones or Cores, editing
+/// the queue and rebinding keys
+fn locked_in_party(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::Quit
+            | Action::ForceQuit
+            | Action::ShowZoneSelector
+            | Action::ShowCoreSwitcher
+            | Action::FollowSuccessorZone
+            | Action::ToggleFollow
+            | Action::DedupeQueue
+            | Action::StartRebind
+            | Action::ResetBinding
+    )
+}
 
-            // Timeout for UI refresh (smooth progress bar)
-            _ = tokio::time::sleep(Duration::from_millis(50)) => {}
-        }
+/// Start party mode, or end it: with the passphrase when one is set,
+/// otherwise by holding the party mode key
+fn toggle_party(app: &mut App) {
+    if !app.party.on {
+        app.party.on = true;
+        app.show_notice("Party mode on: quitting and zone switching are locked");
+    } else if app.config.party.passphrase.is_some() {
+        app.show_popup(Popup::PartyUnlock);
+    } else if app.party.hold() {
+        app.party.on = false;
+        app.show_notice("Party mode off");
+    } else {
+        let key = app.keymap.keys_display(&Action::ToggleParty);
+        app.show_notice(format!("Hold {} to leave party mode", key));
+    }
+}
 
-        // Periodically refresh zone data
-        if last_poll.elapsed() >= poll_interval {
-            refresh_zones(app);
-            last_poll = Instant::now();
+/// Show the outcome of a command in the status bar: what it did, or why
+/// it failed (also logged)
+fn report(app: &mut App, result: Result<()>, done: String, what: &str) {
+    match result {
+        Ok(()) => app.show_notice(done),
+        Err(e) => {
+            tracing::error!("Failed to {}: {}", what, e);
+            app.show_notice(format!("Couldn't {}: {}", what, e));
         }
+    }
+}
 
-        // Check if album art needs fetching
-        if let Some(url) = app.album_art_url_if_changed() {
-            let url = url.to_string();
-            let tx = art_tx.clone();
+fn on_off(on: bool) -> &'static str {
+    if on {
+        "on"
+    } else {
+        "off"
+    }
+}
 
-            // Mark as loading by setting the URL
-            app.album_art_url = Some(url.clone());
+/// Notice for a browse action that ran ("Queued: OK Computer")
+fn action_notice(action: &str, subject: Option<&str>) -> String {
+    let done = match action {
+        "Play Now" => "Playing",
+        "Queue" | "Add to Queue" => "Queued",
+        "Add Next" => "Playing next",
+        "Start Radio" => "Radio from",
+        _ => action,
+    };
+    match subject {
+        Some(subject) => format!("{}: {}", done, subject),
+        None => done.to_string(),
+    }
+}
 
-            // Spawn async task to fetch album art
-            tokio::spawn(async move {
-                tracing::debug!("Fetching album art: {}", url);
-                match reqwest::get(&url).await {
-                    Ok(response) => {
-                        if let Ok(bytes) = response.bytes().await {
-                            if let Ok(image) = image::load_from_memory(&bytes) {
-                                let _ = tx.send(AlbumArtMsg::Loaded(image, url)).await;
-                                tracing::debug!("Loaded album art");
-                            } else {
-                                tracing::warn!("Failed to decode album art image");
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to fetch album art: {}", e);
-                    }
-                }
-            });
-        }
+/// Re-run the onboarding connection check (CLI present, Core reachable)
+fn check_onboarding_connection(app: &mut App) {
+    app.onboarding.cli_found = roon::is_available();
+    refresh_zones(app);
+}
 
-        if app.should_quit {
-            return Ok(());
-        }
+/// Finish onboarding by writing the initial config file
+fn finish_onboarding(app: &mut App) {
+    let zone = app.onboarding.default_zone.take();
+    if let Err(e) = Config::save_default_zone(zone.as_deref()) {
+        tracing::error!("Failed to write initial config: {}", e);
     }
+    app.config.default_zone = zone;
+    app.close_popup();
 }
 
-/// Refresh zone data from roon CLI
-fn refresh_zones(app: &mut App) {
-    match roon::get_zones() {
-        Ok(zones) => {
-            app.zones = zones;
-            app.connected = true;
-            app.error = None;
-            app.mark_refreshed();
-            tracing::debug!("Refreshed {} zones", app.zones.len());
-        }
+/// Persist keybinding overrides, returning a status message for the editor
+fn save_keymap(app: &App) -> String {
+    match Config::save_keys(&app.keymap.overrides()) {
+        Ok(()) => "Saved to config".to_string(),
         Err(e) => {
-            app.connected = false;
-            app.error = Some(e.to_string());
-            tracing::error!("Failed to get zones: {}", e);
+            tracing::error!("Failed to save keybindings: {}", e);
+            format!("Failed to save: {}", e)
         }
     }
 }
 
 fn handle_action(action: Action, app: &mut App) {
+    // Any key in a leader menu either opens a submenu or ends the sequence
+    if !matches!(action, Action::OpenLeaderMenu(_)) {
+        app.leader_menu = None;
+    }
+
+    if app.party.on && locked_in_party(&action) {
+        app.show_notice("Locked in party mode");
+        return;
+    }
+
     match action {
-        Action::Quit => app.should_quit = true,
+        Action::OpenLeaderMenu(menu) => app.leader_menu = Some(menu),
+        Action::ToggleParty => toggle_party(app),
+        Action::PartyChar(c) => app.party.input.push(c),
+        Action::PartyBackspace => {
+            app.party.input.pop();
+        }
+        Action::PartySubmit => {
+            if app.config.party.passphrase.as_deref() == Some(app.party.input.as_str()) {
+                app.party.on = false;
+                app.close_popup();
+                app.show_notice("Party mode off");
+            } else {
+                app.party.input.clear();
+                app.party.message = Some("Wrong passphrase".to_string());
+            }
+        }
+        Action::Quit => app.request_quit(),
+        Action::ForceQuit => app.should_quit = true,
         Action::ShowHelp => app.show_popup(Popup::Help),
         Action::ClosePopup => app.close_popup(),
+        Action::RunPlugin(index) => {
+            app.plugins.run_action(index);
+            if app.plugins.take_redraw() {
+                app.dirty = true;
+            }
+        }
+        Action::ShowCredentials => app.show_popup(Popup::Credentials),
+        Action::CredentialsEdit => app.credentials.input = Some(String::new()),
+        Action::CredentialsChar(c) => {
+            if let Some(input) = &mut app.credentials.input {
+                input.push(c);
+            }
+        }
+        Action::CredentialsBackspace => {
+            if let Some(input) = &mut app.credentials.input {
+                input.pop();
+            }
+        }
+        Action::CredentialsCancel => app.credentials.input = None,
+        Action::CredentialsSubmit => {
+            let token = app.credentials.input.take().unwrap_or_default();
+            let token = token.trim();
+            if !token.is_empty() {
+                let service = app.credentials.selected();
+                app.credentials.message = Some(
+                    match credentials::set(service, token, &app.config.credentials) {
+                        Ok(credentials::Store::Keyring) => "Saved to the keyring".to_string(),
+                        Ok(credentials::Store::Plaintext) => {
+                            "Saved to credentials.toml".to_string()
+                        }
+                        Err(e) => format!("Not saved: {:#}", e),
+                    },
+                );
+                app.credentials.changed = true;
+                app.refresh_credentials();
+            }
+        }
+        Action::CredentialsDelete => {
+            let service = app.credentials.selected();
+            app.credentials.message = Some(
+                match credentials::delete(service, &app.config.credentials) {
+                    Ok(()) => "Removed".to_string(),
+                    Err(e) => format!("Not removed: {:#}", e),
+                },
+            );
+            app.credentials.changed = true;
+            app.refresh_credentials();
+        }
+        Action::ShowFocus => app.show_popup(Popup::Focus),
+        Action::FocusEdit => {
+            let value = app.focus.values[app.focus.selected_index].clone();
+            app.focus.input = Some(value);
+        }
+        Action::FocusChar(c) => {
+            if let Some(input) = &mut app.focus.input {
+                input.push(c);
+            }
+        }
+        Action::FocusBackspace => {
+            if let Some(input) = &mut app.focus.input {
+                input.pop();
+            }
+        }
+        Action::FocusCancel => app.focus.input = None,
+        Action::FocusSubmit => {
+            if let Some(input) = app.focus.input.take() {
+                app.focus.values[app.focus.selected_index] = input.trim().to_string();
+                app.focus.message = None;
+            }
+        }
+        Action::FocusClear => {
+            app.focus.values[app.focus.selected_index].clear();
+            app.focus.message = None;
+        }
+        Action::FocusApply => apply_focus(app),
+        Action::ResumePlayback => {
+            if let Some(position) = app.resume_offer.take() {
+                if let Err(e) = roon::seek(position) {
+                    tracing::error!("Failed to resume: {}", e);
+                }
+            }
+            app.close_popup();
+            refresh_zones(app);
+        }
         Action::PlayPause => {
+            if !app.is_playing() {
+                set_start_volume(app.start_volume_changes());
+            }
             if let Err(e) = roon::playpause() {
                 tracing::error!("Failed to toggle play/pause: {}", e);
             }
             refresh_zones(app);
         }
         Action::ShowZoneSelector => app.show_popup(Popup::ZoneSelector),
+        Action::ShowCoreSwitcher => {
+            if app.config.cores.is_empty() {
+                app.show_notice("No Cores configured (add [[cores]] to the config)");
+            } else {
+                app.show_popup(Popup::CoreSwitcher);
+            }
+        }
+        Action::SelectCore => {
+            let index = app.core_index;
+            app.close_popup();
+            switch_core(app, index);
+        }
+        Action::ShowDspPresets => {
+            match roon::dsp_presets() {
+                Ok(presets) => {
+                    app.dsp_presets = presets;
+                    app.dsp_error = None;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to get DSP presets: {}", e);
+                    app.dsp_presets.clear();
+                    app.dsp_error = Some(e.to_string());
+                }
+            }
+            app.show_popup(Popup::DspPresets);
+        }
+        Action::SelectDspPreset => {
+            let Some(preset) = app.dsp_presets.get(app.dsp_index) else {
+                return;
+            };
+            let name = preset.name.clone();
+            app.close_popup();
+            let result = roon::set_dsp_preset(&name);
+            report(
+                app,
+                result,
+                format!("DSP preset {}", name),
+                "switch DSP preset",
+            );
+            refresh_zones(app);
+        }
+        Action::ShowOpenIn => app.show_popup(Popup::OpenIn),
+        Action::ShowLyrics => app.show_popup(Popup::Lyrics),
+        Action::ShowVolume => app.show_popup(Popup::Volume),
+        Action::ToggleVolumeLink => app.volume_linked = !app.volume_linked,
+        Action::ShowThemeSwitcher => app.show_popup(Popup::ThemeSwitcher),
+        Action::ShowStats => app.show_popup(Popup::Stats),
+        Action::StatsPrevYear => app.step_stats_year(false),
+        Action::StatsNextYear => app.step_stats_year(true),
+        Action::ToggleMetrics => app.show_metrics = !app.show_metrics,
+        Action::ToggleSplitView => app.split_view = !app.split_view,
+        Action::ToggleZenMode => app.zen_mode = !app.zen_mode,
+        Action::ToggleFollow => app.follow = !app.follow,
+        Action::ToggleTimeDisplay => app.show_remaining = !app.show_remaining,
+        Action::CycleAbRepeat => {
+            let notice = app.cycle_ab_repeat();
+            app.show_notice(notice);
+        }
+        Action::ToggleAlbumShuffle => {
+            app.album_shuffle = !app.album_shuffle;
+            let state = if app.album_shuffle { "on" } else { "off" };
+            app.show_notice(format!("Album shuffle {}", state));
+        }
+        Action::HelpSwitchPage => app.switch_help_page(),
+        Action::HelpStartFilter => app.start_help_input(HelpInput::Filter),
+        Action::HelpFilterChar(c) => app.edit_help_filter(Some(c)),
+        Action::HelpFilterBackspace => app.edit_help_filter(None),
+        Action::HelpFilterDone => app.help_input = HelpInput::Browse,
+        Action::HelpFilterClear => {
+            app.help_filter.clear();
+            app.help_input = HelpInput::Browse;
+        }
+        Action::HelpStartLookup => app.start_help_input(HelpInput::Lookup),
+        Action::HelpLookup(key) => app.lookup_help_key(key),
+        Action::ShowFullscreenArt => app.fullscreen_art = true,
+        Action::CopyTrackInfo => {
+            if let Some(text) = app.track_info_text() {
+                match clipboard::copy(&text, app.config.osc52) {
+                    Ok(method) => {
+                        tracing::debug!("Copied track info ({:?})", method);
+                        app.show_notice("Copied track info");
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to copy track info: {:#}", e);
+                        app.show_notice(format!("Copy failed: {}", e));
+                    }
+                }
+            }
+        }
+        Action::CloseFullscreenArt => app.fullscreen_art = false,
+        Action::WakeScreensaver => app.screensaver = None,
+        Action::ToggleMiniPlayer => {
+            app.mini_player = !app.mini_player;
+            app.view = View::NowPlaying;
+        }
+
+        // ========== Onboarding ==========
+        Action::OnboardingRetry => {
+            if app.onboarding.step == OnboardingStep::Connection {
+                check_onboarding_connection(app);
+            }
+        }
+        Action::OnboardingNext => match app.onboarding.step {
+            OnboardingStep::Connection => {
+                app.zone_selector_index = app.selected_zone_position().unwrap_or(0);
+                app.onboarding.step = OnboardingStep::Zone;
+            }
+            OnboardingStep::Zone => {
+                if let Some(name) = app.get_selected_zone_name() {
+                    if let Err(e) = roon::set_zone(&name) {
+                        tracing::error!("Failed to set zone: {}", e);
+                    }
+                    app.select_zone_named(&name);
+                    app.onboarding.default_zone = Some(name);
+                }
+                app.onboarding.step = OnboardingStep::Keys;
+            }
+            OnboardingStep::Keys => finish_onboarding(app),
+        },
+        Action::OnboardingSkip => finish_onboarding(app),
+        Action::ConfirmTheme => {
+            app.confirm_theme();
+            if let Err(e) = Config::save_theme(&app.theme.name) {
+                tracing::error!("Failed to save theme: {}", e);
+            }
+        }
+        Action::OpenInService => {
+            if let Some(url) = app.selected_open_in_url() {
+                tracing::debug!("Opening {}", url);
+                if let Err(e) = external::open_url(&url) {
+                    tracing::error!("Failed to open {}: {}", url, e);
+                }
+            }
+            app.close_popup();
+        }
         Action::SelectUp => app.select_up(),
         Action::SelectDown => app.select_down(),
+        Action::ScrollUp(rows) => {
+            for _ in 0..rows {
+                app.select_up();
+            }
+        }
+        Action::ScrollDown(rows) => {
+            for _ in 0..rows {
+                app.select_down();
+            }
+        }
+        Action::MoveZoneUp | Action::MoveZoneDown => {
+            let up = action == Action::MoveZoneUp;
+            if let Some(order) = app.move_selector_zone(up) {
+                if let Err(e) = Config::save_zone_order(&order) {
+                    tracing::error!("Failed to save zone order: {}", e);
+                }
+            }
+        }
         Action::SelectZone => {
             if let Some(name) = app.get_selected_zone_name() {
                 if let Err(e) = roon::set_zone(&name) {
@@ -187,17 +1822,28 @@ fn handle_action(action: Action, app: &mut App) {
             app.select_zone();
             refresh_zones(app);
         }
+        Action::FollowSuccessorZone => {
+            if let Some(name) = app.follow_successor() {
+                if let Err(e) = roon::set_zone(&name) {
+                    tracing::error!("Failed to set zone: {}", e);
+                }
+                app.show_notice(format!("Following {}", name));
+                refresh_zones(app);
+            }
+        }
 
         // ========== Playback Controls ==========
         Action::NextTrack => {
             if let Err(e) = roon::next() {
                 tracing::error!("Failed to skip to next track: {}", e);
+                app.show_notice(format!("Couldn't skip: {}", e));
             }
             refresh_zones(app);
         }
         Action::PrevTrack => {
             if let Err(e) = roon::prev() {
                 tracing::error!("Failed to skip to previous track: {}", e);
+                app.show_notice(format!("Couldn't skip back: {}", e));
             }
             refresh_zones(app);
         }
@@ -206,9 +1852,9 @@ fn handle_action(action: Action, app: &mut App) {
                 .current_zone()
                 .map(|z| z.settings.shuffle)
                 .unwrap_or(false);
-            if let Err(e) = roon::shuffle(!current) {
-                tracing::error!("Failed to toggle shuffle: {}", e);
-            }
+            let result = roon::shuffle(!current);
+            let done = format!("Shuffle {}", on_off(!current));
+            report(app, result, done, "toggle shuffle");
             refresh_zones(app);
         }
         Action::CycleLoop => {
@@ -221,9 +1867,29 @@ fn handle_action(action: Action, app: &mut App) {
                 "loop" => "loop_one",
                 _ => "disabled",
             };
-            if let Err(e) = roon::set_loop(next_mode) {
-                tracing::error!("Failed to cycle loop mode: {}", e);
-            }
+            let result = roon::set_loop(next_mode);
+            let label = match next_mode {
+                "loop" => "all",
+                "loop_one" => "one",
+                _ => "off",
+            };
+            report(app, result, format!("Repeat {}", label), "cycle loop mode");
+            refresh_zones(app);
+        }
+        Action::CycleLeveling => {
+            let current = app
+                .current_zone()
+                .and_then(|z| z.settings.volume_leveling.as_deref())
+                .unwrap_or("off");
+            let next_mode = match current {
+                "off" => "auto",
+                "auto" => "album",
+                "album" => "track",
+                _ => "off",
+            };
+            let result = roon::set_leveling(next_mode);
+            let done = format!("Volume leveling {}", next_mode);
+            report(app, result, done, "set volume leveling");
             refresh_zones(app);
         }
         Action::ToggleRadio => {
@@ -231,30 +1897,25 @@ fn handle_action(action: Action, app: &mut App) {
                 .current_zone()
                 .map(|z| z.settings.auto_radio)
                 .unwrap_or(false);
-            if let Err(e) = roon::radio(!current) {
-                tracing::error!("Failed to toggle radio: {}", e);
-            }
+            let result = roon::radio(!current);
+            let done = format!("Roon Radio {}", on_off(!current));
+            report(app, result, done, "toggle radio");
             refresh_zones(app);
         }
-        Action::VolumeUp => {
-            if let Some(output) = app.first_output_name() {
-                if let Err(e) = roon::volume(&output, "+5") {
-                    tracing::error!("Failed to increase volume: {}", e);
-                }
-                refresh_zones(app);
-            }
-        }
-        Action::VolumeDown => {
-            if let Some(output) = app.first_output_name() {
-                if let Err(e) = roon::volume(&output, "-5") {
-                    tracing::error!("Failed to decrease volume: {}", e);
+        Action::SeekDrag(column) => app.drag_seek(column),
+        Action::SeekRelease => {
+            if let Some(position) = app.finish_seek_drag() {
+                if let Err(e) = roon::seek(position) {
+                    tracing::error!("Failed to seek: {}", e);
                 }
-                refresh_zones(app);
             }
         }
+        Action::VolumeUp => adjust_volume(app, VOLUME_STEP),
+        Action::VolumeDown => adjust_volume(app, -VOLUME_STEP),
         Action::ToggleMute => {
             if let Some(zone) = app.current_zone() {
-                if let Some(output) = zone.outputs.first() {
+                // Fixed-volume outputs can't be muted
+                if let Some(output) = zone.outputs.first().filter(|o| o.volume.is_some()) {
                     let is_muted = output.volume.as_ref().map(|v| v.is_muted).unwrap_or(false);
                     let name = output.display_name.clone();
                     let result = if is_muted {
@@ -262,14 +1923,52 @@ fn handle_action(action: Action, app: &mut App) {
                     } else {
                         roon::mute(&name)
                     };
-                    if let Err(e) = result {
-                        tracing::error!("Failed to toggle mute: {}", e);
-                    }
+                    let done = format!("{} {}", if is_muted { "Unmuted" } else { "Muted" }, name);
+                    report(app, result, done, "toggle mute");
                     refresh_zones(app);
                 }
             }
         }
 
+        Action::ShowTrim => {
+            if app
+                .current_zone()
+                .is_some_and(|zone| zone.outputs.len() > 1)
+            {
+                app.show_popup(Popup::Trim);
+            } else {
+                app.show_notice("Trims need a zone of grouped outputs");
+            }
+        }
+        Action::ShowOutputs => {
+            if app.current_zone().is_some() {
+                app.show_popup(Popup::Outputs);
+            } else {
+                app.show_notice("No zone selected");
+            }
+        }
+        Action::TrimUp => nudge_trim(app, TRIM_STEP),
+        Action::TrimDown => nudge_trim(app, -TRIM_STEP),
+        Action::ToggleOutputMute => {
+            // Only the highlighted output, even when volume is linked
+            let output = app
+                .current_zone()
+                .and_then(|zone| zone.outputs.get(app.volume_output_index));
+            // Fixed-volume outputs can't be muted
+            if let Some(output) = output.filter(|o| o.volume.is_some()) {
+                let is_muted = output.volume.as_ref().map(|v| v.is_muted).unwrap_or(false);
+                let name = output.display_name.clone();
+                let result = if is_muted {
+                    roon::unmute(&name)
+                } else {
+                    roon::mute(&name)
+                };
+                let done = format!("{} {}", if is_muted { "Unmuted" } else { "Muted" }, name);
+                report(app, result, done, "toggle mute");
+                refresh_zones(app);
+            }
+        }
+
         // ========== View Switching ==========
         Action::SwitchToNowPlaying => {
             app.view = View::NowPlaying;
@@ -278,9 +1977,10 @@ fn handle_action(action: Action, app: &mut App) {
             app.view = View::Browse;
             app.browse.reset();
             app.browse.loading = true;
-            match roon::browse() {
-                Ok(result) => {
-                    app.browse.items = result.items;
+            match app.browser.open(BrowseRoot::Library) {
+                Ok((path, result)) => {
+                    app.browse.path = Some(path);
+                    app.browse.set_items(result.items);
                     app.browse.selected_index = 0;
                     app.browse.breadcrumbs = vec!["Library".to_string()];
                     if let Some(title) = result.title {
@@ -296,23 +1996,154 @@ fn handle_action(action: Action, app: &mut App) {
                 }
             }
         }
+        Action::ShowItemDetail => show_item_detail(app),
+        Action::DedupeQueue => dedupe_queue(app),
+        Action::PlaySimilar => play_similar(app),
+        Action::SortDiscography => {
+            if !shows_discography(app) {
+                app.show_notice("Sorting is for an artist's albums");
+                return;
+            }
+            app.discography_sort = app.discography_sort.next();
+            sort_discography(app);
+            app.show_notice(format!("Albums: {}", app.discography_sort.label()));
+        }
+        Action::BrowseCurrentAlbum => {
+            let Some(np) = app.current_zone().and_then(|z| z.now_playing.as_ref()) else {
+                return;
+            };
+            if np.album.is_empty() {
+                app.show_notice("The playing track has no album");
+                return;
+            }
+            let (artist, album) = (np.artist.clone(), np.album.clone());
+            match app.browser.find_album(&artist, &album) {
+                Ok(Some((path, result, breadcrumbs))) => {
+                    app.browse.reset();
+                    app.browse.path = Some(path);
+                    app.browse.set_items(result.into_list());
+                    app.browse.breadcrumbs = breadcrumbs;
+                    app.view = View::Browse;
+                }
+                Ok(None) => app.show_notice(format!("\"{}\" not found in Browse", album)),
+                Err(e) => {
+                    tracing::error!("Failed to find album {}: {}", album, e);
+                    app.show_notice(format!("Couldn't open the album: {}", e));
+                }
+            }
+        }
+        Action::BrowseGenres => open_library_section(app, "Genres"),
+        Action::BrowseComposers => open_library_section(app, "Composers"),
+        Action::SurpriseMe => {
+            set_start_volume(app.start_volume_changes());
+            match app.browser.random_album("Play Now") {
+                Ok(Some((album, artist))) if artist.is_empty() => {
+                    app.show_notice(format!("Surprise: {}", album))
+                }
+                Ok(Some((album, artist))) => {
+                    app.show_notice(format!("Surprise: {} by {}", album, artist))
+                }
+                Ok(None) => app.show_notice("No albums in the library"),
+                Err(e) => {
+                    tracing::error!("Failed to play a random album: {}", e);
+                    app.show_notice(format!("Couldn't play a random album: {}", e));
+                }
+            }
+        }
         Action::SwitchToSearch => {
             app.view = View::Search;
             app.search.reset();
         }
+        Action::SwitchToQueue => {
+            app.view = View::Queue;
+            refresh_queue(app);
+        }
+        Action::SwitchToForYou => {
+            app.view = View::ForYou;
+            if app.for_you.is_stale() {
+                refresh_for_you(app, false);
+            }
+        }
+        Action::ForYouPlay => run_for_you_action(app, "Play Now"),
+        Action::ForYouQueue => run_for_you_action(app, "Queue"),
+        Action::SwitchToSettings => {
+            app.view = View::Settings;
+            app.settings.capturing = false;
+            app.settings.message = None;
+        }
+
+        // ========== Keybinding Editor ==========
+        Action::StartRebind => {
+            app.settings.capturing = true;
+            app.settings.message = None;
+        }
+        Action::CancelRebind => {
+            app.settings.capturing = false;
+        }
+        Action::RebindKey(key) => {
+            app.settings.capturing = false;
+            let index = app.settings.selected_index;
+            let result = app.keymap.rebind(index, key.into(), &app.plugins.actions);
+            app.settings.message = Some(match result {
+                Ok(()) => save_keymap(app),
+                Err(e) => e.to_string(),
+            });
+        }
+        Action::ResetBinding => {
+            app.keymap.reset(app.settings.selected_index);
+            app.settings.message = Some(save_keymap(app));
+        }
 
         // ========== Browse/Search Navigation ==========
         Action::BrowseSelect => {
+            if app.view == View::Search && app.search.merged.is_some() {
+                select_merged(app, app.search.results.selected_index);
+                return;
+            }
             let (index, is_search) = match app.view {
                 View::Browse => (app.browse.selected_index, false),
                 View::Search => (app.search.results.selected_index, true),
                 _ => return,
             };
+            let path = if is_search {
+                &app.search.results.path
+            } else {
+                &app.browse.path
+            };
+            let Some(path) = path.clone() else {
+                return;
+            };
+            let state = if is_search {
+                &app.search.results
+            } else {
+                &app.browse
+            };
+            if state.items.get(index).is_some_and(BrowseItem::is_show_more) {
+                show_more(app, &path, is_search);
+                return;
+            }
+            let level_index = state.level_index(index);
 
-            match roon::select(index) {
+            // Worked out while the zone is still idle, applied only if the
+            // selection turns out to be a play action
+            let start_volume = app.start_volume_changes();
+            match app.browser.select(&path, level_index) {
                 Ok(result) => {
                     if result.action.as_deref() == Some("message") {
+                        set_start_volume(start_volume);
                         // Play action executed - switch to Now Playing
+                        let state = if is_search {
+                            &app.search.results
+                        } else {
+                            &app.browse
+                        };
+                        if let Some(item) = state.items.get(index) {
+                            let notice = action_notice(
+                                &item.title,
+                                state.breadcrumbs.last().map(String::as_str),
+                            );
+                            app.show_notice(notice);
+                        }
                         app.view = View::NowPlaying;
                         refresh_zones(app);
                     } else {
@@ -332,9 +2163,12 @@ fn handle_action(action: Action, app: &mut App) {
                                 state.breadcrumbs[len - 1] = title.clone();
                             }
                         }
-                        state.items = result.items;
+                        state.set_items(result.into_list());
                         state.selected_index = 0;
                         state.error = None;
+                        let mut path = path;
+                        path.indexes.push(level_index);
+                        state.path = Some(path);
                     }
                 }
                 Err(e) => {
@@ -347,6 +2181,7 @@ fn handle_action(action: Action, app: &mut App) {
                     state.error = Some(e.to_string());
                 }
             }
+            sort_discography(app);
         }
         Action::BrowseBack => {
             let (state, view) = match app.view {
@@ -357,6 +2192,9 @@ fn handle_action(action: Action, app: &mut App) {
                         app.view = View::NowPlaying;
                         return;
                     }
+                    if app.search.back_to_merged() {
+                        return;
+                    }
                     (&mut app.search.results, View::Search)
                 }
                 _ => return,
@@ -365,11 +2203,12 @@ fn handle_action(action: Action, app: &mut App) {
             if state.breadcrumbs.len() <= 1 {
                 // At root - switch back to Now Playing
                 app.view = View::NowPlaying;
-            } else {
-                match roon::back() {
+            } else if let Some(path) = &mut state.path {
+                match app.browser.back(path) {
                     Ok(result) => {
+                        path.indexes.pop();
                         state.breadcrumbs.pop();
-                        state.items = result.items;
+                        state.set_items(result.into_list());
                         state.selected_index = 0;
                         state.error = None;
                     }
@@ -384,6 +2223,57 @@ fn handle_action(action: Action, app: &mut App) {
             if app.view != View::NowPlaying {
                 app.view = view;
             }
+            sort_discography(app);
+        }
+        Action::BrowseRefresh => {
+            let state = match app.view {
+                View::ForYou => return refresh_for_you(app, true),
+                View::Browse => &mut app.browse,
+                // Merged levels are rebuilt from the levels they came from
+                View::Search if app.search.merged.is_some() => return,
+                View::Search => &mut app.search.results,
+                _ => return,
+            };
+            let Some(path) = &state.path else {
+                return;
+            };
+            match app.browser.refresh(path) {
+                Ok(result) => {
+                    state.set_items(result.into_list());
+                    state.selected_index = state
+                        .selected_index
+                        .min(state.items.len().saturating_sub(1));
+                    state.error = None;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to reload list: {}", e);
+                    state.error = Some(e.to_string());
+                }
+            }
+            sort_discography(app);
+        }
+        Action::ErrorRetry => match app.view {
+            View::Browse if app.browse.path.is_none() => handle_action(Action::SwitchToBrowse, app),
+            // Merged levels can't be reloaded; their items are still there
+            View::Search if app.search.merged.is_some() => app.search.results.error = None,
+            View::Search if app.search.results.path.is_none() => {
+                handle_action(Action::SearchSubmit, app)
+            }
+            _ => handle_action(Action::BrowseRefresh, app),
+        },
+        Action::ErrorRoot => match app.view {
+            View::Search => app.search.reset(),
+            _ => handle_action(Action::SwitchToBrowse, app),
+        },
+        Action::OpenLog => {
+            let log = paths::log_file();
+            match external::open_url(&log.to_string_lossy()) {
+                Ok(()) => app.show_notice(format!("Opened {}", log.display())),
+                Err(e) => {
+                    tracing::error!("Failed to open the log: {}", e);
+                    app.show_notice(format!("Couldn't open {}: {}", log.display(), e));
+                }
+            }
         }
         Action::SearchChar(c) => {
             app.search.query.push(c);
@@ -394,17 +2284,11 @@ fn handle_action(action: Action, app: &mut App) {
         Action::SearchSubmit => {
             if !app.search.query.is_empty() {
                 let query = app.search.query.clone();
-                match roon::search(&query) {
-                    Ok(result) => {
-                        app.search.results.items = result.items;
-                        app.search.results.selected_index = 0;
-                        app.search.results.breadcrumbs = vec!["Search".to_string()];
-                        if let Some(title) = result.title {
-                            app.search.results.breadcrumbs = vec![title];
-                        }
-                        app.search.results.error = None;
-                        app.search.input_active = false;
-                    }
+                // A new search starts over, dropping levels of the last one
+                app.search.reset();
+                app.search.query = query.clone();
+                match open_search(app, &query) {
+                    Ok(()) => app.search.input_active = false,
                     Err(e) => {
                         app.search.results.error = Some(e.to_string());
                         app.search.input_active = false;
@@ -0,0 +1,67 @@
+// Media key bridge for roon-tui on macOS
+//
+// Registers as the system's Now Playing app so the keyboard's media keys
+// (and Control Center) reach roon-tui even when the terminal isn't focused.
+// Key presses are printed one per line ("playpause", "next", ...); the track
+// to show comes in on stdin as one JSON object per line (anything else
+// clears it). Exits when stdin closes, i.e. when roon-tui does.
+
+import AppKit
+import Foundation
+import MediaPlayer
+
+setvbuf(stdout, nil, _IOLBF, 0)
+
+let commands = MPRemoteCommandCenter.shared()
+
+func forward(_ command: MPRemoteCommand, as name: String) {
+    command.isEnabled = true
+    command.addTarget { _ in
+        print(name)
+        return .success
+    }
+}
+
+forward(commands.togglePlayPauseCommand, as: "playpause")
+forward(commands.playCommand, as: "play")
+forward(commands.pauseCommand, as: "pause")
+forward(commands.nextTrackCommand, as: "next")
+forward(commands.previousTrackCommand, as: "previous")
+
+struct Track: Decodable {
+    let title: String
+    let artist: String
+    let album: String
+    let duration: Double
+    let elapsed: Double
+    let playing: Bool
+}
+
+func show(_ track: Track?) {
+    let center = MPNowPlayingInfoCenter.default()
+    guard let track = track else {
+        center.nowPlayingInfo = nil
+        center.playbackState = .stopped
+        return
+    }
+    center.nowPlayingInfo = [
+        MPMediaItemPropertyTitle: track.title,
+        MPMediaItemPropertyArtist: track.artist,
+        MPMediaItemPropertyAlbumTitle: track.album,
+        MPMediaItemPropertyPlaybackDuration: track.duration,
+        MPNowPlayingInfoPropertyElapsedPlaybackTime: track.elapsed,
+        MPNowPlayingInfoPropertyPlaybackRate: track.playing ? 1.0 : 0.0,
+    ]
+    center.playbackState = track.playing ? .playing : .paused
+}
+
+DispatchQueue.global().async {
+    while let line = readLine() {
+        let track = try? JSONDecoder().decode(Track.self, from: Data(line.utf8))
+        DispatchQueue.main.async { show(track) }
+    }
+    exit(0)
+}
+
+NSApplication.shared.setActivationPolicy(.prohibited)
+NSApplication.shared.run()
@@ -0,0 +1,151 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{ChildStdin, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::roon::NowPlaying;
+
+/// Source of the helper, compiled with `swiftc` on first use
+const HELPER_SOURCE: &str = include_str!("helper.swift");
+
+/// A media key press (or Control Center button)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKey {
+    PlayPause,
+    Play,
+    Pause,
+    Next,
+    Previous,
+}
+
+impl MediaKey {
+    fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "playpause" => Some(MediaKey::PlayPause),
+            "play" => Some(MediaKey::Play),
+            "pause" => Some(MediaKey::Pause),
+            "next" => Some(MediaKey::Next),
+            "previous" => Some(MediaKey::Previous),
+            _ => None,
+        }
+    }
+}
+
+/// Track shown in the system's Now Playing widget, one JSON line to the helper
+#[derive(Serialize)]
+struct Track<'a> {
+    title: &'a str,
+    artist: &'a str,
+    album: &'a str,
+    duration: f64,
+    elapsed: f64,
+    playing: bool,
+}
+
+/// The system media keys, through a small Swift helper registered as the
+/// Now Playing app (macOS only)
+///
+/// The helper is built in the background on first start and cached; until
+/// it runs, updates are dropped. It exits when its stdin closes, i.e. when
+/// this is dropped.
+#[derive(Default)]
+pub struct MediaKeys {
+    stdin: Arc<Mutex<Option<ChildStdin>>>,
+    /// What the helper was last told, without the position (it advances
+    /// the position itself while playing)
+    shown: Option<(String, String, String, bool)>,
+}
+
+impl MediaKeys {
+    pub fn spawn(tx: mpsc::Sender<MediaKey>) -> Result<Self> {
+        if !cfg!(target_os = "macos") {
+            bail!("media keys are only supported on macOS");
+        }
+        let helper = crate::swift::helper_path("media-keys").context("no cache directory")?;
+        let stdin = Arc::new(Mutex::new(None));
+        let shared = Arc::clone(&stdin);
+        std::thread::spawn(move || {
+            if let Err(e) = run_helper(&helper, &shared, &tx) {
+                tracing::warn!("Media keys unavailable: {:#}", e);
+            }
+        });
+        Ok(Self { stdin, shown: None })
+    }
+
+    /// Show the selected zone's track (or nothing) in the Now Playing widget
+    pub fn update(&mut self, np: Option<&NowPlaying>, playing: bool) {
+        let shown = np.map(|np| {
+            (
+                np.track.clone(),
+                np.artist.clone(),
+                np.album.clone(),
+                playing,
+            )
+        });
+        if shown == self.shown {
+            return;
+        }
+        let line = match np {
+            Some(np) => serde_json::to_string(&Track {
+                title: &np.track,
+                artist: &np.artist,
+                album: &np.album,
+                duration: np.length,
+                elapsed: np.seek_position,
+                playing,
+            })
+            .unwrap_or_default(),
+            None => "null".to_string(),
+        };
+        let Ok(mut stdin) = self.stdin.lock() else {
+            return;
+        };
+        let Some(pipe) = stdin.as_mut() else {
+            return;
+        };
+        if writeln!(pipe, "{}", line)
+            .and_then(|_| pipe.flush())
+            .is_err()
+        {
+            tracing::warn!("Media key helper exited");
+            *stdin = None;
+            return;
+        }
+        self.shown = shown;
+    }
+}
+
+/// Build the helper if needed, start it and forward its key presses
+fn run_helper(
+    helper: &Path,
+    stdin: &Mutex<Option<ChildStdin>>,
+    tx: &mpsc::Sender<MediaKey>,
+) -> Result<()> {
+    crate::swift::build_helper(helper, HELPER_SOURCE)?;
+    let mut child = Command::new(helper)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start the media key helper")?;
+    let stdout = child.stdout.take().context("No stdout")?;
+    if let Ok(mut slot) = stdin.lock() {
+        *slot = child.stdin.take();
+    }
+    tracing::info!("Media keys enabled");
+
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        if let Some(key) = MediaKey::parse(&line) {
+            if tx.blocking_send(key).is_err() {
+                break;
+            }
+        }
+    }
+    let _ = child.wait();
+    Ok(())
+}
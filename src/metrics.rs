@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds of the latency histogram buckets, in milliseconds (roughly
+/// logarithmic; anything slower lands in a final overflow bucket)
+const BUCKETS_MS: [u64; 22] = [
+    1, 2, 3, 5, 7, 10, 15, 20, 30, 50, 75, 100, 150, 200, 300, 500, 750, 1000, 1500, 2000, 5000,
+    10000,
+];
+
+/// Latency of one roon CLI command (keyed by its first argument), or of
+/// rendering frames
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Latency {
+    pub last: Duration,
+    pub max: Duration,
+    pub count: u32,
+    /// Sample counts per `BUCKETS_MS` bucket, plus overflow
+    buckets: [u32; BUCKETS_MS.len() + 1],
+}
+
+impl Latency {
+    pub fn record(&mut self, elapsed: Duration) {
+        self.last = elapsed;
+        self.max = self.max.max(elapsed);
+        self.count += 1;
+        let ms = elapsed.as_millis() as u64;
+        let bucket = BUCKETS_MS
+            .iter()
+            .position(|&bound| ms < bound)
+            .unwrap_or(BUCKETS_MS.len());
+        self.buckets[bucket] += 1;
+    }
+
+    /// Upper bound of the bucket holding the `percent` percentile (capped at
+    /// the slowest sample seen)
+    pub fn percentile(&self, percent: u32) -> Duration {
+        let target = (u64::from(self.count) * u64::from(percent)).div_ceil(100);
+        let mut seen = 0;
+        for (i, &count) in self.buckets.iter().enumerate() {
+            seen += u64::from(count);
+            if seen >= target && count > 0 {
+                return BUCKETS_MS
+                    .get(i)
+                    .map(|&ms| Duration::from_millis(ms).min(self.max))
+                    .unwrap_or(self.max);
+            }
+        }
+        self.max
+    }
+}
+
+/// Backend commands run from several threads (UI loop and blocking tasks),
+/// so their timings are collected globally rather than on `App`
+static COMMANDS: Mutex<BTreeMap<String, Latency>> = Mutex::new(BTreeMap::new());
+
+/// Record how long a roon CLI command took
+pub fn record_command(name: &str, elapsed: Duration) {
+    let Ok(mut commands) = COMMANDS.lock() else {
+        return;
+    };
+    commands
+        .entry(name.to_string())
+        .or_default()
+        .record(elapsed);
+}
+
+/// Snapshot of all command latencies, sorted by command name
+pub fn command_latencies() -> Vec<(String, Latency)> {
+    COMMANDS
+        .lock()
+        .map(|commands| commands.iter().map(|(k, v)| (k.clone(), *v)).collect())
+        .unwrap_or_default()
+}
+
+/// Frame and event loop timings for the debug overlay
+#[derive(Debug)]
+pub struct Metrics {
+    /// Render time of the last frame
+    pub frame_time: Duration,
+    /// Slowest frame since startup
+    pub frame_max: Duration,
+    /// Every frame's render time, for the session report
+    pub frames: Latency,
+    started: Instant,
+    /// Event loop iterations per second (over the last full second)
+    pub loop_rate: f64,
+    window_start: Instant,
+    window_iterations: u32,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            frame_time: Duration::ZERO,
+            frame_max: Duration::ZERO,
+            frames: Latency::default(),
+            started: Instant::now(),
+            loop_rate: 0.0,
+            window_start: Instant::now(),
+            window_iterations: 0,
+        }
+    }
+}
+
+impl Metrics {
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        self.frame_time = elapsed;
+        self.frame_max = self.frame_max.max(elapsed);
+        self.frames.record(elapsed);
+    }
+
+    /// Count one pass of the event loop, updating the rate once a second
+    pub fn record_iteration(&mut self) {
+        self.window_iterations += 1;
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            self.loop_rate = self.window_iterations as f64 / elapsed.as_secs_f64();
+            self.window_start = Instant::now();
+            self.window_iterations = 0;
+        }
+    }
+}
+
+/// Where the last session's report is kept (`roon-tui perf-report`)
+fn report_path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|dir| dir.join("perf-report.txt"))
+}
+
+/// Summary of render and per-command latencies for the session so far
+///
+/// Slow commands with fast renders point at the CLI or Core, slow renders
+/// at the terminal.
+pub fn report(metrics: &Metrics) -> String {
+    let ms = |d: Duration| format!("{:.1}ms", d.as_secs_f64() * 1000.0);
+    let minutes = metrics.started.elapsed().as_secs() / 60;
+    let mut out = format!(
+        "roon-tui {} — session of {}h {:02}m\n\n{:<16}{:>8}{:>10}{:>10}{:>10}\n",
+        env!("CARGO_PKG_VERSION"),
+        minutes / 60,
+        minutes % 60,
+        "",
+        "count",
+        "p50",
+        "p95",
+        "max"
+    );
+    let rows = std::iter::once(("render".to_string(), metrics.frames)).chain(
+        command_latencies()
+            .into_iter()
+            .map(|(name, l)| (format!("roon {name}"), l)),
+    );
+    for (name, latency) in rows {
+        let _ = writeln!(
+            out,
+            "{:<16}{:>8}{:>10}{:>10}{:>10}",
+            name,
+            latency.count,
+            ms(latency.percentile(50)),
+            ms(latency.percentile(95)),
+            ms(latency.max)
+        );
+    }
+    out
+}
+
+/// Keep the session's report for `roon-tui perf-report`
+pub fn save_report(metrics: &Metrics) {
+    let Some(path) = report_path() else {
+        return;
+    };
+    if let Err(e) = crate::paths::write_file(&path, report(metrics).as_bytes()) {
+        tracing::warn!("Failed to save perf report: {}", e);
+    }
+}
+
+/// The last session's report, if there was one
+pub fn last_report() -> Option<String> {
+    std::fs::read_to_string(report_path()?).ok()
+}
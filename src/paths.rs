@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// Where roon-tui keeps its files: the XDG base directories on Linux, and
+/// the platform's equivalents on macOS and Windows
+const APP_DIR: &str = "roon-tui";
+
+/// Config file and themes (`$XDG_CONFIG_HOME/roon-tui`)
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
+
+/// Files that can be re-created at any time: lyrics, update check, generated
+/// cava config (`$XDG_CACHE_HOME/roon-tui`)
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(APP_DIR))
+}
+
+/// State worth keeping across runs but not worth backing up: resume
+/// positions and the log (`$XDG_STATE_HOME/roon-tui`)
+///
+/// Only Linux has a state directory; elsewhere the local data dir is used.
+pub fn state_dir() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join(APP_DIR))
+}
+
+/// Log file, in the temp dir if there's no state directory
+pub fn log_file() -> PathBuf {
+    state_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("roon-tui.log")
+}
+
+/// Save state as JSON, see `write_file`
+pub fn write_state<T: Serialize + ?Sized>(path: &Path, state: &T) -> Result<()> {
+    write_file(path, serde_json::to_string(state)?.as_bytes())
+}
+
+/// Write a file whole: to a temp file next to it that's then renamed over
+/// it, so a crash mid-write leaves the old contents rather than half of the
+/// new ones. Creates the directory if needed.
+pub fn write_file(path: &Path, contents: &[u8]) -> Result<()> {
+    let dir = path.parent().context("no parent directory")?;
+    let name = path.file_name().context("no file name")?;
+    std::fs::create_dir_all(dir)?;
+    let temp = dir.join(format!(".{}.tmp", name.to_string_lossy()));
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+/// Print every path roon-tui uses (`--print-paths`)
+pub fn print() {
+    let show = |path: Option<PathBuf>| {
+        path.map(|p| p.display().to_string())
+            .unwrap_or_else(|| "(not available on this platform)".to_string())
+    };
+    println!("config  {}", show(crate::config::Config::path()));
+    if crate::config::Config::profile().is_some() {
+        println!("profile {}", show(crate::config::Config::profile_path()));
+    }
+    println!("themes  {}", show(crate::theme::Theme::dir()));
+    println!("plugins {}", show(crate::plugins::Plugins::dir()));
+    println!("cache   {}", show(cache_dir()));
+    println!("state   {}", show(state_dir()));
+    println!("log     {}", log_file().display());
+}
@@ -0,0 +1,407 @@
+mod script;
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+
+use anyhow::{Context, Result};
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::keymap::Key;
+use script::Script;
+
+/// Events waiting for an executable plugin before it counts as stuck (it
+/// stopped reading its stdin) and is stopped
+const QUEUED_EVENTS: usize = 64;
+
+/// Something that happened in the TUI, sent to every plugin as one JSON line
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// Sent once, right after the plugin starts
+    Started { version: &'a str },
+    /// A zone started a different track
+    TrackChanged {
+        zone: &'a str,
+        artist: &'a str,
+        track: &'a str,
+        album: &'a str,
+    },
+    /// A zone's playback state changed ("playing", "paused", ...)
+    StateChanged { zone: &'a str, state: &'a str },
+    /// The user switched to another zone
+    ZoneSelected { zone: &'a str },
+    /// The selected zone went away; `successor` is the zone that took over
+    /// its outputs, if any
+    ZoneGone {
+        zone: &'a str,
+        successor: Option<&'a str>,
+    },
+    /// The user ran one of this plugin's actions
+    Action { name: &'a str },
+}
+
+/// A request from a plugin: one JSON object per line on an executable's
+/// stdout, or a function call in a script
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Request {
+    /// Add an action, optionally bound to a key
+    RegisterAction {
+        name: String,
+        description: String,
+        #[serde(default)]
+        key: Option<String>,
+    },
+    /// Set this plugin's status bar text (empty hides it)
+    Status { text: String },
+    /// Set this plugin's status widget: styled pieces of text (none hides it)
+    Widget { segments: Vec<Segment> },
+}
+
+/// A styled piece of a plugin's status widget
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Segment {
+    pub text: String,
+    /// Theme color to show it in ("accent", "muted", "heading", "error",
+    /// "success"; the status bar text color otherwise)
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub bold: bool,
+}
+
+impl Segment {
+    fn plain(text: String) -> Self {
+        Self {
+            text,
+            color: None,
+            bold: false,
+        }
+    }
+}
+
+/// A request together with the plugin that sent it
+#[derive(Debug)]
+pub struct PluginMsg {
+    pub plugin: usize,
+    pub request: Request,
+}
+
+/// An action registered by a plugin
+#[derive(Debug)]
+pub struct PluginAction {
+    plugin: usize,
+    pub name: String,
+    pub description: String,
+    pub key: Option<Key>,
+}
+
+/// A loaded plugin
+struct Plugin {
+    name: String,
+    kind: Kind,
+}
+
+enum Kind {
+    Process(Process),
+    /// Boxed: an engine is much larger than a process handle
+    Script(Box<Script>),
+}
+
+/// A running plugin executable; killed on drop
+struct Process {
+    child: Child,
+    /// Event lines for the thread writing its stdin; `None` once the plugin
+    /// exited or was stopped
+    events: Option<SyncSender<String>>,
+}
+
+impl Process {
+    /// Queue an event line without waiting on the plugin
+    fn send(&mut self, name: &str, line: &str) {
+        let Some(events) = &self.events else {
+            return;
+        };
+        match events.try_send(line.to_string()) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                tracing::warn!("Plugin {} stopped reading events, stopping it", name);
+                self.events = None;
+                let _ = self.child.kill();
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                tracing::warn!("Plugin {} exited", name);
+                self.events = None;
+            }
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Scripts and executables in the plugins directory, extending the TUI
+/// without recompiling it
+///
+/// `.rhai` scripts run inside the TUI (see `Script`). Executables can be
+/// written in any language: they read events as JSON lines on stdin and
+/// write requests as JSON lines to stdout.
+#[derive(Default)]
+pub struct Plugins {
+    plugins: Vec<Plugin>,
+    pub actions: Vec<PluginAction>,
+    /// Status widget per plugin
+    widgets: BTreeMap<usize, Vec<Segment>>,
+    /// Zone the last `ZoneSelected` event was about
+    last_zone: Option<String>,
+    /// Whether a script changed what's shown since the last `take_redraw`
+    redraw: bool,
+}
+
+impl Plugins {
+    /// Directory plugins are loaded from (`<config dir>/roon-tui/plugins`)
+    pub fn dir() -> Option<PathBuf> {
+        crate::paths::config_dir().map(|dir| dir.join("plugins"))
+    }
+
+    /// Load every script and start every executable in the plugins directory
+    pub fn load(tx: mpsc::Sender<PluginMsg>) -> Self {
+        let mut plugins = Self::default();
+        let entries = Self::dir().and_then(|dir| std::fs::read_dir(dir).ok());
+        let mut files: Vec<PathBuf> = entries
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| is_script(path) || is_executable(path))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let index = plugins.plugins.len();
+            let kind = if is_script(&path) {
+                Script::load(&path, &name).map(|script| Kind::Script(Box::new(script)))
+            } else {
+                spawn(&path, &name, index, tx.clone()).map(Kind::Process)
+            };
+            match kind {
+                Ok(kind) => {
+                    tracing::info!("Started plugin {}", name);
+                    plugins.plugins.push(Plugin { name, kind });
+                }
+                Err(e) => tracing::warn!("Failed to start plugin {}: {:#}", path.display(), e),
+            }
+        }
+        plugins.send_to(
+            None,
+            &Event::Started {
+                version: env!("CARGO_PKG_VERSION"),
+            },
+        );
+        plugins
+    }
+
+    /// Apply a plugin's request; returns whether the UI needs a redraw
+    pub fn handle(&mut self, msg: PluginMsg) -> bool {
+        let name = self
+            .plugins
+            .get(msg.plugin)
+            .map_or("?", |p| p.name.as_str());
+        match msg.request {
+            Request::RegisterAction {
+                name: action,
+                description,
+                key,
+            } => {
+                let parsed = key.as_deref().and_then(Key::parse);
+                if key.is_some() && parsed.is_none() {
+                    tracing::warn!("Plugin {}: invalid key {:?} for {}", name, key, action);
+                }
+                self.actions
+                    .retain(|a| !(a.plugin == msg.plugin && a.name == action));
+                self.actions.push(PluginAction {
+                    plugin: msg.plugin,
+                    name: action,
+                    description,
+                    key: parsed,
+                });
+                false
+            }
+            Request::Status { text } if text.is_empty() => self.set_widget(msg.plugin, Vec::new()),
+            Request::Status { text } => self.set_widget(msg.plugin, vec![Segment::plain(text)]),
+            Request::Widget { segments } => self.set_widget(msg.plugin, segments),
+        }
+    }
+
+    /// Replace a plugin's status widget; returns whether it changed
+    fn set_widget(&mut self, plugin: usize, segments: Vec<Segment>) -> bool {
+        let previous = if segments.is_empty() {
+            self.widgets.remove(&plugin)
+        } else {
+            self.widgets.insert(plugin, segments.clone())
+        };
+        previous.unwrap_or_default() != segments
+    }
+
+    /// Whether a script changed what's shown since the last call
+    pub fn take_redraw(&mut self) -> bool {
+        std::mem::take(&mut self.redraw)
+    }
+
+    /// The plugin action bound to a key, if any
+    pub fn action_for(&self, key: KeyEvent) -> Option<usize> {
+        let key = Key::from(key);
+        self.actions
+            .iter()
+            .position(|a| a.key.as_ref() == Some(&key))
+    }
+
+    /// Tell the plugin that registered an action to run it
+    pub fn run_action(&mut self, index: usize) {
+        let Some(action) = self.actions.get(index) else {
+            return;
+        };
+        let (plugin, name) = (action.plugin, action.name.clone());
+        self.send_to(Some(plugin), &Event::Action { name: &name });
+    }
+
+    /// Status widgets, in plugin order
+    pub fn widgets(&self) -> impl Iterator<Item = &[Segment]> {
+        self.widgets.values().map(Vec::as_slice)
+    }
+
+    /// Send an event to every plugin
+    pub fn send(&mut self, event: &Event) {
+        self.send_to(None, event);
+    }
+
+    /// Send `ZoneSelected` if the selected zone changed since last time
+    pub fn zone_selected(&mut self, zone: &str) {
+        if self.last_zone.as_deref() != Some(zone) {
+            self.last_zone = Some(zone.to_string());
+            self.send(&Event::ZoneSelected { zone });
+        }
+    }
+
+    /// Send `ZoneGone`, and `ZoneSelected` again for whichever zone the user
+    /// picks next, even one with the same name
+    pub fn zone_gone(&mut self, zone: &str, successor: Option<&str>) {
+        self.last_zone = None;
+        self.send(&Event::ZoneGone { zone, successor });
+    }
+
+    /// Send an event to one plugin, or all of them
+    fn send_to(&mut self, only: Option<usize>, event: &Event) {
+        if self.plugins.is_empty() {
+            return;
+        }
+        let Ok(line) = serde_json::to_string(event) else {
+            return;
+        };
+        let mut requests = Vec::new();
+        for (index, plugin) in self.plugins.iter_mut().enumerate() {
+            if only.is_some_and(|only| only != index) {
+                continue;
+            }
+            match &mut plugin.kind {
+                Kind::Process(process) => process.send(&plugin.name, &line),
+                Kind::Script(script) => match script.on(event) {
+                    Ok(made) => requests.extend(made.into_iter().map(|request| PluginMsg {
+                        plugin: index,
+                        request,
+                    })),
+                    Err(e) => tracing::warn!("Plugin {}: {:#}", plugin.name, e),
+                },
+            }
+        }
+        for msg in requests {
+            if self.handle(msg) {
+                self.redraw = true;
+            }
+        }
+    }
+}
+
+fn spawn(path: &Path, name: &str, index: usize, tx: mpsc::Sender<PluginMsg>) -> Result<Process> {
+    let mut child = Command::new(path)
+        .current_dir(path.parent().unwrap_or(Path::new(".")))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut stdin = child.stdin.take().context("No stdin")?;
+    let stdout = child.stdout.take().context("No stdout")?;
+
+    // Writes happen off the UI thread, so a plugin that stops reading can't
+    // block it
+    let (events, queue) = sync_channel::<String>(QUEUED_EVENTS);
+    std::thread::spawn(move || {
+        for line in queue {
+            if writeln!(stdin, "{}", line)
+                .and_then(|_| stdin.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    let plugin_name = name.to_string();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(request) => {
+                    if tx
+                        .blocking_send(PluginMsg {
+                            plugin: index,
+                            request,
+                        })
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Plugin {}: invalid request {:?}: {}", plugin_name, line, e)
+                }
+            }
+        }
+    });
+
+    Ok(Process {
+        child,
+        events: Some(events),
+    })
+}
+
+fn is_script(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "rhai")
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -0,0 +1,146 @@
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+
+use anyhow::{anyhow, Result};
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+
+use super::{Event, Request, Segment};
+
+/// Operations one call into a script may take before it's stopped, so an
+/// endless loop can't freeze the UI
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A Rhai script from the plugins directory, run inside the TUI
+///
+/// The script's top level runs once when it's loaded. For each event it
+/// calls `on_<event>` (`on_track_changed`, `on_action`, ...) if the script
+/// defines it, with the event's fields as a map; `this` in those functions
+/// is a map that's kept between calls. Scripts make requests by calling
+/// `register_action(name, description[, key])`, `status(text)` and
+/// `widget([..])`.
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    /// `this` in the script's functions
+    state: Dynamic,
+    /// Requests made since they were last taken
+    requests: Rc<RefCell<Vec<Request>>>,
+}
+
+impl Script {
+    pub fn load(path: &Path, name: &str) -> Result<Self> {
+        let requests = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let plugin = name.to_string();
+        engine.on_print(move |text| tracing::info!("Plugin {}: {}", plugin, text));
+        let plugin = name.to_string();
+        engine.on_debug(move |text, _, position| {
+            tracing::debug!("Plugin {} ({}): {}", plugin, position, text)
+        });
+        register_requests(&mut engine, &requests);
+
+        let ast = engine
+            .compile_file(path.to_path_buf())
+            .map_err(|e| anyhow!("{}", e))?;
+        engine.run_ast(&ast).map_err(|e| anyhow!("{}", e))?;
+        Ok(Self {
+            engine,
+            ast,
+            state: Dynamic::from_map(Map::new()),
+            requests,
+        })
+    }
+
+    /// Run the script's function for an event; returns the requests it
+    /// made (along with any made while loading)
+    pub fn on(&mut self, event: &Event) -> Result<Vec<Request>> {
+        let result = self.call(event);
+        let requests = std::mem::take(&mut *self.requests.borrow_mut());
+        result.map(|_| requests)
+    }
+
+    fn call(&mut self, event: &Event) -> Result<()> {
+        let mut fields: Map = rhai::serde::to_dynamic(event)
+            .map_err(|e| anyhow!("{}", e))?
+            .try_cast()
+            .unwrap_or_default();
+        let kind = fields
+            .remove("event")
+            .and_then(|kind| kind.into_string().ok())
+            .unwrap_or_default();
+        let function = format!("on_{}", kind);
+        let defined = self
+            .ast
+            .iter_functions()
+            .any(|f| f.name == function && f.params.len() == 1);
+        if !defined {
+            return Ok(());
+        }
+
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        self.engine
+            .call_fn_with_options::<Dynamic>(
+                options,
+                &mut Scope::new(),
+                &self.ast,
+                &function,
+                (Dynamic::from_map(fields),),
+            )
+            .map(drop)
+            .map_err(|e| anyhow!("{}: {}", function, e))
+    }
+}
+
+/// The functions scripts make requests with
+fn register_requests(engine: &mut Engine, requests: &Rc<RefCell<Vec<Request>>>) {
+    let sink = requests.clone();
+    engine.register_fn("register_action", move |name: &str, description: &str| {
+        sink.borrow_mut().push(Request::RegisterAction {
+            name: name.to_string(),
+            description: description.to_string(),
+            key: None,
+        });
+    });
+    let sink = requests.clone();
+    engine.register_fn(
+        "register_action",
+        move |name: &str, description: &str, key: &str| {
+            sink.borrow_mut().push(Request::RegisterAction {
+                name: name.to_string(),
+                description: description.to_string(),
+                key: Some(key.to_string()),
+            });
+        },
+    );
+    let sink = requests.clone();
+    engine.register_fn("status", move |text: &str| {
+        sink.borrow_mut().push(Request::Status {
+            text: text.to_string(),
+        });
+    });
+    let sink = requests.clone();
+    engine.register_fn(
+        "widget",
+        move |items: Array| -> Result<(), Box<EvalAltResult>> {
+            let segments = items
+                .into_iter()
+                .map(segment)
+                .collect::<Result<Vec<_>, _>>()?;
+            sink.borrow_mut().push(Request::Widget { segments });
+            Ok(())
+        },
+    );
+}
+
+/// A widget piece from a script: a string, or a map with `text` and
+/// optionally `color` and `bold`
+fn segment(item: Dynamic) -> Result<Segment, Box<EvalAltResult>> {
+    if item.is_string() {
+        return Ok(Segment::plain(item.into_string()?));
+    }
+    rhai::serde::from_dynamic(&item)
+}
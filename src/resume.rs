@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::roon::NowPlaying;
+
+/// How often changed positions are written out while an item plays
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Last playback position of long items (podcasts, audiobooks), persisted
+/// as one JSON file so they can pick up where they were left off
+pub struct ResumeStore {
+    path: Option<PathBuf>,
+    /// Item key -> position in seconds
+    positions: BTreeMap<String, f64>,
+    /// Whether `positions` has changes not yet on disk
+    changed: bool,
+    saved_at: Instant,
+}
+
+impl ResumeStore {
+    /// Load saved positions (an empty store if there are none yet)
+    pub fn load() -> Self {
+        let path = crate::paths::state_dir().map(|dir| dir.join("resume.json"));
+        let positions = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            positions,
+            changed: false,
+            saved_at: Instant::now(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<f64> {
+        self.positions.get(key).copied()
+    }
+
+    /// Remember a position, saving now and then while an item plays
+    pub fn set(&mut self, key: &str, position: f64) {
+        self.positions.insert(key.to_string(), position);
+        self.changed = true;
+        if self.saved_at.elapsed() >= SAVE_INTERVAL {
+            self.save();
+        }
+    }
+
+    /// Forget an item (it was played to the end)
+    pub fn remove(&mut self, key: &str) {
+        if self.positions.remove(key).is_some() {
+            self.changed = true;
+            self.save();
+        }
+    }
+
+    /// Write changed positions to disk
+    pub fn save(&mut self) {
+        self.saved_at = Instant::now();
+        let Some(path) = &self.path else {
+            return;
+        };
+        if !self.changed {
+            return;
+        }
+        let result = crate::paths::write_state(path, &self.positions);
+        match result {
+            Ok(()) => self.changed = false,
+            Err(e) => tracing::warn!("Failed to save resume positions: {}", e),
+        }
+    }
+}
+
+/// Key identifying an item across plays
+pub fn item_key(np: &NowPlaying) -> String {
+    format!("{} — {} — {}", np.artist, np.album, np.track)
+}
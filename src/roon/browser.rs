@@ -0,0 +1,585 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+
+use super::{BrowseItem, BrowseResult};
+
+/// How long a cached browse level is reused before it's fetched again
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Where a browse session starts
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BrowseRoot {
+    Library,
+    Search(String),
+    /// The library's albums narrowed by Focus filters
+    Focus(FocusQuery),
+}
+
+/// Roon Focus filters on the library's albums; unset filters don't narrow
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FocusQuery {
+    pub year_from: Option<u16>,
+    pub year_to: Option<u16>,
+    /// File format, e.g. "FLAC" or "DSD"
+    pub format: Option<String>,
+    pub label: Option<String>,
+    /// Albums have to be in every one of these genres
+    pub genres: Vec<String>,
+}
+
+impl FocusQuery {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Arguments for `roon focus`
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        let mut push = |flag: &str, value: String| {
+            args.push(flag.to_string());
+            args.push(value);
+        };
+        if let Some(year) = self.year_from {
+            push("--from", year.to_string());
+        }
+        if let Some(year) = self.year_to {
+            push("--to", year.to_string());
+        }
+        if let Some(format) = &self.format {
+            push("--format", format.clone());
+        }
+        if let Some(label) = &self.label {
+            push("--label", label.clone());
+        }
+        for genre in &self.genres {
+            push("--genre", genre.clone());
+        }
+        args
+    }
+
+    /// One-line description ("1958–1965 · FLAC · Blue Note · Jazz")
+    pub fn summary(&self) -> String {
+        let years = match (self.year_from, self.year_to) {
+            (Some(from), Some(to)) => Some(format!("{}–{}", from, to)),
+            (Some(from), None) => Some(format!("{}–", from)),
+            (None, Some(to)) => Some(format!("–{}", to)),
+            (None, None) => None,
+        };
+        let genres = (!self.genres.is_empty()).then(|| self.genres.join(" + "));
+        [years, self.format.clone(), self.label.clone(), genres]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// A browse level: the root plus the item index selected at each step
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BrowsePath {
+    pub root: BrowseRoot,
+    pub indexes: Vec<usize>,
+}
+
+impl BrowsePath {
+    fn root(root: BrowseRoot) -> Self {
+        Self {
+            root,
+            indexes: Vec::new(),
+        }
+    }
+
+    pub fn child(&self, index: usize) -> Self {
+        let mut child = self.clone();
+        child.indexes.push(index);
+        child
+    }
+
+    fn parent(&self) -> Self {
+        let mut parent = self.clone();
+        parent.indexes.pop();
+        parent
+    }
+
+    fn contains(&self, other: &BrowsePath) -> bool {
+        self.root == other.root && other.indexes.starts_with(&self.indexes)
+    }
+}
+
+/// Source label of search results outside the streaming service sections
+const LIBRARY: &str = "Library";
+
+/// Where an item of a merged list is selected: its level and index there
+pub type Origin = (BrowsePath, usize);
+
+/// A result category ("Albums", "Tracks", ...) of a unified search, with
+/// where each source lists it
+#[derive(Debug, Clone)]
+pub struct MergedCategory {
+    pub title: String,
+    /// (source label, path of the category's level)
+    pub sources: Vec<(String, BrowsePath)>,
+    /// Entries across the sources (None when a source's count isn't known)
+    pub count: Option<usize>,
+}
+
+/// Browse/search navigation with a short-lived cache of visited levels
+///
+/// The CLI keeps its own position in the browse hierarchy. Levels served from
+/// the cache don't move it, so before any uncached call the CLI is first
+/// walked (back/select) to the level the UI is on.
+#[derive(Default)]
+pub struct Browser {
+    cache: HashMap<BrowsePath, (Instant, BrowseResult)>,
+    /// Level the CLI is actually at (`None` when unknown, e.g. after an error)
+    synced: Option<BrowsePath>,
+}
+
+impl Browser {
+    /// Open the library root or a search, returning its path and items
+    pub fn open(&mut self, root: BrowseRoot) -> Result<(BrowsePath, BrowseResult)> {
+        let path = BrowsePath::root(root);
+        if let Some(result) = self.cached(&path) {
+            return Ok((path, result));
+        }
+        let result = self.fetch_root(&path)?;
+        Ok((path, result))
+    }
+
+    /// Search the library and the streaming service sections (e.g. "TIDAL")
+    /// at once, merging their categories by title
+    ///
+    /// The CLI has a single browse position, so the sections can't be
+    /// queried in parallel and are opened one after another. Categories are
+    /// counted from what the listing already tells (a cached level, or a
+    /// count in the subtitle) rather than by opening each one. Returns
+    /// `None` when the results have none of the sections.
+    pub fn unified_search(
+        &mut self,
+        query: &str,
+        sections: &[String],
+    ) -> Result<Option<(BrowsePath, Vec<MergedCategory>)>> {
+        let (root, result) = self.open(BrowseRoot::Search(query.to_string()))?;
+        let is_section = |title: &str| sections.iter().any(|s| s.eq_ignore_ascii_case(title));
+        if !result.items.iter().any(|item| is_section(&item.title)) {
+            return Ok(None);
+        }
+
+        let mut categories: Vec<MergedCategory> = Vec::new();
+        let mut add = |title: &str, source: &str, path: BrowsePath, count: Option<usize>| {
+            let source = (source.to_string(), path);
+            match categories.iter_mut().find(|c| c.title == title) {
+                Some(category) => {
+                    category.sources.push(source);
+                    category.count = category.count.zip(count).map(|(a, b)| a + b);
+                }
+                None => categories.push(MergedCategory {
+                    title: title.to_string(),
+                    sources: vec![source],
+                    count,
+                }),
+            }
+        };
+        for (index, item) in result.items.iter().enumerate() {
+            if !is_section(&item.title) {
+                let path = root.child(index);
+                let count = self.known_count(&path, item);
+                add(&item.title, LIBRARY, path, count);
+                continue;
+            }
+            let section = self.select(&root, index)?;
+            let path = root.child(index);
+            for (child, category) in section.items.iter().enumerate() {
+                let count = self.known_count(&path.child(child), category);
+                add(&category.title, &item.title, path.child(child), count);
+            }
+        }
+        Ok(Some((root, categories)))
+    }
+
+    /// Items of a merged category from every source, labelled with the
+    /// source, and the (level, index) each one is selected at
+    ///
+    /// `full` loads every source's level completely; otherwise a "Show
+    /// more" entry (without an origin) ends the items when a source is only
+    /// partly loaded.
+    pub fn merged_items(
+        &mut self,
+        category: &MergedCategory,
+        full: bool,
+    ) -> Result<(Vec<BrowseItem>, Vec<Origin>)> {
+        let mut items = Vec::new();
+        let mut origins = Vec::new();
+        let mut remaining = 0;
+        for (source, path) in &category.sources {
+            let Some(&index) = path.indexes.last() else {
+                continue;
+            };
+            let level = if full {
+                self.load_all(path)?
+            } else {
+                self.select(&path.parent(), index)?
+            };
+            remaining += level.remaining();
+            for (i, mut item) in level.items.into_iter().enumerate() {
+                item.subtitle = Some(match item.subtitle {
+                    Some(subtitle) if !subtitle.is_empty() => format!("{} · {}", source, subtitle),
+                    _ => source.clone(),
+                });
+                items.push(item);
+                origins.push((path.clone(), i));
+            }
+        }
+        if remaining > 0 {
+            items.push(BrowseItem::show_more(remaining));
+        }
+        Ok((items, origins))
+    }
+
+    /// Number of entries of the level an item opens (None when it can't be
+    /// fetched)
+    pub fn count(&mut self, path: &BrowsePath, index: usize) -> Option<usize> {
+        match self.select(path, index) {
+            Ok(level) => Some(level.count.unwrap_or(level.items.len())),
+            Err(e) => {
+                tracing::warn!("Failed to count {:?}: {}", path.child(index), e);
+                None
+            }
+        }
+    }
+
+    /// Number of entries of the level `item` opens at `path`, if it's known
+    /// without fetching it: from the cached level, or a subtitle that starts
+    /// with the count ("128 Albums")
+    fn known_count(&self, path: &BrowsePath, item: &BrowseItem) -> Option<usize> {
+        if let Some(level) = self.cached(path) {
+            return Some(level.count.unwrap_or(level.items.len()));
+        }
+        item.subtitle
+            .as_deref()?
+            .split_whitespace()
+            .next()?
+            .replace(',', "")
+            .parse()
+            .ok()
+    }
+
+    /// The level at `path` with all its entries, loading the pages the CLI
+    /// left out through list continuation
+    pub fn load_all(&mut self, path: &BrowsePath) -> Result<BrowseResult> {
+        let mut level = match self.cached(path) {
+            Some(level) => level,
+            None => self.refresh(path)?,
+        };
+        if level.remaining() == 0 {
+            return Ok(level);
+        }
+        self.sync_to(path)?;
+        while level.remaining() > 0 {
+            let page = self.call(super::load(level.items.len()))?;
+            if page.items.is_empty() {
+                break;
+            }
+            level.items.extend(page.items);
+        }
+        self.store(path, &level);
+        Ok(level)
+    }
+
+    /// Find an album's level by searching for its title: the path, its
+    /// items and a breadcrumb per level on the way
+    ///
+    /// Among albums of the same title, the one whose subtitle names the
+    /// artist wins.
+    pub fn find_album(
+        &mut self,
+        artist: &str,
+        album: &str,
+    ) -> Result<Option<(BrowsePath, BrowseResult, Vec<String>)>> {
+        let (root, result) = self.open(BrowseRoot::Search(album.to_string()))?;
+        let Some(albums_index) = position(&result, "Albums") else {
+            return Ok(None);
+        };
+        let albums = self.select(&root, albums_index)?;
+        let matches = |item: &BrowseItem| item.title.eq_ignore_ascii_case(album);
+        let by_artist = albums.items.iter().position(|item| {
+            matches(item)
+                && item
+                    .subtitle
+                    .as_deref()
+                    .is_some_and(|subtitle| subtitle.contains(artist))
+        });
+        let Some(index) = by_artist.or_else(|| albums.items.iter().position(matches)) else {
+            return Ok(None);
+        };
+
+        let albums_path = root.child(albums_index);
+        let level = self.select(&albums_path, index)?;
+        let breadcrumbs = vec![
+            result.title.unwrap_or_else(|| "Search".to_string()),
+            albums.title.unwrap_or_else(|| "Albums".to_string()),
+            level
+                .title
+                .clone()
+                .unwrap_or_else(|| albums.items[index].title.clone()),
+        ];
+        Ok(Some((albums_path.child(index), level, breadcrumbs)))
+    }
+
+    /// Pick a random album of the library and run one of its album actions
+    /// ("Play Now", "Queue", ...) on the CLI's zone
+    ///
+    /// Returns the album's title and artist, or `None` when the library has
+    /// no albums. Any album of the level can be picked, not only the first
+    /// page the CLI lists: the page holding the pick is loaded for it.
+    pub fn random_album(&mut self, action: &str) -> Result<Option<(String, String)>> {
+        let Some((path, level, _)) = self.library_section("Albums")? else {
+            return Ok(None);
+        };
+        let total = level.items.len() + level.remaining();
+        if total == 0 {
+            return Ok(None);
+        }
+
+        let index = random_index(total);
+        let album = match level.items.get(index) {
+            Some(album) => album.clone(),
+            None => {
+                self.sync_to(&path)?;
+                let page = self.call(super::load(index))?;
+                page.items
+                    .into_iter()
+                    .next()
+                    .with_context(|| format!("no album at {} of {}", index, total))?
+            }
+        };
+        let picked = (
+            album.title.clone(),
+            album.subtitle.clone().unwrap_or_default(),
+        );
+        if !self.run_action(&path, index, action)? {
+            bail!("\"{}\" has no {} action", picked.0, action);
+        }
+        Ok(Some(picked))
+    }
+
+    /// Run one of an item's actions ("Play Now", "Queue", ...) by title;
+    /// returns whether the item has that action
+    ///
+    /// The item is either an action list itself, or a level starting with
+    /// one (the "Play Album" list of an album, or a playlist's).
+    pub fn run_action(&mut self, path: &BrowsePath, index: usize, action: &str) -> Result<bool> {
+        let item_path = path.child(index);
+        let level = self.select(path, index)?;
+        if let Some(run) = position(&level, action) {
+            self.select(&item_path, run)?;
+            return Ok(true);
+        }
+        let Some(list) = level
+            .items
+            .iter()
+            .position(|item| item.hint.as_deref() == Some("action_list"))
+        else {
+            return Ok(false);
+        };
+        let actions = self.select(&item_path, list)?;
+        let Some(run) = position(&actions, action) else {
+            return Ok(false);
+        };
+        self.select(&item_path.child(list), run)?;
+        Ok(true)
+    }
+
+    /// Mixes and recommendations: the items of every level titled like one
+    /// of `titles`, on the root level or in a streaming service section,
+    /// labelled with where they're from, and the (level, index) each one is
+    /// selected at
+    ///
+    /// `fresh` drops the cached library levels first, so the CLI is asked
+    /// again (mixes change daily).
+    pub fn for_you(
+        &mut self,
+        titles: &[String],
+        services: &[String],
+        fresh: bool,
+    ) -> Result<(Vec<BrowseItem>, Vec<Origin>)> {
+        if fresh {
+            self.cache
+                .retain(|cached, _| cached.root != BrowseRoot::Library);
+        }
+        let matches = |names: &[String], title: &str| {
+            names.iter().any(|name| name.eq_ignore_ascii_case(title))
+        };
+        let (root, level) = self.open(BrowseRoot::Library)?;
+        let mut found = MergedCategory {
+            title: "For You".to_string(),
+            sources: Vec::new(),
+            count: None,
+        };
+        for (index, item) in level.items.iter().enumerate() {
+            if matches(titles, &item.title) {
+                found.sources.push((item.title.clone(), root.child(index)));
+            } else if matches(services, &item.title) {
+                let section = self.select(&root, index)?;
+                for (child, level) in section.items.iter().enumerate() {
+                    if matches(titles, &level.title) {
+                        let source = format!("{} {}", item.title, level.title);
+                        found.sources.push((source, root.child(index).child(child)));
+                    }
+                }
+            }
+        }
+        self.merged_items(&found, true)
+    }
+
+    /// Open a library section ("Genres", "Composers", ...): the path, its
+    /// items and a breadcrumb per level on the way
+    ///
+    /// Sections are looked for on the root level, then under Library (where
+    /// Roon lists Albums and Composers). Returns `None` when the Core has no
+    /// such section.
+    pub fn library_section(
+        &mut self,
+        title: &str,
+    ) -> Result<Option<(BrowsePath, BrowseResult, Vec<String>)>> {
+        let (mut path, mut level) = self.open(BrowseRoot::Library)?;
+        let mut breadcrumbs = vec![level.title.clone().unwrap_or_else(|| LIBRARY.to_string())];
+        if position(&level, title).is_none() {
+            let Some(index) = position(&level, LIBRARY) else {
+                return Ok(None);
+            };
+            let library = self.select(&path, index)?;
+            breadcrumbs.push(library.title.clone().unwrap_or_else(|| LIBRARY.to_string()));
+            (path, level) = (path.child(index), library);
+        }
+        let Some(index) = position(&level, title) else {
+            return Ok(None);
+        };
+        let section = self.select(&path, index)?;
+        breadcrumbs.push(section.title.clone().unwrap_or_else(|| title.to_string()));
+        Ok(Some((path.child(index), section, breadcrumbs)))
+    }
+
+    /// Enter item `index` of the level at `path`
+    ///
+    /// Results with an action (e.g. "message" after starting playback) aren't
+    /// levels, so they're never cached and the path stays where it was.
+    pub fn select(&mut self, path: &BrowsePath, index: usize) -> Result<BrowseResult> {
+        let child = path.child(index);
+        if let Some(result) = self.cached(&child) {
+            return Ok(result);
+        }
+        self.sync_to(path)?;
+        let result = self.call(super::select(index))?;
+        if result.action.as_deref() != Some("message") {
+            self.store(&child, &result);
+            self.synced = Some(child);
+        }
+        Ok(result)
+    }
+
+    /// Go up one level from `path`
+    pub fn back(&mut self, path: &BrowsePath) -> Result<BrowseResult> {
+        let parent = path.parent();
+        if let Some(result) = self.cached(&parent) {
+            return Ok(result);
+        }
+        self.sync_to(path)?;
+        let result = self.call(super::back())?;
+        self.store(&parent, &result);
+        self.synced = Some(parent);
+        Ok(result)
+    }
+
+    /// Reload the level at `path`, dropping it and everything below from the cache
+    pub fn refresh(&mut self, path: &BrowsePath) -> Result<BrowseResult> {
+        self.cache.retain(|cached, _| !path.contains(cached));
+        match path.indexes.last() {
+            None => self.fetch_root(path),
+            Some(&index) => self.select(&path.parent(), index),
+        }
+    }
+
+    fn cached(&self, path: &BrowsePath) -> Option<BrowseResult> {
+        self.cache
+            .get(path)
+            .filter(|(fetched, _)| fetched.elapsed() < CACHE_TTL)
+            .map(|(_, result)| result.clone())
+    }
+
+    fn store(&mut self, path: &BrowsePath, result: &BrowseResult) {
+        self.cache
+            .insert(path.clone(), (Instant::now(), result.clone()));
+    }
+
+    /// Reset the CLI to a root level
+    fn fetch_root(&mut self, path: &BrowsePath) -> Result<BrowseResult> {
+        let result = match &path.root {
+            BrowseRoot::Library => self.call(super::browse())?,
+            BrowseRoot::Search(query) => self.call(super::search(query))?,
+            BrowseRoot::Focus(query) => self.call(super::focus(query))?,
+        };
+        self.store(path, &result);
+        self.synced = Some(path.clone());
+        Ok(result)
+    }
+
+    /// Walk the CLI from where it is to `path` (a no-op when already there)
+    fn sync_to(&mut self, path: &BrowsePath) -> Result<()> {
+        let synced = match &self.synced {
+            Some(synced) if synced.root == path.root => synced.clone(),
+            _ => {
+                self.fetch_root(&BrowsePath::root(path.root.clone()))?;
+                BrowsePath::root(path.root.clone())
+            }
+        };
+
+        let common = synced
+            .indexes
+            .iter()
+            .zip(&path.indexes)
+            .take_while(|(a, b)| a == b)
+            .count();
+        for _ in common..synced.indexes.len() {
+            self.call(super::back())?;
+        }
+        let mut current = BrowsePath {
+            root: path.root.clone(),
+            indexes: path.indexes[..common].to_vec(),
+        };
+        for &index in &path.indexes[common..] {
+            current = current.child(index);
+            let result = self.call(super::select(index))?;
+            self.store(&current, &result);
+        }
+        self.synced = Some(path.clone());
+        Ok(())
+    }
+
+    /// Forget the CLI position when a call fails; it may have moved or not
+    fn call(&mut self, result: Result<BrowseResult>) -> Result<BrowseResult> {
+        if result.is_err() {
+            self.synced = None;
+        }
+        result
+    }
+}
+
+/// Index of the item titled `title` (ignoring case)
+fn position(level: &BrowseResult, title: &str) -> Option<usize> {
+    level
+        .items
+        .iter()
+        .position(|item| item.title.eq_ignore_ascii_case(title))
+}
+
+/// A random index below `len`, from the std hasher's random keys and the clock
+fn random_index(len: usize) -> usize {
+    use std::hash::{BuildHasher, Hasher};
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+    hasher.write_u128(now.unwrap_or_default().as_nanos());
+    (hasher.finish() % len as u64) as usize
+}
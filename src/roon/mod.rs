@@ -1,15 +1,155 @@
+mod browser;
 #[allow(dead_code)]
 mod models;
+mod session;
+mod version;
 
 use anyhow::Result;
-use std::process::Command;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fmt;
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
 
-pub use models::{BrowseItem, BrowseResult, PlaybackState, Zone};
+pub use browser::{BrowsePath, BrowseRoot, Browser, FocusQuery, MergedCategory, Origin};
+pub use models::{
+    BrowseItem, BrowseResult, DspPreset, NowPlaying, Output, PlaybackState, QualityBadge,
+    QueueItem, Status, Volume, VolumeType, Zone,
+};
+pub use version::detect;
+
+/// Configured roon CLI invocation: optional prefix command, the executable
+/// and global arguments (defaults to `roon` on PATH)
+static COMMAND: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Whether the CLI understands `roon status` (older versions only have `zones`)
+static STATUS_SUPPORTED: AtomicBool = AtomicBool::new(true);
+
+/// How long a command may take before its process is killed, in
+/// milliseconds (0: wait as long as it takes)
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(10_000);
+
+/// A command the roon CLI didn't answer in time, e.g. while it waits on a
+/// Core that went away; its process was killed
+#[derive(Debug)]
+pub struct Timeout {
+    command: String,
+    after: Duration,
+}
+
+impl Timeout {
+    fn new(args: &[&str], after: Duration) -> Self {
+        Self {
+            command: args.first().unwrap_or(&"").to_string(),
+            after,
+        }
+    }
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "roon {} timed out after {}s",
+            self.command,
+            self.after.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+/// Give up on commands that take longer than this (zero never does)
+pub fn set_timeout(timeout: Duration) {
+    TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// The command timeout, if there is one
+fn timeout() -> Option<Duration> {
+    let ms = TIMEOUT_MS.load(Ordering::Relaxed);
+    (ms > 0).then(|| Duration::from_millis(ms))
+}
+
+/// Use a different roon CLI invocation, e.g. `["ssh", "media", "roon"]`
+///
+/// A running session is stopped, so the next command reaches the new one
+/// (another Core, when switching).
+pub fn set_command(invocation: &[String]) {
+    if let Ok(mut command) = COMMAND.write() {
+        *command = invocation.to_vec();
+    }
+    session::stop();
+}
+
+/// A process for the roon CLI, ready for a command's arguments
+///
+/// It gets its own process group, so killing a hung command also takes
+/// down what it started (the CLI behind a wrapper script or `ssh`).
+fn command() -> Command {
+    let invocation = COMMAND.read().map(|c| c.clone()).unwrap_or_default();
+    let mut cmd = match invocation.split_first() {
+        Some((program, rest)) => {
+            let mut cmd = Command::new(program);
+            cmd.args(rest);
+            cmd
+        }
+        None => Command::new("roon"),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    cmd
+}
+
+/// Kill a roon CLI process with its process group, and reap it
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = i32::try_from(child.id()) {
+        unsafe { libc::kill(-pid, libc::SIGKILL) };
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Through ssh the arguments are joined into one remote shell command, so
+/// they have to be quoted for it (a search for "Miles Davis" stays one word)
+fn quoted_args(args: &[&str]) -> Vec<String> {
+    let over_ssh = COMMAND
+        .read()
+        .ok()
+        .and_then(|invocation| invocation.first().cloned())
+        .is_some_and(|program| {
+            std::path::Path::new(&program)
+                .file_name()
+                .is_some_and(|name| name == "ssh")
+        });
+    args.iter()
+        .map(|arg| match over_ssh {
+            true => format!("'{}'", arg.replace('\'', r"'\''")),
+            false => arg.to_string(),
+        })
+        .collect()
+}
 
 /// Execute a roon CLI command and return stdout
+///
+/// Goes through the persistent `roon session` process when available, and
+/// spawns `roon` for this one command otherwise.
 fn run_command(args: &[&str]) -> Result<String> {
-    let output = Command::new("roon").args(args).output()?;
+    let started = Instant::now();
+    let result = session::run(args).unwrap_or_else(|| spawn_command(args));
+    crate::metrics::record_command(args.first().unwrap_or(&""), started.elapsed());
+    result
+}
 
+/// Execute a roon CLI command in a new process
+fn spawn_command(args: &[&str]) -> Result<String> {
+    let output = execute(args)?;
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     } else {
@@ -26,11 +166,177 @@ fn run_command(args: &[&str]) -> Result<String> {
     }
 }
 
-/// Get all zones with their current state
-pub fn get_zones() -> Result<Vec<Zone>> {
+/// Run the roon CLI in a new process and collect its output, killing it if
+/// it outlasts the timeout
+fn execute(args: &[&str]) -> Result<std::process::Output> {
+    let mut child = command()
+        .args(quoted_args(args))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Read both pipes while waiting, so a chatty command can't fill one up
+    // and block
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = match timeout() {
+        Some(limit) => match wait_for(&mut child, limit)? {
+            Some(status) => status,
+            None => {
+                kill(&mut child);
+                tracing::warn!("roon {:?} hung, killed it after {:?}", args, limit);
+                return Err(Timeout::new(args, limit).into());
+            }
+        },
+        None => child.wait()?,
+    };
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// Read a child's pipe to the end on its own thread
+fn drain(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+/// Wait for a child to exit, for at most `limit` (None: still running)
+fn wait_for(child: &mut Child, limit: Duration) -> Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + limit;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Whether the CLI's output or error is its complaint about a command or
+/// option it doesn't know
+fn is_usage_error(text: &str) -> bool {
+    let text = text.to_lowercase();
+    [
+        "unknown command",
+        "unknown argument",
+        "unknown option",
+        "unrecognized command",
+        "invalid command",
+        "usage:",
+    ]
+    .iter()
+    .any(|marker| text.contains(marker))
+}
+
+/// Check whether the roon CLI can be run at all (one that hangs can't)
+pub fn is_available() -> bool {
+    match execute(&["--version"]) {
+        Ok(_) => true,
+        Err(e) => {
+            tracing::info!("roon CLI unavailable: {}", e);
+            false
+        }
+    }
+}
+
+/// Get all zones with their current state, and warnings about zones that
+/// couldn't be parsed and were left out
+pub fn get_zones() -> Result<(Vec<Zone>, Vec<String>)> {
     let output = run_command(&["zones", "--json"])?;
-    let zones: Vec<Zone> = serde_json::from_str(&output)?;
-    Ok(zones)
+    let zones: Vec<serde_json::Value> = serde_json::from_str(&output)?;
+    Ok(parse_each(zones, "zone"))
+}
+
+/// `roon status` output before its zones and queue items are parsed
+#[derive(Deserialize)]
+struct RawStatus {
+    zones: Vec<serde_json::Value>,
+    #[serde(default)]
+    queue: Option<Vec<serde_json::Value>>,
+}
+
+impl RawStatus {
+    fn parse(self) -> Status {
+        let (zones, mut warnings) = parse_each(self.zones, "zone");
+        let queue = self.queue.map(|items| {
+            let (items, queue_warnings) = parse_each(items, "queue item");
+            warnings.extend(queue_warnings);
+            items
+        });
+        Status {
+            zones,
+            queue,
+            warnings,
+        }
+    }
+}
+
+/// Parse each value on its own, so one malformed entry (e.g. from a newer
+/// CLI) is left out with a warning instead of failing the whole list
+fn parse_each<T: DeserializeOwned>(
+    values: Vec<serde_json::Value>,
+    what: &str,
+) -> (Vec<T>, Vec<String>) {
+    let mut parsed = Vec::with_capacity(values.len());
+    let mut warnings = Vec::new();
+    for value in values {
+        let name = value
+            .get("displayName")
+            .or_else(|| value.get("track"))
+            .and_then(|name| name.as_str())
+            .map(|name| format!(" \"{}\"", name))
+            .unwrap_or_default();
+        match serde_json::from_value(value) {
+            Ok(item) => parsed.push(item),
+            Err(e) => warnings.push(format!("Skipped {}{}: {}", what, name, e)),
+        }
+    }
+    (parsed, warnings)
+}
+
+/// Get zones (and the active zone's queue, if asked) in a single round trip
+///
+/// Falls back to `roon zones` on CLI versions without `status`; the queue is
+/// then left out and has to be fetched separately.
+pub fn status(with_queue: bool) -> Result<Status> {
+    if STATUS_SUPPORTED.load(Ordering::Relaxed) {
+        let mut args = vec!["status", "--json"];
+        if with_queue {
+            args.push("--queue");
+        }
+        // Only a usage error means the command is unknown; timeouts, a Core
+        // that's away or a garbled reply are passing trouble
+        match run_command(&args) {
+            Ok(output) => match serde_json::from_str::<RawStatus>(&output) {
+                Ok(status) => return Ok(status.parse()),
+                // Some versions print their usage and exit successfully
+                Err(_) if is_usage_error(&output) => {}
+                Err(e) => return Err(e.into()),
+            },
+            Err(e) if !e.is::<Timeout>() && is_usage_error(&e.to_string()) => {}
+            Err(e) => return Err(e),
+        }
+        tracing::warn!("roon status unknown to this CLI, using separate calls");
+        STATUS_SUPPORTED.store(false, Ordering::Relaxed);
+    }
+
+    let (zones, warnings) = get_zones()?;
+    Ok(Status {
+        zones,
+        queue: None,
+        warnings,
+    })
 }
 
 /// Set the active zone by name
@@ -39,6 +345,19 @@ pub fn set_zone(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Play a file or folder of the Core's storage (a path as the Core sees
+/// it) on the active zone
+pub fn play_path(path: &str) -> Result<()> {
+    run_command(&["play", "--path", path])?;
+    Ok(())
+}
+
+/// Play a library track by its identifier on the active zone
+pub fn play_item(id: &str) -> Result<()> {
+    run_command(&["play", "--item", id])?;
+    Ok(())
+}
+
 /// Toggle play/pause
 pub fn playpause() -> Result<()> {
     run_command(&["playpause"])?;
@@ -64,6 +383,12 @@ pub fn shuffle(on: bool) -> Result<()> {
     Ok(())
 }
 
+/// Seek to an absolute position in the current track (seconds)
+pub fn seek(seconds: f64) -> Result<()> {
+    run_command(&["seek", &format!("{}", seconds.round() as u64)])?;
+    Ok(())
+}
+
 /// Set loop mode (disabled, loop, loop_one)
 pub fn set_loop(mode: &str) -> Result<()> {
     run_command(&["loop", mode])?;
@@ -77,6 +402,24 @@ pub fn radio(on: bool) -> Result<()> {
     Ok(())
 }
 
+/// Set volume leveling mode (off, auto, album, track)
+pub fn set_leveling(mode: &str) -> Result<()> {
+    run_command(&["leveling", mode])?;
+    Ok(())
+}
+
+/// DSP presets of the active zone
+pub fn dsp_presets() -> Result<Vec<DspPreset>> {
+    let output = run_command(&["dsp", "presets", "--json"])?;
+    Ok(serde_json::from_str(&output)?)
+}
+
+/// Switch the active zone to a DSP preset
+pub fn set_dsp_preset(name: &str) -> Result<()> {
+    run_command(&["dsp", "preset", name])?;
+    Ok(())
+}
+
 /// Set volume for an output
 pub fn volume(output: &str, value: &str) -> Result<()> {
     run_command(&["volume", value, "--output", output])?;
@@ -95,6 +438,23 @@ pub fn unmute(output: &str) -> Result<()> {
     Ok(())
 }
 
+/// Get the play queue of the active zone
+pub fn queue() -> Result<Vec<QueueItem>> {
+    let output = run_command(&["queue", "--json"])?;
+    let items: Vec<serde_json::Value> = serde_json::from_str(&output)?;
+    let (items, warnings) = parse_each(items, "queue item");
+    for warning in warnings {
+        tracing::warn!("{}", warning);
+    }
+    Ok(items)
+}
+
+/// Remove an item from the active zone's play queue
+pub fn queue_remove(queue_item_id: u64) -> Result<()> {
+    run_command(&["queue", "remove", &queue_item_id.to_string()])?;
+    Ok(())
+}
+
 /// Browse the library (resets to root)
 pub fn browse() -> Result<BrowseResult> {
     let output = run_command(&["browse", "--json"])?;
@@ -109,6 +469,18 @@ pub fn search(query: &str) -> Result<BrowseResult> {
     Ok(result)
 }
 
+/// Albums of the library narrowed by Focus filters; like a search, this
+/// starts a new browse context at the filtered list
+pub fn focus(query: &FocusQuery) -> Result<BrowseResult> {
+    let args = query.args();
+    let mut command = vec!["focus"];
+    command.extend(args.iter().map(String::as_str));
+    command.push("--json");
+    let output = run_command(&command)?;
+    let result: BrowseResult = serde_json::from_str(&output)?;
+    Ok(result)
+}
+
 /// Select an item by index in the current browse context (0-based internally, 1-based for CLI)
 pub fn select(index: usize) -> Result<BrowseResult> {
     let output = run_command(&["select", &(index + 1).to_string(), "--json"])?;
@@ -116,6 +488,14 @@ pub fn select(index: usize) -> Result<BrowseResult> {
     Ok(result)
 }
 
+/// Load more entries of the current browse level from `offset` (0-based),
+/// Roon's list continuation for levels longer than one page
+pub fn load(offset: usize) -> Result<BrowseResult> {
+    let output = run_command(&["load", "--offset", &offset.to_string(), "--json"])?;
+    let result: BrowseResult = serde_json::from_str(&output)?;
+    Ok(result)
+}
+
 /// Go back one level in the browse context
 pub fn back() -> Result<BrowseResult> {
     let output = run_command(&["back", "--json"])?;
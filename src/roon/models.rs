@@ -1,72 +1,261 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Deserialize)]
+// Everything but the ids has a default, so zones from newer (or older) CLI
+// versions with missing fields still parse; unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Zone {
     pub zone_id: String,
+    #[serde(default)]
     pub display_name: String,
-    pub state: String,
+    #[serde(default)]
+    pub state: PlaybackState,
+    #[serde(default)]
     pub outputs: Vec<Output>,
     pub now_playing: Option<NowPlaying>,
     #[serde(default)]
     pub queue_items_remaining: u32,
     #[serde(default)]
     pub queue_time_remaining: u32,
+    #[serde(default)]
     pub settings: ZoneSettings,
 }
 
 impl Zone {
     pub fn is_playing(&self) -> bool {
-        self.state == "playing"
+        self.state == PlaybackState::Playing
     }
 
     pub fn is_paused(&self) -> bool {
-        self.state == "paused"
+        self.state == PlaybackState::Paused
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Output {
     pub output_id: String,
+    #[serde(default)]
     pub display_name: String,
     pub volume: Option<Volume>,
+    /// What the output plays through (absent on CLI versions that don't
+    /// report it)
+    #[serde(default)]
+    pub device: Option<Device>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// The device behind an output
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Device {
+    /// Model of the device or DAC ("RME ADI-2 DAC", "HomePod")
+    pub name: Option<String>,
+    /// Endpoint type: "RAAT", "AirPlay", "Chromecast", "Squeezebox", ...
+    pub endpoint: Option<String>,
+    /// How it's connected: "network", "usb", "hdmi", "internal", ...
+    pub connection: Option<String>,
+    /// Format the device is fed right now, after DSP and conversions
+    pub format: Option<Quality>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Volume {
+    #[serde(rename = "type", default)]
+    pub kind: VolumeType,
+    #[serde(default)]
     pub value: f64,
+    #[serde(default)]
     pub min: f64,
+    #[serde(default = "default_volume_max")]
     pub max: f64,
+    pub step: Option<f64>,
     #[serde(default)]
     pub is_muted: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+fn default_volume_max() -> f64 {
+    100.0
+}
+
+/// How an output's volume is controlled
+///
+/// Outputs with fixed volume have no `volume` object at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VolumeType {
+    /// Absolute level on a min..max scale
+    #[default]
+    Number,
+    /// Absolute level in decibels (min/max usually negative)
+    Db,
+    /// Only relative up/down steps, no known level
+    Incremental,
+    /// A kind this version doesn't know, handled like a plain number so the
+    /// rest of the zone still parses
+    #[serde(other)]
+    Unknown,
+}
+
+impl Volume {
+    /// Format the current level for display ("45%", "-23.5 dB", "±")
+    pub fn level_display(&self) -> String {
+        match self.kind {
+            VolumeType::Db => format!("{:.1} dB", self.value),
+            VolumeType::Incremental => "±".to_string(),
+            VolumeType::Number if self.min == 0.0 && self.max == 100.0 => {
+                format!("{:.0}%", self.value)
+            }
+            VolumeType::Number | VolumeType::Unknown => format!("{:.0}", self.value),
+        }
+    }
+
+    /// Relative change to send for a step of `percent` of this output's range
+    ///
+    /// dB and stepped outputs are rounded to their step size; incremental
+    /// outputs can only move one step at a time.
+    pub fn relative_change(&self, percent: f64) -> f64 {
+        if self.kind == VolumeType::Incremental {
+            return percent.signum();
+        }
+        let delta = percent * (self.max - self.min) / 100.0;
+        match self.step {
+            Some(step) if step > 0.0 => {
+                let steps = (delta / step).round();
+                // Always move at least one step
+                let steps = if steps == 0.0 { delta.signum() } else { steps };
+                steps * step
+            }
+            _ => delta.round(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NowPlaying {
+    #[serde(default)]
     pub artist: String,
+    #[serde(default)]
     pub track: String,
+    #[serde(default)]
     pub album: String,
+    #[serde(default)]
     pub image_key: String,
     #[serde(default)]
     pub seek_position: f64,
     #[serde(default)]
     pub length: f64,
     pub album_art_url: Option<String>,
+    // Classical metadata (present when Roon identifies a composition)
+    pub composer: Option<String>,
+    pub work: Option<String>,
+    pub movement: Option<String>,
+    // Release metadata for the current album
+    pub year: Option<u32>,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    /// Gain volume leveling applies to this track, in dB
+    pub leveling_gain: Option<f64>,
+    /// Chapter marks of long-form items (podcasts, audiobooks)
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+    /// How the stream can be seeked: "track" for normal tracks, "one_hour"
+    /// (a buffered hour) or "live" for radio
+    pub seek_model: Option<String>,
+    /// Format of the source, from CLI versions that report the signal path
+    #[serde(default)]
+    pub quality: Option<Quality>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl NowPlaying {
+    /// Internet radio and other live streams: no length, nothing to seek
+    ///
+    /// Told by the seek model; without one (older CLI versions), a track
+    /// that's playing without a length is taken for a stream. A length that
+    /// isn't known yet while loading or paused doesn't count.
+    pub fn is_live(&self, playing: bool) -> bool {
+        match self.seek_model.as_deref() {
+            Some(model) => matches!(model, "live" | "one_hour"),
+            None => playing && self.length <= 0.0,
+        }
+    }
+
+    /// The chapter playing at a position
+    pub fn chapter_at(&self, position: f64) -> Option<&Chapter> {
+        self.chapters.iter().rev().find(|c| c.start <= position)
+    }
+
+    /// Whether a browse item is this track or its album: the title matches
+    /// (without an album's track number, "3. Title") and so does the artist
+    /// when the item names one
+    pub fn is_item(&self, item: &BrowseItem) -> bool {
+        let title = item.title.trim_start_matches(|c: char| c.is_ascii_digit());
+        let title = match title.strip_prefix(". ") {
+            Some(rest) if title.len() < item.title.len() => rest,
+            _ => item.title.as_str(),
+        };
+        let titled = [&self.track, &self.album]
+            .iter()
+            .any(|name| !name.is_empty() && title.eq_ignore_ascii_case(name));
+        let by_artist = match item.subtitle.as_deref() {
+            Some(subtitle) if !subtitle.is_empty() && !self.artist.is_empty() => {
+                let (subtitle, artist) = (subtitle.to_lowercase(), self.artist.to_lowercase());
+                subtitle.contains(&artist) || artist.contains(&subtitle)
+            }
+            _ => true,
+        };
+        titled && by_artist
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+pub struct Chapter {
+    pub title: String,
+    /// Start position in seconds
+    #[serde(default)]
+    pub start: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
 pub struct ZoneSettings {
     #[serde(rename = "loop")]
     pub loop_mode: String,
     pub shuffle: bool,
     pub auto_radio: bool,
+    /// Volume leveling mode: "off", "auto", "album" or "track" (absent on
+    /// older CLI versions)
+    pub volume_leveling: Option<String>,
+    /// Name of the active DSP preset (absent on CLI versions without DSP
+    /// support, or when the zone has none)
+    pub dsp_preset: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl Default for ZoneSettings {
+    fn default() -> Self {
+        Self {
+            loop_mode: "disabled".to_string(),
+            shuffle: false,
+            auto_radio: false,
+            volume_leveling: None,
+            dsp_preset: None,
+        }
+    }
+}
+
+/// A saved DSP preset of the active zone ("Headphone EQ", "Speakers")
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DspPreset {
+    pub name: String,
+    #[serde(default)]
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct AlbumArt {
     pub url: String,
 }
@@ -79,6 +268,12 @@ pub struct BrowseItem {
     pub subtitle: Option<String>,
     pub image_key: Option<String>,
     pub hint: Option<String>,
+    /// Artwork URL, from CLI versions that resolve image keys
+    #[serde(default)]
+    pub album_art_url: Option<String>,
+    /// Release year of an album, from CLI versions that include it
+    #[serde(default)]
+    pub year: Option<i32>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -93,23 +288,195 @@ pub struct BrowseResult {
     pub message: Option<String>,
 }
 
+/// Hint of the "Show more" entry ending a partly loaded level
+const SHOW_MORE: &str = "show_more";
+
+impl BrowseItem {
+    /// Entry that loads the rest of a long level when selected
+    pub fn show_more(remaining: usize) -> Self {
+        Self {
+            item_key: None,
+            title: "Show more".to_string(),
+            subtitle: Some(format!("{} more", remaining)),
+            image_key: None,
+            hint: Some(SHOW_MORE.to_string()),
+            album_art_url: None,
+            year: None,
+        }
+    }
+
+    pub fn is_show_more(&self) -> bool {
+        self.hint.as_deref() == Some(SHOW_MORE)
+    }
+
+    /// Release year: the CLI's, or a year in the subtitle or title
+    /// ("2019", "(2021 Remaster)")
+    pub fn release_year(&self) -> Option<i32> {
+        let in_text = |text: &str| {
+            text.split(|c: char| !c.is_ascii_digit())
+                .filter(|word| word.len() == 4)
+                .filter_map(|word| word.parse().ok())
+                .find(|year| (1900..=2099).contains(year))
+        };
+        self.year
+            .or_else(|| self.subtitle.as_deref().and_then(in_text))
+            .or_else(|| in_text(&self.title))
+    }
+}
+
+impl BrowseResult {
+    /// Entries of the level not loaded yet (the CLI lists long levels a
+    /// page at a time)
+    pub fn remaining(&self) -> usize {
+        self.count
+            .map_or(0, |count| count.saturating_sub(self.items.len()))
+    }
+
+    /// Entries of the level, and a "Show more" entry when it's only partly
+    /// loaded
+    pub fn into_list(self) -> Vec<BrowseItem> {
+        let remaining = self.remaining();
+        let mut items = self.items;
+        if remaining > 0 {
+            items.push(BrowseItem::show_more(remaining));
+        }
+        items
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueItem {
+    pub queue_item_id: u64,
+    #[serde(default)]
+    pub track: String,
+    #[serde(default)]
+    pub artist: String,
+    #[serde(default)]
+    pub album: String,
+    #[serde(default)]
+    pub length: f64,
+    pub image_key: Option<String>,
+    pub album_art_url: Option<String>,
+    /// Where the track comes from ("Library", "TIDAL", "Qobuz", ...)
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub quality: Option<Quality>,
+}
+
+/// Audio format of a queued track (or fed to an output), as far as the
+/// Core knows it
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Quality {
+    /// "FLAC", "MQA", "DSD", ...
+    pub codec: Option<String>,
+    pub bit_depth: Option<u32>,
+    /// In Hz
+    pub sample_rate: Option<u32>,
+}
+
+impl Quality {
+    /// e.g. "FLAC 24-bit / 96 kHz"
+    pub fn label(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(codec) = &self.codec {
+            parts.push(codec.clone());
+        }
+        let depth = self.bit_depth.map(|bits| format!("{}-bit", bits));
+        let rate = self
+            .sample_rate
+            .map(|hz| format!("{} kHz", hz as f64 / 1000.0));
+        match (depth, rate) {
+            (Some(depth), Some(rate)) => parts.push(format!("{} / {}", depth, rate)),
+            (Some(one), None) | (None, Some(one)) => parts.push(one),
+            (None, None) => {}
+        }
+        parts.join(" ")
+    }
+
+    /// The badge summing up the format: DSD and MQA by codec, hi-res for
+    /// lossless PCM above 16-bit / 48 kHz, plain lossless below; lossy and
+    /// unknown formats get none
+    pub fn badge(&self) -> Option<QualityBadge> {
+        const LOSSLESS: [&str; 7] = ["FLAC", "ALAC", "WAV", "AIFF", "PCM", "APE", "WAVPACK"];
+        let codec = self
+            .codec
+            .as_deref()
+            .unwrap_or_default()
+            .to_ascii_uppercase();
+        if codec.contains("DSD") {
+            return Some(QualityBadge::Dsd);
+        }
+        if codec.contains("MQA") {
+            return Some(QualityBadge::Mqa);
+        }
+        if !LOSSLESS.iter().any(|name| codec.contains(name)) {
+            return None;
+        }
+        let hi_res = self.bit_depth.is_some_and(|bits| bits > 16)
+            || self.sample_rate.is_some_and(|hz| hz > 48_000);
+        Some(if hi_res {
+            QualityBadge::HiRes
+        } else {
+            QualityBadge::Lossless
+        })
+    }
+}
+
+/// Stream quality at a glance, shown next to the time in Now Playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QualityBadge {
+    Lossless,
+    HiRes,
+    Mqa,
+    Dsd,
+}
+
+impl QualityBadge {
+    pub fn label(self) -> &'static str {
+        match self {
+            QualityBadge::Lossless => "LOSSLESS",
+            QualityBadge::HiRes => "HI-RES",
+            QualityBadge::Mqa => "MQA",
+            QualityBadge::Dsd => "DSD",
+        }
+    }
+}
+
+/// Everything a refresh needs, from one `roon status` call
+#[derive(Debug, Clone)]
+pub struct Status {
+    pub zones: Vec<Zone>,
+    /// Queue of the active zone (only when requested with `--queue`)
+    pub queue: Option<Vec<QueueItem>>,
+    /// Zones and queue items that couldn't be parsed and were left out
+    pub warnings: Vec<String>,
+}
+
 /// Playback state enum
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+///
+/// States this version doesn't know (from a newer CLI) count as stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PlaybackState {
     Playing,
     Paused,
+    Loading,
     #[default]
+    #[serde(other)]
     Stopped,
-    Loading,
 }
 
-impl From<&str> for PlaybackState {
-    fn from(s: &str) -> Self {
-        match s {
-            "playing" => PlaybackState::Playing,
-            "paused" => PlaybackState::Paused,
-            "loading" => PlaybackState::Loading,
-            _ => PlaybackState::Stopped,
+impl PlaybackState {
+    /// The state as the CLI names it
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PlaybackState::Playing => "playing",
+            PlaybackState::Paused => "paused",
+            PlaybackState::Stopped => "stopped",
+            PlaybackState::Loading => "loading",
         }
     }
 }
@@ -0,0 +1,191 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use super::Timeout;
+
+/// Long-lived `roon session` process, when the CLI supports it
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Set once starting a session failed, so we don't retry on every command
+static UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+/// One reply line from the session
+#[derive(Debug, Deserialize)]
+struct Reply {
+    ok: bool,
+    #[serde(default)]
+    output: String,
+    #[serde(default)]
+    error: String,
+}
+
+/// How a command sent to the session went wrong (either way the session is
+/// broken and gets restarted)
+enum Broken {
+    /// Before the command was written: it can still run in its own process
+    Unsent(anyhow::Error),
+    /// After: the command may have run, so it mustn't run a second time
+    Sent(anyhow::Error),
+}
+
+impl Broken {
+    fn into_error(self) -> anyhow::Error {
+        match self {
+            Broken::Unsent(e) | Broken::Sent(e) => e,
+        }
+    }
+}
+
+/// A `roon session` child: commands go in as one JSON array of arguments per
+/// line, replies come back as one JSON object per line
+struct Session {
+    child: Child,
+    stdin: ChildStdin,
+    /// Reply lines, read on their own thread so a hung reply can time out
+    replies: Receiver<String>,
+}
+
+impl Session {
+    fn start() -> Result<Self> {
+        let mut child = super::command()
+            .arg("session")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().context("no stdin")?;
+        let stdout = BufReader::new(child.stdout.take().context("no stdout")?);
+        let (sender, replies) = mpsc::channel();
+        // Ends when the session exits (or is killed) and stdout closes
+        std::thread::spawn(move || {
+            for line in stdout.lines() {
+                let Ok(line) = line else { break };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let mut session = Self {
+            child,
+            stdin,
+            replies,
+        };
+
+        // An older CLI exits with a usage error here instead of replying
+        session.send(&["--version"]).map_err(Broken::into_error)??;
+        Ok(session)
+    }
+
+    /// Send one command; the outer error means the session itself broke
+    /// (or hung: a `Timeout`)
+    fn send(&mut self, args: &[&str]) -> Result<Result<String>, Broken> {
+        self.write(args).map_err(Broken::Unsent)?;
+        self.read(args).map_err(Broken::Sent)
+    }
+
+    fn write(&mut self, args: &[&str]) -> Result<()> {
+        writeln!(self.stdin, "{}", serde_json::to_string(args)?)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    fn read(&mut self, args: &[&str]) -> Result<Result<String>> {
+        let line = match super::timeout() {
+            Some(limit) => match self.replies.recv_timeout(limit) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Err(Timeout::new(args, limit).into()),
+                Err(RecvTimeoutError::Disconnected) => bail!("session closed"),
+            },
+            None => match self.replies.recv() {
+                Ok(line) => line,
+                Err(_) => bail!("session closed"),
+            },
+        };
+        let reply: Reply = serde_json::from_str(&line)?;
+        Ok(if reply.ok {
+            Ok(reply.output)
+        } else {
+            Err(anyhow::anyhow!(
+                "roon {:?} failed: {}",
+                args,
+                reply.error.trim()
+            ))
+        })
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        super::kill(&mut self.child);
+    }
+}
+
+/// End the running session, if any (the next command starts a new one)
+pub fn stop() {
+    if let Ok(mut slot) = SESSION.lock() {
+        *slot = None;
+    }
+}
+
+/// Never start a session (the CLI is known not to have one)
+pub fn disable() {
+    UNSUPPORTED.store(true, Ordering::Relaxed);
+}
+
+/// Run a command through the persistent session
+///
+/// Returns `None` when no session is available, so the caller spawns a
+/// process for this command instead. Once a command reached the session
+/// it's never run again: a session breaking after that is the command's
+/// error.
+pub fn run(args: &[&str]) -> Option<Result<String>> {
+    if UNSUPPORTED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let mut slot = SESSION.lock().ok()?;
+
+    if slot.is_none() {
+        match Session::start() {
+            Ok(session) => {
+                tracing::info!("Using persistent roon session");
+                *slot = Some(session);
+            }
+            // The CLI has sessions but hangs: spawning would hang as well
+            Err(e) if e.is::<Timeout>() => {
+                tracing::warn!("roon session hung on start, killed it");
+                return Some(Err(e));
+            }
+            Err(e) => {
+                tracing::info!("roon session unavailable ({}), spawning per command", e);
+                UNSUPPORTED.store(true, Ordering::Relaxed);
+                return None;
+            }
+        }
+    }
+
+    // On error the session is dropped (which kills and reaps the process),
+    // and the next command starts a fresh one
+    match slot.as_mut()?.send(args) {
+        Ok(result) => Some(result),
+        Err(Broken::Unsent(e)) => {
+            tracing::warn!("roon session failed: {}", e);
+            *slot = None;
+            None
+        }
+        Err(Broken::Sent(e)) => {
+            if e.is::<Timeout>() {
+                tracing::warn!("roon session hung on {:?}, killed it", args);
+            } else {
+                tracing::warn!("roon session failed during {:?}: {}", args, e);
+            }
+            *slot = None;
+            Some(Err(e))
+        }
+    }
+}
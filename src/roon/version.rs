@@ -0,0 +1,132 @@
+use std::fmt;
+use std::sync::atomic::Ordering;
+use std::sync::OnceLock;
+
+use anyhow::{bail, Result};
+
+/// Oldest roon-cli release roon-tui works with
+const MIN_VERSION: Version = Version(0, 2, 0);
+
+/// First release with `roon status` (zones and queue in one call)
+const STATUS_SINCE: Version = Version(0, 4, 0);
+
+/// First release with the persistent `roon session`
+const SESSION_SINCE: Version = Version(0, 5, 0);
+
+/// The CLI found by `detect`
+static DETECTED: OnceLock<Cli> = OnceLock::new();
+
+/// A `major.minor.patch` version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(pub u32, pub u32, pub u32);
+
+impl Version {
+    /// Parse "1.2.3", "v1.2" or "1.2.3-beta"
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.strip_prefix('v').unwrap_or(text);
+        let core = text.split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next()??;
+        let patch = parts.next().flatten().unwrap_or(0);
+        Some(Self(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+/// Which CLI answered `--version`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    /// github.com/EdgarPost/roon-cli
+    RoonCli,
+    /// Something else named `roon`; commands are probed as they're used
+    Unknown,
+}
+
+/// The detected roon CLI
+#[derive(Debug, Clone)]
+pub struct Cli {
+    pub flavor: Flavor,
+    pub version: Option<Version>,
+    /// First line of `roon --version`
+    pub line: String,
+}
+
+impl Cli {
+    fn parse(line: &str) -> Self {
+        let mut words = line.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let flavor = if matches!(name, "roon" | "roon-cli") {
+            Flavor::RoonCli
+        } else {
+            Flavor::Unknown
+        };
+        Self {
+            flavor,
+            version: words.find_map(Version::parse),
+            line: line.to_string(),
+        }
+    }
+
+    /// Version of a recognised roon-cli (`None` when it can't be relied on)
+    fn known_version(&self) -> Option<Version> {
+        self.version.filter(|_| self.flavor == Flavor::RoonCli)
+    }
+
+    /// Short description for the doctor and `--version`
+    pub fn summary(&self) -> String {
+        match (self.flavor, self.version) {
+            (Flavor::RoonCli, Some(version)) => format!("roon-cli {}", version),
+            _ => format!("unrecognized CLI ({:?})", self.line),
+        }
+    }
+}
+
+/// Run `roon --version` once and adapt to what that version supports
+///
+/// Commands a known older version doesn't have (`status`, `session`) are
+/// never tried; for an unrecognized CLI they're probed on first use as
+/// before. Returns `Ok(None)` when the CLI can't be run at all, and an
+/// error when it's too old to work with.
+pub fn detect() -> Result<Option<&'static Cli>> {
+    if let Some(cli) = DETECTED.get() {
+        return check(cli).map(Some);
+    }
+    // Spawned directly: a session must not be started before we know it exists
+    let Ok(output) = super::spawn_command(&["--version"]) else {
+        return Ok(None);
+    };
+    let cli = Cli::parse(output.lines().next().unwrap_or_default().trim());
+    match cli.known_version() {
+        Some(version) => {
+            tracing::info!("Detected {}", cli.summary());
+            if version < STATUS_SINCE {
+                super::STATUS_SUPPORTED.store(false, Ordering::Relaxed);
+            }
+            if version < SESSION_SINCE {
+                super::session::disable();
+            }
+        }
+        None => tracing::warn!("Unrecognized roon CLI {:?}, probing commands", cli.line),
+    }
+    let cli = DETECTED.get_or_init(|| cli);
+    check(cli).map(Some)
+}
+
+/// Refuse versions older than the minimum
+fn check(cli: &Cli) -> Result<&Cli> {
+    match cli.known_version() {
+        Some(version) if version < MIN_VERSION => bail!(
+            "roon-tui needs roon-cli {} or newer, found {}; update it from \
+             https://github.com/EdgarPost/roon-cli",
+            MIN_VERSION,
+            version
+        ),
+        _ => Ok(cli),
+    }
+}
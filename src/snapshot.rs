@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::roon::Zone;
+
+/// The zones as they were when roon-tui last quit, shown (marked stale)
+/// while the first refresh after startup is in flight
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZoneSnapshot {
+    /// Core the zones are from (None without configured Cores)
+    pub core: Option<String>,
+    /// Id of the zone selected then
+    pub selected: Option<String>,
+    pub zones: Vec<Zone>,
+}
+
+fn path() -> Option<PathBuf> {
+    crate::paths::state_dir().map(|dir| dir.join("zones.json"))
+}
+
+impl ZoneSnapshot {
+    /// Load the saved snapshot, if there is one
+    pub fn load() -> Option<Self> {
+        let contents = std::fs::read_to_string(path()?).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    pub fn save(&self) {
+        let Some(path) = path() else {
+            return;
+        };
+        let result = crate::paths::write_state(&path, self);
+        if let Err(e) = result {
+            tracing::warn!("Failed to save the zone snapshot: {}", e);
+        }
+    }
+}
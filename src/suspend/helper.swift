@@ -0,0 +1,69 @@
+// Sleep and screen lock bridge for roon-tui on macOS
+//
+// Prints "sleep", "wake", "lock" and "unlock", one per line. Before the
+// system sleeps it waits for a line on stdin (roon-tui answers once it has
+// paused playback, or after a few seconds at most) and only then lets the
+// sleep go ahead. Exits when stdin closes, i.e. when roon-tui does.
+
+import Foundation
+import IOKit.pwr_mgt
+
+setvbuf(stdout, nil, _IOLBF, 0)
+
+// iokit_common_msg(0x270), (0x280) and (0x300): the C macros don't reach Swift
+let canSystemSleep: UInt32 = 0xE000_0270
+let systemWillSleep: UInt32 = 0xE000_0280
+let systemHasPoweredOn: UInt32 = 0xE000_0300
+
+/// Longest roon-tui gets to pause before the sleep goes ahead anyway
+let pauseTimeout = 5.0
+
+let answers = DispatchSemaphore(value: 0)
+var rootPort: io_connect_t = 0
+
+func power(_: UnsafeMutableRawPointer?, _: io_service_t, message: UInt32, argument: UnsafeMutableRawPointer?) {
+    switch message {
+    case canSystemSleep:
+        IOAllowPowerChange(rootPort, Int(bitPattern: argument))
+    case systemWillSleep:
+        // An answer that came too late for the last sleep doesn't count
+        while answers.wait(timeout: .now()) == .success {}
+        print("sleep")
+        _ = answers.wait(timeout: .now() + pauseTimeout)
+        IOAllowPowerChange(rootPort, Int(bitPattern: argument))
+    case systemHasPoweredOn:
+        print("wake")
+    default:
+        break
+    }
+}
+
+var notifyPort: IONotificationPortRef?
+var notifier: io_object_t = 0
+rootPort = IORegisterForSystemPower(nil, &notifyPort, power, &notifier)
+guard rootPort != 0, let port = notifyPort else {
+    FileHandle.standardError.write("Couldn't register for sleep notifications\n".data(using: .utf8)!)
+    exit(1)
+}
+CFRunLoopAddSource(
+    CFRunLoopGetCurrent(),
+    IONotificationPortGetRunLoopSource(port).takeUnretainedValue(),
+    .defaultMode
+)
+
+let screen = DistributedNotificationCenter.default()
+screen.addObserver(forName: .init("com.apple.screenIsLocked"), object: nil, queue: nil) { _ in
+    print("lock")
+}
+screen.addObserver(forName: .init("com.apple.screenIsUnlocked"), object: nil, queue: nil) { _ in
+    print("unlock")
+}
+
+DispatchQueue.global().async {
+    while readLine() != nil {
+        answers.signal()
+    }
+    exit(0)
+}
+
+CFRunLoopRun()
@@ -0,0 +1,275 @@
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use tokio::sync::mpsc;
+
+/// Source of the macOS helper, compiled with `swiftc` on first use
+const HELPER_SOURCE: &str = include_str!("helper.swift");
+
+/// System power and screen lock transitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerEvent {
+    /// About to suspend
+    Sleep,
+    /// Resumed from suspend
+    Wake,
+    /// The screen locked
+    Lock,
+    /// The screen unlocked
+    Unlock,
+}
+
+impl PowerEvent {
+    /// A line from the macOS helper
+    fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "sleep" => Some(PowerEvent::Sleep),
+            "wake" => Some(PowerEvent::Wake),
+            "lock" => Some(PowerEvent::Lock),
+            "unlock" => Some(PowerEvent::Unlock),
+            _ => None,
+        }
+    }
+}
+
+/// Watches for system sleep and screen locks: logind's signals through
+/// `gdbus monitor` on Linux, IOKit and the screen lock notifications through
+/// a small Swift helper on macOS
+///
+/// Sleep can be held back until the watched zone is paused: on Linux with a
+/// logind delay inhibitor, released by `ready_to_sleep` and taken again by
+/// `woke`; on macOS the helper waits for `ready_to_sleep`. Processes are
+/// stopped on drop.
+pub struct SuspendMonitor {
+    backend: Backend,
+}
+
+enum Backend {
+    Logind {
+        monitor: Child,
+        /// `systemd-inhibit` holding the delay lock, while it's held
+        inhibitor: Option<Child>,
+        /// Whether sleep is held back at all
+        hold: bool,
+    },
+    /// The helper's stdin once it runs (it's built in the background on
+    /// first start)
+    Helper(Arc<Mutex<Option<ChildStdin>>>),
+}
+
+impl SuspendMonitor {
+    /// Start watching; with `hold`, sleep waits (a few seconds at most) for
+    /// `ready_to_sleep`
+    pub fn spawn(tx: mpsc::Sender<PowerEvent>, hold: bool) -> Result<Self> {
+        let backend = if cfg!(target_os = "macos") {
+            spawn_helper(tx)?
+        } else {
+            spawn_logind(tx, hold)?
+        };
+        Ok(Self { backend })
+    }
+
+    /// Let the system go to sleep (after handling a `Sleep`)
+    pub fn ready_to_sleep(&mut self) {
+        match &mut self.backend {
+            Backend::Logind { inhibitor, .. } => release(inhibitor.take()),
+            Backend::Helper(stdin) => {
+                let Ok(mut stdin) = stdin.lock() else {
+                    return;
+                };
+                if let Some(pipe) = stdin.as_mut() {
+                    if writeln!(pipe, "ready").and_then(|_| pipe.flush()).is_err() {
+                        tracing::warn!("Suspend helper exited");
+                        *stdin = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Hold back the next sleep again (after handling a `Wake`)
+    pub fn woke(&mut self) {
+        if let Backend::Logind {
+            inhibitor,
+            hold: true,
+            ..
+        } = &mut self.backend
+        {
+            if inhibitor.is_none() {
+                *inhibitor = inhibit();
+            }
+        }
+    }
+}
+
+impl Drop for SuspendMonitor {
+    fn drop(&mut self) {
+        // The helper exits by itself when its stdin closes
+        if let Backend::Logind {
+            monitor, inhibitor, ..
+        } = &mut self.backend
+        {
+            let _ = monitor.kill();
+            let _ = monitor.wait();
+            release(inhibitor.take());
+        }
+    }
+}
+
+/// Linux: logind's `PrepareForSleep`, and `Lock`/`Unlock` of this session
+///
+/// Other platforms without logind fail to start `gdbus monitor` or never
+/// see a signal, and the caller carries on without suspend handling.
+fn spawn_logind(tx: mpsc::Sender<PowerEvent>, hold: bool) -> Result<Backend> {
+    let mut monitor = Command::new("gdbus")
+        .args(["monitor", "--system", "--dest", "org.freedesktop.login1"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start gdbus")?;
+
+    let stdout = monitor.stdout.take().context("No stdout")?;
+    let session = session_path();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            let Some(event) = parse_logind(&line, session.as_deref()) else {
+                continue;
+            };
+            if tx.blocking_send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(Backend::Logind {
+        monitor,
+        inhibitor: if hold { inhibit() } else { None },
+        hold,
+    })
+}
+
+/// A `gdbus monitor` line about sleep, or about a lock of our session (of
+/// any session when we're not in one)
+fn parse_logind(line: &str, session: Option<&str>) -> Option<PowerEvent> {
+    // e.g. "/org/freedesktop/login1: org.freedesktop.login1.Manager.PrepareForSleep (true,)"
+    let (path, signal) = line.split_once(": ")?;
+    if let Some(args) = signal.strip_prefix("org.freedesktop.login1.Manager.PrepareForSleep") {
+        return Some(if args.contains("true") {
+            PowerEvent::Sleep
+        } else {
+            PowerEvent::Wake
+        });
+    }
+    // e.g. "/org/freedesktop/login1/session/_32: org.freedesktop.login1.Session.Lock ()"
+    if session.is_some_and(|session| session != path) {
+        return None;
+    }
+    match signal.split_whitespace().next()? {
+        "org.freedesktop.login1.Session.Lock" => Some(PowerEvent::Lock),
+        "org.freedesktop.login1.Session.Unlock" => Some(PowerEvent::Unlock),
+        _ => None,
+    }
+}
+
+/// logind's object path for this process's session, if it's in one
+///
+/// Session ids are escaped the way sd-bus does it: anything but letters
+/// (and digits after the first character) becomes `_` and its hex code.
+fn session_path() -> Option<String> {
+    let id = std::env::var("XDG_SESSION_ID")
+        .ok()
+        .filter(|id| !id.is_empty())?;
+    let escaped: String = id
+        .bytes()
+        .enumerate()
+        .map(|(i, b)| match b {
+            b if b.is_ascii_alphabetic() || (i > 0 && b.is_ascii_digit()) => {
+                char::from(b).to_string()
+            }
+            b => format!("_{:02x}", b),
+        })
+        .collect();
+    Some(format!("/org/freedesktop/login1/session/{}", escaped))
+}
+
+/// Take a logind delay lock on sleep, held until it's released
+///
+/// `systemd-inhibit` holds it while `cat` runs, and `cat` runs until its
+/// stdin closes.
+fn inhibit() -> Option<Child> {
+    let result = Command::new("systemd-inhibit")
+        .args([
+            "--what=sleep",
+            "--mode=delay",
+            "--who=roon-tui",
+            "--why=Pausing playback before suspend",
+            "cat",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    match result {
+        Ok(child) => Some(child),
+        Err(e) => {
+            tracing::warn!("Can't hold off suspend, systemd-inhibit failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Let go of a delay lock, so the pending sleep goes ahead
+fn release(inhibitor: Option<Child>) {
+    if let Some(mut child) = inhibitor {
+        drop(child.stdin.take());
+        let _ = child.wait();
+    }
+}
+
+/// macOS: the helper, built and started in the background
+fn spawn_helper(tx: mpsc::Sender<PowerEvent>) -> Result<Backend> {
+    let helper = crate::swift::helper_path("suspend").context("no cache directory")?;
+    let stdin = Arc::new(Mutex::new(None));
+    let shared = Arc::clone(&stdin);
+    std::thread::spawn(move || {
+        if let Err(e) = run_helper(&helper, &shared, &tx) {
+            tracing::warn!("Suspend monitor unavailable: {:#}", e);
+        }
+    });
+    Ok(Backend::Helper(stdin))
+}
+
+/// Build the helper if needed, start it and forward what it reports
+fn run_helper(
+    helper: &Path,
+    stdin: &Mutex<Option<ChildStdin>>,
+    tx: &mpsc::Sender<PowerEvent>,
+) -> Result<()> {
+    crate::swift::build_helper(helper, HELPER_SOURCE)?;
+    let mut child = Command::new(helper)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start the suspend helper")?;
+    let stdout = child.stdout.take().context("No stdout")?;
+    if let Ok(mut slot) = stdin.lock() {
+        *slot = child.stdin.take();
+    }
+
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        if let Some(event) = PowerEvent::parse(&line) {
+            if tx.blocking_send(event).is_err() {
+                break;
+            }
+        }
+    }
+    let _ = child.wait();
+    Ok(())
+}
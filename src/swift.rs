@@ -0,0 +1,39 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+/// Cached binary of a macOS helper, per roon-tui version so it's rebuilt on
+/// upgrade
+pub fn helper_path(name: &str) -> Option<PathBuf> {
+    crate::paths::cache_dir().map(|dir| dir.join(format!("{}-{}", name, env!("CARGO_PKG_VERSION"))))
+}
+
+/// Compile a helper from its Swift source with `swiftc` (from the Xcode
+/// command line tools), unless it's already built
+pub fn build_helper(helper: &Path, source: &str) -> Result<()> {
+    if helper.exists() {
+        return Ok(());
+    }
+    let dir = helper.parent().context("no cache directory")?;
+    std::fs::create_dir_all(dir)?;
+    let file_name = helper.file_name().context("no helper name")?;
+    let source_path = dir.join(format!("{}.swift", file_name.to_string_lossy()));
+    std::fs::write(&source_path, source)?;
+    tracing::info!("Building helper {}", helper.display());
+    let output = Command::new("swiftc")
+        .arg("-O")
+        .arg("-o")
+        .arg(helper)
+        .arg(&source_path)
+        .output()
+        .context("swiftc not found (install the Xcode command line tools)")?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(helper);
+        bail!(
+            "swiftc failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
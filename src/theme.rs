@@ -0,0 +1,313 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use ratatui::style::Color;
+
+use crate::colors::ColorDepth;
+
+/// UI color palette
+///
+/// Colors are semantic roles rather than literal colors, so a theme can
+/// restyle every view consistently.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: String,
+    /// Highlights, active tab, artist, progress bar
+    pub accent: Color,
+    /// Primary text
+    pub text: Color,
+    /// Secondary text and hints
+    pub muted: Color,
+    /// Breadcrumbs, section headers, zone name
+    pub heading: Color,
+    pub error: Color,
+    pub success: Color,
+    /// Popup and tab bar background
+    pub background: Color,
+    /// Status bar, progress track and selection backgrounds
+    pub surface: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            accent: Color::Cyan,
+            text: Color::White,
+            muted: Color::DarkGray,
+            heading: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+            background: Color::Black,
+            surface: Color::DarkGray,
+        }
+    }
+}
+
+/// Theme file contents: any color left out keeps its default
+///
+/// Colors are names ("cyan", "darkgray"), hex ("#88c0d0") or indexes ("208").
+#[derive(Debug, Default)]
+struct ThemeFile {
+    accent: Option<String>,
+    text: Option<String>,
+    muted: Option<String>,
+    heading: Option<String>,
+    error: Option<String>,
+    success: Option<String>,
+    background: Option<String>,
+    surface: Option<String>,
+}
+
+/// Which base16 slot fills each color role
+///
+/// base16 schemes describe editor colors; these are the closest matches
+/// (e.g. base03 is for comments, base0D for functions).
+const BASE16_ROLES: [(&str, &str); 8] = [
+    ("accent", "base0D"),
+    ("text", "base05"),
+    ("muted", "base03"),
+    ("heading", "base0A"),
+    ("error", "base08"),
+    ("success", "base0B"),
+    ("background", "base00"),
+    ("surface", "base02"),
+];
+
+impl ThemeFile {
+    /// Parse a theme file: our own role colors in TOML, or a base16 scheme in
+    /// YAML (or TOML, with `base00`…`base0F` keys)
+    ///
+    /// Role colors win over base16 slots, so a scheme can be tweaked in place.
+    fn parse(path: &Path, contents: &str) -> anyhow::Result<Self> {
+        let is_yaml = path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml");
+        let mut entries: BTreeMap<String, String> = if is_yaml {
+            parse_yaml(contents)
+        } else {
+            toml::from_str::<toml::Table>(contents)?
+                .into_iter()
+                .filter_map(|(key, value)| Some((key, value.as_str()?.to_string())))
+                .collect()
+        };
+
+        for (role, slot) in BASE16_ROLES {
+            if entries.contains_key(role) {
+                continue;
+            }
+            if let Some(hex) = entries
+                .get(slot)
+                .or_else(|| entries.get(&slot.to_lowercase()))
+            {
+                let color = if hex.starts_with('#') {
+                    hex.clone()
+                } else {
+                    format!("#{}", hex)
+                };
+                entries.insert(role.to_string(), color);
+            }
+        }
+
+        let mut take = |role: &str| entries.remove(role);
+        Ok(Self {
+            accent: take("accent"),
+            text: take("text"),
+            muted: take("muted"),
+            heading: take("heading"),
+            error: take("error"),
+            success: take("success"),
+            background: take("background"),
+            surface: take("surface"),
+        })
+    }
+}
+
+/// Flat `key: value` pairs of a YAML file (all a base16 scheme needs, also
+/// for the newer nested `palette:` layout)
+fn parse_yaml(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            // Drop trailing comments ("base00: "282828" # ----")
+            let value = value.split(" #").next().unwrap_or_default().trim();
+            let value = value.trim_matches(|c| c == '"' || c == '\'');
+            (!value.is_empty()).then(|| (key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+impl Theme {
+    fn from_file(name: &str, file: ThemeFile) -> Self {
+        let mut theme = Self {
+            name: name.to_string(),
+            ..Self::default()
+        };
+        let slots = [
+            (&mut theme.accent, file.accent),
+            (&mut theme.text, file.text),
+            (&mut theme.muted, file.muted),
+            (&mut theme.heading, file.heading),
+            (&mut theme.error, file.error),
+            (&mut theme.success, file.success),
+            (&mut theme.background, file.background),
+            (&mut theme.surface, file.surface),
+        ];
+        for (slot, value) in slots {
+            if let Some(value) = value {
+                match Color::from_str(&value) {
+                    Ok(color) => *slot = color,
+                    Err(_) => tracing::warn!("Invalid color {:?} in theme {}", value, name),
+                }
+            }
+        }
+        theme
+    }
+
+    /// A color by its role name ("accent", "muted", ...), for colors named
+    /// outside the theme
+    pub fn role(&self, name: &str) -> Option<Color> {
+        match name {
+            "accent" => Some(self.accent),
+            "text" => Some(self.text),
+            "muted" => Some(self.muted),
+            "heading" => Some(self.heading),
+            "error" => Some(self.error),
+            "success" => Some(self.success),
+            _ => None,
+        }
+    }
+
+    /// This theme with every color reduced to what the terminal can show
+    pub fn fit(mut self, depth: ColorDepth) -> Self {
+        let roles = [
+            &mut self.accent,
+            &mut self.text,
+            &mut self.muted,
+            &mut self.heading,
+            &mut self.error,
+            &mut self.success,
+            &mut self.background,
+            &mut self.surface,
+        ];
+        for color in roles {
+            *color = depth.fit(*color);
+        }
+        // Dark backgrounds and surfaces often both become black; keep
+        // selections and the progress track visible
+        if depth != ColorDepth::TrueColor && self.surface == self.background {
+            self.surface = if self.background == Color::Black {
+                Color::DarkGray
+            } else {
+                Color::Black
+            };
+        }
+        self
+    }
+
+    /// Directory user themes are loaded from (`<config dir>/roon-tui/themes`)
+    pub fn dir() -> Option<PathBuf> {
+        crate::paths::config_dir().map(|dir| dir.join("themes"))
+    }
+
+    /// All installed themes: built-ins followed by `*.toml` and base16
+    /// `*.yaml` files in the themes dir
+    pub fn installed() -> Vec<Theme> {
+        let mut themes = builtin();
+
+        let entries = Self::dir().and_then(|dir| std::fs::read_dir(dir).ok());
+        let mut files: Vec<PathBuf> = entries
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "toml" || ext == "yaml" || ext == "yml")
+            })
+            .collect();
+        files.sort();
+
+        for path in files {
+            let Some(name) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| ThemeFile::parse(&path, &contents));
+            match parsed {
+                Ok(file) => {
+                    // A user file with a built-in's name replaces it
+                    themes.retain(|t| t.name != name);
+                    themes.push(Theme::from_file(&name, file));
+                }
+                Err(e) => tracing::warn!("Invalid theme file {}: {}", path.display(), e),
+            }
+        }
+        themes
+    }
+
+    /// Look up an installed theme by name, falling back to the default
+    pub fn named(name: &str) -> Theme {
+        Self::installed()
+            .into_iter()
+            .find(|t| t.name == name)
+            .unwrap_or_else(|| {
+                tracing::warn!("Unknown theme {:?}, using default", name);
+                Theme::default()
+            })
+    }
+}
+
+/// Themes shipped with the binary
+fn builtin() -> Vec<Theme> {
+    let rgb = |hex: u32| Color::from_u32(hex);
+    vec![
+        Theme::default(),
+        Theme {
+            name: "gruvbox".to_string(),
+            accent: rgb(0x83a598),
+            text: rgb(0xebdbb2),
+            muted: rgb(0x928374),
+            heading: rgb(0xfabd2f),
+            error: rgb(0xfb4934),
+            success: rgb(0xb8bb26),
+            background: rgb(0x282828),
+            surface: rgb(0x3c3836),
+        },
+        Theme {
+            name: "nord".to_string(),
+            accent: rgb(0x88c0d0),
+            text: rgb(0xeceff4),
+            muted: rgb(0x616e88),
+            heading: rgb(0xebcb8b),
+            error: rgb(0xbf616a),
+            success: rgb(0xa3be8c),
+            background: rgb(0x2e3440),
+            surface: rgb(0x3b4252),
+        },
+        Theme {
+            name: "catppuccin".to_string(),
+            accent: rgb(0x89b4fa),
+            text: rgb(0xcdd6f4),
+            muted: rgb(0x6c7086),
+            heading: rgb(0xf9e2af),
+            error: rgb(0xf38ba8),
+            success: rgb(0xa6e3a1),
+            background: rgb(0x1e1e2e),
+            surface: rgb(0x313244),
+        },
+        Theme {
+            name: "mono".to_string(),
+            accent: Color::White,
+            text: Color::Gray,
+            muted: Color::DarkGray,
+            heading: Color::White,
+            error: Color::White,
+            success: Color::Gray,
+            background: Color::Black,
+            surface: Color::DarkGray,
+        },
+    ]
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::roon::{Output, Volume, VolumeType};
+
+/// Where an output's volume sits in its range, in percent (None for fixed
+/// and incremental outputs, which have no absolute level)
+fn position(volume: Option<&Volume>) -> Option<f64> {
+    let volume = volume?;
+    let range = volume.max - volume.min;
+    (volume.kind != VolumeType::Incremental && range > 0.0)
+        .then(|| (volume.value - volume.min) / range * 100.0)
+}
+
+/// Key of a group: its output names, sorted, so the same outputs grouped
+/// again in another order match
+pub fn group_key(outputs: &[Output]) -> String {
+    let mut names: Vec<&str> = outputs.iter().map(|o| o.display_name.as_str()).collect();
+    names.sort_unstable();
+    names.join(" + ")
+}
+
+/// The output trims are relative to: the first one by name that has a
+/// volume level
+pub fn reference(outputs: &[Output]) -> Option<usize> {
+    (0..outputs.len())
+        .filter(|&i| position(outputs[i].volume.as_ref()).is_some())
+        .min_by(|&a, &b| outputs[a].display_name.cmp(&outputs[b].display_name))
+}
+
+/// Each output's trim: how far above or below the reference output it sits,
+/// in percent of its range
+///
+/// Percent of range is what linked (group) volume changes keep, so trims
+/// stay put while the whole group gets louder or quieter.
+pub fn offsets(outputs: &[Output]) -> Vec<Option<f64>> {
+    let base = reference(outputs).and_then(|i| position(outputs[i].volume.as_ref()));
+    outputs
+        .iter()
+        .map(|output| Some(position(output.volume.as_ref())? - base?))
+        .collect()
+}
+
+/// A trim for display ("+4%", "-2%", "±0%")
+pub fn display(offset: f64) -> String {
+    if offset.abs() < 0.5 {
+        "±0%".to_string()
+    } else {
+        format!("{:+.0}%", offset)
+    }
+}
+
+/// Preferred trims of grouped outputs, persisted as one JSON file and
+/// reapplied when the same outputs are grouped again
+pub struct Trims {
+    path: Option<PathBuf>,
+    /// Group key -> output name -> trim
+    groups: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl Trims {
+    /// Load the saved trims (none if there's no file yet)
+    pub fn load() -> Self {
+        let path = crate::paths::state_dir().map(|dir| dir.join("trims.json"));
+        let groups = path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        Self { path, groups }
+    }
+
+    /// Remember a group's current trims
+    pub fn remember(&mut self, outputs: &[Output]) {
+        let trims: BTreeMap<String, f64> = outputs
+            .iter()
+            .zip(offsets(outputs))
+            .filter_map(|(output, offset)| Some((output.display_name.clone(), offset?)))
+            .collect();
+        if trims.len() < 2 {
+            return;
+        }
+        let key = group_key(outputs);
+        if self.groups.get(&key) != Some(&trims) {
+            self.groups.insert(key, trims);
+            self.save();
+        }
+    }
+
+    /// Relative volume changes that bring a group back to its saved trims
+    /// (empty when none are saved or they already match)
+    pub fn reapply(&self, outputs: &[Output]) -> Vec<(String, f64)> {
+        let Some(saved) = self.groups.get(&group_key(outputs)) else {
+            return Vec::new();
+        };
+        let Some(base) = reference(outputs).and_then(|i| position(outputs[i].volume.as_ref()))
+        else {
+            return Vec::new();
+        };
+        outputs
+            .iter()
+            .filter_map(|output| {
+                let volume = output.volume.as_ref()?;
+                let current = position(Some(volume))?;
+                let target = (base + saved.get(&output.display_name)?).clamp(0.0, 100.0);
+                let delta = (target - current) * (volume.max - volume.min) / 100.0;
+                let delta = match volume.step {
+                    Some(step) if step > 0.0 => (delta / step).round() * step,
+                    _ => delta.round(),
+                };
+                (delta != 0.0).then(|| (output.display_name.clone(), delta))
+            })
+            .collect()
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = crate::paths::write_state(path, &self.groups);
+        if let Err(e) = result {
+            tracing::warn!("Failed to save volume trims: {}", e);
+        }
+    }
+}
@@ -1,39 +1,63 @@
 use ratatui::{
     prelude::*,
-    widgets::{List, ListItem, ListState, Paragraph},
+    widgets::{List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use crate::app::BrowseState;
+use crate::roon::NowPlaying;
+use crate::theme::Theme;
 
-/// Draw the browse view
-pub fn draw(frame: &mut Frame, area: Rect, state: &BrowseState) {
+/// Draw the browse view, marking the items that are what's playing
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    state: &BrowseState,
+    theme: &Theme,
+    playing: Option<&NowPlaying>,
+    hints: Option<&str>,
+) {
+    let hints_height = u16::from(hints.is_some());
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Breadcrumbs
-            Constraint::Min(0),    // List
-            Constraint::Length(1), // Hints
+            Constraint::Length(1),            // Breadcrumbs
+            Constraint::Min(0),               // List
+            Constraint::Length(hints_height), // Hints
         ])
         .split(area);
 
     // Breadcrumbs
     let crumbs = state.breadcrumbs.join(" > ");
     let breadcrumb_line = Paragraph::new(crumbs)
-        .style(Style::default().fg(Color::Yellow));
+        .style(Style::default().fg(theme.heading));
     frame.render_widget(breadcrumb_line, chunks[0]);
 
-    // Error or loading state
+    // Error or loading state, the error with the keys that recover from it
     if let Some(err) = &state.error {
-        let error = Paragraph::new(err.as_str())
-            .style(Style::default().fg(Color::Red))
-            .alignment(Alignment::Center);
+        let key = |key: &'static str| Span::styled(key, Style::default().fg(theme.accent));
+        let muted = |text: &'static str| Span::styled(text, Style::default().fg(theme.muted));
+        let lines = vec![
+            Line::styled(err.as_str(), Style::default().fg(theme.error)),
+            Line::from(""),
+            Line::from(vec![
+                key("r"),
+                muted(" retry  "),
+                key("b"),
+                muted(" back to root  "),
+                key("d"),
+                muted(" open debug log"),
+            ]),
+        ];
+        let error = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
         frame.render_widget(error, chunks[1]);
         return;
     }
 
     if state.loading {
         let loading = Paragraph::new("Loading...")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(loading, chunks[1]);
         return;
@@ -41,7 +65,7 @@ pub fn draw(frame: &mut Frame, area: Rect, state: &BrowseState) {
 
     if state.items.is_empty() {
         let empty = Paragraph::new("No items")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(empty, chunks[1]);
     } else {
@@ -53,19 +77,27 @@ pub fn draw(frame: &mut Frame, area: Rect, state: &BrowseState) {
                 let indicator = match item.hint.as_deref() {
                     Some("list") => "> ",
                     Some("action_list") => "▶ ",
+                    Some("show_more") => "+ ",
                     _ => "  ",
                 };
 
-                let mut spans = vec![
-                    Span::styled(indicator, Style::default().fg(Color::DarkGray)),
-                    Span::styled(&item.title, Style::default().fg(Color::White)),
-                ];
+                let mut spans = if playing.is_some_and(|np| np.is_item(item)) {
+                    vec![
+                        Span::styled("♪ ", Style::default().fg(theme.accent)),
+                        Span::styled(&item.title, Style::default().fg(theme.accent)),
+                    ]
+                } else {
+                    vec![
+                        Span::styled(indicator, Style::default().fg(theme.muted)),
+                        Span::styled(&item.title, Style::default().fg(theme.text)),
+                    ]
+                };
 
                 if let Some(subtitle) = &item.subtitle {
                     spans.push(Span::raw("  "));
                     spans.push(Span::styled(
                         subtitle.as_str(),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(theme.muted),
                     ));
                 }
 
@@ -76,7 +108,7 @@ pub fn draw(frame: &mut Frame, area: Rect, state: &BrowseState) {
         let list = List::new(items)
             .highlight_style(
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol("▸ ");
@@ -88,8 +120,10 @@ pub fn draw(frame: &mut Frame, area: Rect, state: &BrowseState) {
     }
 
     // Hints
-    let hints = Paragraph::new("j/k navigate  Enter select  Esc back")
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    frame.render_widget(hints, chunks[2]);
+    if let Some(hints) = hints {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[2]);
+    }
 }
@@ -0,0 +1,39 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+
+/// Draw the "quit while playing?" confirmation popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Quit? ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let playing: Vec<&str> = app
+        .zones
+        .iter()
+        .filter(|zone| zone.is_playing())
+        .map(|zone| app.zone_label(zone))
+        .collect();
+
+    let lines = vec![
+        Line::from(""),
+        Line::styled(
+            format!("Still playing in {}.", playing.join(", ")),
+            Style::default().fg(theme.text),
+        ),
+        Line::from(""),
+        Line::styled("y quit  n stay", Style::default().fg(theme.muted)),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+
+use super::hints;
+
+/// Draw the Core switcher popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Roon Core ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let hints = hints::text(app, hints::CORES);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                             // Cores
+            Constraint::Length(u16::from(hints.is_some())), // Hints
+        ])
+        .split(inner);
+
+    let current = app.current_core().map(|core| core.name.as_str());
+    let items: Vec<ListItem> = app
+        .config
+        .cores
+        .iter()
+        .enumerate()
+        .map(|(i, core)| {
+            let prefix = if Some(core.name.as_str()) == current {
+                "● "
+            } else {
+                "○ "
+            };
+            let style = if i == app.core_index {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let mut spans = vec![Span::styled(prefix, style), Span::styled(&core.name, style)];
+            // The zone the switch comes back to
+            if let Some(zone) = app.cores.zone(&core.name) {
+                spans.push(Span::styled(
+                    format!("  {}", zone),
+                    Style::default().fg(theme.muted),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("");
+
+    let mut state = ListState::default();
+    state.select(Some(app.core_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    if let Some(hints) = hints {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[1]);
+    }
+}
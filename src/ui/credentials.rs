@@ -0,0 +1,82 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::credentials::{Service, Store};
+
+use super::hints;
+
+/// Draw the integration tokens popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Credentials ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Services
+            Constraint::Length(1), // Token input / message
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem> = Service::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, service)| {
+            let (status, color) = match app.credentials.stored.get(i).copied().flatten() {
+                Some(Store::Keyring) => ("keyring", theme.success),
+                Some(Store::Plaintext) => ("credentials.toml", theme.heading),
+                None => ("not set", theme.muted),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:24}", service.label()),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(status, Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.credentials.selected_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    // The token itself is never shown, only how much has been typed
+    let status = if let Some(input) = &app.credentials.input {
+        Paragraph::new(format!(
+            "Token: {}▏ (Enter save, Esc cancel)",
+            "•".repeat(input.chars().count().min(32))
+        ))
+        .style(Style::default().fg(theme.accent))
+    } else {
+        let message = app.credentials.message.as_deref().unwrap_or_default();
+        Paragraph::new(message).style(Style::default().fg(theme.heading))
+    };
+    frame.render_widget(status, chunks[1]);
+
+    if let Some(hints) = hints::text(app, hints::CREDENTIALS) {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[2]);
+    }
+}
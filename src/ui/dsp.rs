@@ -0,0 +1,84 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::App;
+
+use super::hints;
+
+/// Draw the DSP preset picker
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" DSP Preset ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let hints = hints::text(app, hints::DSP);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),                             // Presets
+            Constraint::Length(u16::from(hints.is_some())), // Hints
+        ])
+        .split(inner);
+
+    let empty = match &app.dsp_error {
+        Some(error) => Some((format!("Couldn't get DSP presets: {}", error), theme.error)),
+        None if app.dsp_presets.is_empty() => {
+            Some(("This zone has no DSP presets".to_string(), theme.muted))
+        }
+        None => None,
+    };
+    if let Some((text, color)) = empty {
+        let paragraph = Paragraph::new(text)
+            .style(Style::default().fg(color))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(paragraph, chunks[0]);
+    } else {
+        let items: Vec<ListItem> = app
+            .dsp_presets
+            .iter()
+            .enumerate()
+            .map(|(i, preset)| {
+                let prefix = if preset.active { "● " } else { "○ " };
+                let style = if i == app.dsp_index {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(&preset.name, style),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(theme.surface)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("");
+
+        let mut state = ListState::default();
+        state.select(Some(app.dsp_index));
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+    }
+
+    if let Some(hints) = hints {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[1]);
+    }
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::{App, FocusField};
+
+use super::hints;
+
+/// Draw the Focus filter builder
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let focus = &app.focus;
+    let block = Block::default()
+        .title(" Focus ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Filters
+            Constraint::Length(1), // Summary / message
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem> = FocusField::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let editing = focus.input.as_ref().filter(|_| i == focus.selected_index);
+            let value = match editing {
+                Some(input) => {
+                    Span::styled(format!("{}▏", input), Style::default().fg(theme.accent))
+                }
+                None if focus.values[i].is_empty() => {
+                    Span::styled(field.placeholder(), Style::default().fg(theme.muted))
+                }
+                None => Span::styled(focus.values[i].as_str(), Style::default().fg(theme.text)),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:12}", field.label()),
+                    Style::default().fg(theme.heading),
+                ),
+                value,
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut state = ListState::default();
+    state.select(Some(focus.selected_index));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    // What applying would filter by, or why it can't
+    let status = match (focus.message.clone(), focus.query()) {
+        (Some(message), _) | (None, Err(message)) => {
+            Paragraph::new(message).style(Style::default().fg(theme.error))
+        }
+        (None, Ok(query)) if query.is_empty() => {
+            Paragraph::new("All albums").style(Style::default().fg(theme.muted))
+        }
+        (None, Ok(query)) => {
+            Paragraph::new(query.summary()).style(Style::default().fg(theme.accent))
+        }
+    };
+    frame.render_widget(status, chunks[1]);
+
+    if let Some(hints) = hints::text(app, hints::FOCUS) {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[2]);
+    }
+}
@@ -3,30 +3,73 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
 };
 
+use crate::app::{App, HelpInput, HelpPage};
 use crate::input::help_text;
 
+use super::hints;
+
 /// Draw the help popup
-pub fn draw(frame: &mut Frame, area: Rect) {
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let page = match app.help_page {
+        HelpPage::View => "1/2 This view",
+        HelpPage::Global => "2/2 Global",
+    };
     let block = Block::default()
-        .title(" Help - Keybindings ")
+        .title(format!(" Help - Keybindings ({}) ", page))
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
+    let filtering = app.help_input == HelpInput::Filter || !app.help_filter.is_empty();
+    let status = if app.help_input == HelpInput::Lookup {
+        Some(Line::styled(
+            "Press a key to see what it does",
+            Style::default().fg(theme.heading),
+        ))
+    } else if filtering {
+        let cursor = if app.help_input == HelpInput::Filter {
+            "█"
+        } else {
+            ""
+        };
+        Some(Line::styled(
+            format!("Filter: {}{}", app.help_filter, cursor),
+            Style::default().fg(theme.accent),
+        ))
+    } else {
+        app.help_lookup
+            .as_deref()
+            .map(|lookup| Line::styled(lookup, Style::default().fg(theme.heading)))
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(u16::from(status.is_some()) * 2), // Filter / lookup
+            Constraint::Min(0),                                  // Bindings
+            Constraint::Length(1),                               // Hints
+        ])
+        .split(inner);
+
+    if let Some(status) = status {
+        frame.render_widget(Paragraph::new(status), chunks[0]);
+    }
+
     // Build help text
-    let bindings = help_text();
+    let bindings = help_text(app);
     let lines: Vec<Line> = bindings
-        .iter()
+        .into_iter()
         .map(|(key, desc)| {
             if desc.is_empty() {
                 // Section header
                 Line::from(Span::styled(
-                    *key,
+                    key,
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(theme.heading)
                         .add_modifier(Modifier::BOLD),
                 ))
             } else {
@@ -34,10 +77,10 @@ pub fn draw(frame: &mut Frame, area: Rect) {
                     Span::styled(
                         format!("{:12}", key),
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme.accent)
                             .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(*desc, Style::default().fg(Color::White)),
+                    Span::styled(desc, Style::default().fg(theme.text)),
                 ])
             }
         })
@@ -45,23 +88,22 @@ pub fn draw(frame: &mut Frame, area: Rect) {
 
     let paragraph = Paragraph::new(lines)
         .style(Style::default())
-        .alignment(Alignment::Left);
+        .alignment(Alignment::Left)
+        .scroll((app.help_scroll, 0));
 
-    frame.render_widget(paragraph, inner);
+    frame.render_widget(paragraph, chunks[1]);
 
-    // Close hint at bottom
-    let close_hint = Line::from(Span::styled(
-        "Press Esc or ? to close",
-        Style::default().fg(Color::DarkGray),
-    ));
-    let hint_area = Rect {
-        x: area.x + 2,
-        y: area.y + area.height - 2,
-        width: area.width - 4,
-        height: 1,
+    let help_hints = if app.help_input == HelpInput::Filter {
+        hints::HELP_FILTER
+    } else {
+        hints::HELP
     };
-    frame.render_widget(
-        Paragraph::new(close_hint).alignment(Alignment::Center),
-        hint_area,
-    );
+    if let Some(hints) = hints::text(app, help_hints) {
+        frame.render_widget(
+            Paragraph::new(hints)
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            chunks[2],
+        );
+    }
 }
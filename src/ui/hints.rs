@@ -0,0 +1,134 @@
+use crate::app::App;
+use crate::input::Action;
+
+/// The key half of a footer hint
+pub enum HintKey {
+    /// Fixed navigation key(s), e.g. "j/k"
+    Fixed(&'static str),
+    /// A rebindable action, shown with its current keys
+    Action(Action),
+}
+
+use HintKey::Fixed;
+
+/// Browse and search results
+pub const BROWSE: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "navigate"),
+    (Fixed("Enter"), "select"),
+    (Fixed("i"), "details"),
+    (Fixed("r"), "similar"),
+    (Fixed("f"), "focus"),
+    (Fixed("o"), "sort"),
+    (Fixed("Esc"), "back"),
+    (Fixed("Ctrl+r"), "refresh"),
+];
+
+pub const CORES: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("Enter"), "connect"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const DSP: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("Enter"), "switch"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const CREDENTIALS: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("Enter"), "set token"),
+    (Fixed("d"), "remove"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const FOCUS: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("Enter"), "edit"),
+    (Fixed("d"), "clear"),
+    (Fixed("a"), "apply"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const HELP: &[(HintKey, &str)] = &[
+    (Fixed("Tab"), "switch page"),
+    (Fixed("j/k"), "scroll"),
+    (Fixed("/"), "filter"),
+    (Fixed("r"), "what's this key"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const HELP_FILTER: &[(HintKey, &str)] = &[(Fixed("Enter"), "done"), (Fixed("Esc"), "clear")];
+
+pub const ZONES: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("J/K"), "move"),
+    (Fixed("Enter"), "choose"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const QUEUE: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "navigate"),
+    (Fixed("i"), "details"),
+    (Fixed("D"), "dedupe"),
+    (Fixed("Esc"), "back"),
+];
+
+pub const FOR_YOU: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "navigate"),
+    (Fixed("Enter"), "play"),
+    (Fixed("a"), "queue"),
+    (Fixed("Ctrl+r"), "refresh"),
+    (Fixed("Esc"), "back"),
+];
+
+pub const SETTINGS: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "navigate"),
+    (Fixed("Enter"), "rebind"),
+    (Fixed("Bksp"), "reset to default"),
+    (Fixed("c"), "credentials"),
+    (Fixed("Esc"), "back"),
+];
+
+pub const THEMES: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "preview"),
+    (Fixed("Enter"), "keep"),
+    (Fixed("Esc"), "cancel"),
+];
+
+pub const VOLUME: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("+/-"), "volume"),
+    (Fixed("m"), "mute"),
+    (Fixed("g"), "link/unlink"),
+    (Fixed("t"), "trims"),
+    (Fixed("Esc"), "close"),
+];
+
+pub const TRIM: &[(HintKey, &str)] = &[
+    (Fixed("j/k"), "select"),
+    (Fixed("h/l"), "trim"),
+    (Fixed("Esc"), "close"),
+];
+
+/// Footer text ("j/k navigate  Enter select"), or None when hints are hidden
+/// (by config or zen mode)
+///
+/// Actions without any key bound are left out.
+pub fn text(app: &App, hints: &[(HintKey, &str)]) -> Option<String> {
+    if !app.config.show_hints || app.zen_mode {
+        return None;
+    }
+    let text = hints
+        .iter()
+        .filter_map(|(key, label)| {
+            let key = match key {
+                HintKey::Fixed(key) => key.to_string(),
+                HintKey::Action(action) => app.keymap.keys_display(action),
+            };
+            (!key.is_empty()).then(|| format!("{} {}", key, label))
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    Some(text)
+}
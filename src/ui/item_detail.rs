@@ -0,0 +1,105 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use ratatui_image::StatefulImage;
+
+use crate::app::App;
+
+/// Draw the browse item detail popup: artwork on the left, metadata and
+/// actions on the right
+pub fn draw(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme.clone();
+    let block = Block::default()
+        .title(" Details ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let Some(detail) = &mut app.item_detail else {
+        return;
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Artwork and metadata
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    // Cells are about twice as tall as wide, so a square cover is 2:1
+    let art_width = if detail.art.is_some() {
+        (rows[0].height * 2).min(rows[0].width / 2)
+    } else {
+        0
+    };
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(art_width), Constraint::Min(0)])
+        .horizontal_margin(1)
+        .spacing(2)
+        .split(rows[0]);
+    if let Some(art) = &mut detail.art {
+        frame.render_stateful_widget(StatefulImage::new(), columns[0], art);
+    }
+
+    let item = &detail.item;
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<9}", label), Style::default().fg(theme.muted)),
+            Span::styled(value, Style::default().fg(theme.text)),
+        ])
+    };
+    let mut lines = vec![
+        Line::from(""),
+        Line::styled(
+            item.title.clone(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Line::from(""),
+    ];
+    if let Some(subtitle) = item.subtitle.as_deref().filter(|s| !s.is_empty()) {
+        lines.push(field("Subtitle", subtitle.to_string()));
+    }
+    let kind = match item.hint.as_deref() {
+        Some("list") => "List",
+        Some("action_list") => "Playable (actions below)",
+        Some("action") => "Action",
+        Some("header") => "Header",
+        _ => "Item",
+    };
+    lines.push(field("Type", kind.to_string()));
+    if let Some(count) = detail.count {
+        lines.push(field("Entries", count.to_string()));
+    }
+    if !detail.actions.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::styled("Actions", Style::default().fg(theme.heading)));
+        for action in &detail.actions {
+            lines.push(Line::styled(
+                format!("  {}", action),
+                Style::default().fg(theme.text),
+            ));
+        }
+    }
+    if let Some(error) = &detail.error {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            error.clone(),
+            Style::default().fg(theme.error),
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), columns[1]);
+
+    frame.render_widget(
+        Paragraph::new("Esc close")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center),
+        rows[1],
+    );
+}
@@ -0,0 +1,45 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+
+/// Draw the lyrics popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let title = match &app.lyrics {
+        Some(lyrics) => format!(" Lyrics ({}) ", lyrics.source),
+        None => " Lyrics ".to_string(),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let paragraph = match &app.lyrics {
+        Some(lyrics) => Paragraph::new(lyrics.text.as_str())
+            .style(Style::default().fg(theme.text))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .scroll((app.lyrics_scroll, 0)),
+        None => {
+            let message = if app.lyrics_loading {
+                "Loading..."
+            } else if app.lyrics_key.is_none() {
+                "No track playing"
+            } else {
+                "No lyrics found"
+            };
+            Paragraph::new(message)
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center)
+        }
+    };
+
+    frame.render_widget(paragraph, inner);
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::metrics;
+
+/// Draw the performance metrics overlay in the top-right corner
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let label =
+        |text: &str| Span::styled(format!("{:<14}", text), Style::default().fg(theme.muted));
+    let value = |text: String| Span::styled(text, Style::default().fg(theme.text));
+
+    let mut lines = vec![
+        Line::from(vec![
+            label("Frame"),
+            value(format!(
+                "{} (max {})",
+                format_ms(app.metrics.frame_time),
+                format_ms(app.metrics.frame_max)
+            )),
+        ]),
+        Line::from(vec![
+            label("Loop rate"),
+            value(format!("{:.1}/s", app.metrics.loop_rate)),
+        ]),
+        Line::from(""),
+        Line::styled(
+            "Command       p50 / p95 / max",
+            Style::default().fg(theme.heading),
+        ),
+    ];
+
+    let commands = metrics::command_latencies();
+    if commands.is_empty() {
+        lines.push(Line::styled("none yet", Style::default().fg(theme.muted)));
+    }
+    for (name, latency) in commands {
+        lines.push(Line::from(vec![
+            label(&name),
+            value(format!(
+                "{} / {} / {}",
+                format_ms(latency.percentile(50)),
+                format_ms(latency.percentile(95)),
+                format_ms(latency.max)
+            )),
+        ]));
+    }
+
+    let width = 44.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let overlay = Rect {
+        x: area.x + area.width - width,
+        y: area.y + 1,
+        width,
+        height: height.min(area.height.saturating_sub(1)),
+    };
+
+    let block = Block::default()
+        .title(" Metrics ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    frame.render_widget(Clear, overlay);
+    frame.render_widget(Paragraph::new(lines).block(block), overlay);
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
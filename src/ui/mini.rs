@@ -0,0 +1,61 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Gauge, Paragraph},
+};
+
+use crate::app::App;
+use crate::input::Action;
+
+use super::hints::{self, HintKey};
+
+/// Draw the compact mini-player: track, progress and transport hints
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Track
+            Constraint::Length(1), // Progress
+            Constraint::Length(1), // Hints
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let (title, artist, _) = app.track_info();
+    let track = Line::from(vec![
+        Span::styled(
+            format!("{} ", app.playback_icon()),
+            Style::default().fg(theme.accent),
+        ),
+        Span::styled(
+            title,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" — ", Style::default().fg(theme.muted)),
+        Span::styled(artist, Style::default().fg(theme.accent)),
+    ]);
+    frame.render_widget(Paragraph::new(track), chunks[0]);
+
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(theme.accent).bg(theme.surface))
+        .ratio(app.progress_ratio())
+        .label(Span::styled(
+            app.progress_display(),
+            Style::default().fg(theme.text),
+        ))
+        .use_unicode(true);
+    frame.render_widget(gauge, chunks[1]);
+
+    let hints = [
+        (HintKey::Action(Action::PlayPause), "play/pause"),
+        (HintKey::Action(Action::PrevTrack), "prev"),
+        (HintKey::Action(Action::NextTrack), "next"),
+        (HintKey::Action(Action::ToggleMiniPlayer), "full view"),
+    ];
+    if let Some(hints) = hints::text(app, &hints) {
+        frame.render_widget(
+            Paragraph::new(hints).style(Style::default().fg(theme.muted)),
+            chunks[2],
+        );
+    }
+}
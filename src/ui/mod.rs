@@ -1,57 +1,160 @@
 mod browse;
+mod confirm_quit;
+mod cores;
+mod credentials;
+mod dsp;
+mod focus;
 mod help;
+mod hints;
+mod item_detail;
+mod lyrics;
+mod metrics;
+mod mini;
 mod now_playing;
+mod onboarding;
+mod open_in;
+mod outputs;
+mod party;
+mod queue;
+mod resume;
+mod screensaver;
 mod search;
+mod settings;
+mod stats;
+mod themes;
+mod trim;
+mod volume;
+mod which_key;
 mod zones;
 
 use ratatui::{prelude::*, widgets::Paragraph};
 
 use crate::app::{App, Popup, View};
+use crate::input::Action;
+
+/// Narrowest content width that still fits the split layout
+const SPLIT_MIN_WIDTH: u16 = 100;
 
 /// Main draw function - renders the entire UI
 pub fn draw(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
 
-    // Create main layout: tab bar + content area + status bar
+    if app.screensaver.is_some() {
+        screensaver::draw(frame, area, app);
+        return;
+    }
+
+    if app.fullscreen_art {
+        now_playing::draw_fullscreen(frame, area, app);
+        return;
+    }
+
+    if app.mini_player {
+        mini::draw(frame, area, app);
+        if let Some(popup) = app.popup {
+            draw_popup(frame, area, popup, app);
+        }
+        if let Some(menu) = app.leader_menu {
+            which_key::draw(frame, area, menu, app);
+        }
+        return;
+    }
+
+    // Create main layout: tab bar + content area + status bar (no bars in zen mode)
+    let bar_height = u16::from(!app.zen_mode);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Tab bar
-            Constraint::Min(0),    // Content
-            Constraint::Length(1), // Status bar
+            Constraint::Length(bar_height), // Tab bar
+            Constraint::Min(0),             // Content
+            Constraint::Length(bar_height), // Status bar
         ])
         .split(area);
 
     // Draw tab bar
-    draw_tab_bar(frame, chunks[0], app);
+    if !app.zen_mode {
+        draw_tab_bar(frame, chunks[0], app);
+    }
+
+    // Split layout: list on the left, compact Now Playing on the right
+    let mut content = chunks[1];
+    let split = app.split_view
+        && matches!(app.view, View::Browse | View::Search)
+        && content.width >= SPLIT_MIN_WIDTH;
+    if split {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(content);
+        content = halves[0];
+        now_playing::draw_compact(frame, halves[1], app);
+    }
 
     // Draw content based on active view
+    let playing = app.current_zone().and_then(|z| z.now_playing.as_ref());
     match app.view {
         View::NowPlaying => now_playing::draw(frame, chunks[1], app),
-        View::Browse => browse::draw(frame, chunks[1], &app.browse),
-        View::Search => search::draw(frame, chunks[1], &app.search),
+        View::Browse => {
+            let hints = hints::text(app, hints::BROWSE);
+            let state = &app.browse;
+            browse::draw(frame, content, state, &app.theme, playing, hints.as_deref())
+        }
+        View::Search => {
+            let hints = hints::text(app, hints::BROWSE);
+            let state = &app.search;
+            search::draw(frame, content, state, &app.theme, playing, hints.as_deref())
+        }
+        View::Queue => queue::draw(frame, chunks[1], app),
+        View::Settings => settings::draw(frame, chunks[1], app),
+        View::ForYou => {
+            let hints = hints::text(app, hints::FOR_YOU);
+            let list = &app.for_you.list;
+            browse::draw(frame, content, list, &app.theme, playing, hints.as_deref())
+        }
     }
 
     // Draw status bar
-    draw_status_bar(frame, chunks[2], app);
+    if !app.zen_mode {
+        draw_status_bar(frame, chunks[2], app);
+    }
 
     // Draw popup if any
-    if let Some(popup) = &app.popup {
+    if let Some(popup) = app.popup {
         draw_popup(frame, area, popup, app);
     }
+
+    // Leader menu hints
+    if let Some(menu) = app.leader_menu {
+        which_key::draw(frame, area, menu, app);
+    }
+
+    // Debug overlay stays on top of everything
+    if app.show_metrics {
+        metrics::draw(frame, area, app);
+    }
 }
 
 /// Draw the tab bar at the top
 fn draw_tab_bar(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let active_style = Style::default()
-        .fg(Color::Cyan)
+        .fg(theme.accent)
         .add_modifier(Modifier::BOLD);
-    let inactive_style = Style::default().fg(Color::DarkGray);
+    let inactive_style = Style::default().fg(theme.muted);
 
-    let tabs = vec![
-        ("1", "Now Playing", View::NowPlaying),
-        ("2", "Browse", View::Browse),
-        ("3", "Search", View::Search),
+    // Queue tab shows how many tracks are left, e.g. "Queue (12)"
+    let queue_label = match app.queue_items_remaining() {
+        Some(count) => format!("Queue ({})", count),
+        None => "Queue".to_string(),
+    };
+
+    let tabs = [
+        ("1", "Now Playing".to_string(), View::NowPlaying),
+        ("2", "Browse".to_string(), View::Browse),
+        ("3", "Search".to_string(), View::Search),
+        ("4", queue_label, View::Queue),
+        ("5", "Settings".to_string(), View::Settings),
+        ("6", "For You".to_string(), View::ForYou),
     ];
 
     let spans: Vec<Span> = tabs
@@ -70,30 +173,94 @@ fn draw_tab_bar(frame: &mut Frame, area: Rect, app: &App) {
         .collect();
 
     let tab_line = Paragraph::new(Line::from(spans))
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
     frame.render_widget(tab_line, area);
 }
 
 /// Draw the status bar at the bottom
 fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
-    let connection_status = if app.connected {
-        Span::styled("● Connected", Style::default().fg(Color::Green))
+    let theme = &app.theme;
+    let connection_status = if app.stale && app.error.is_none() {
+        Span::styled("◌ Last known state", Style::default().fg(theme.muted))
+    } else if let (true, Some(core)) = (app.connected, app.current_core()) {
+        Span::styled(
+            format!("● Connected to {}", core.name),
+            Style::default().fg(theme.success),
+        )
+    } else if app.connected {
+        Span::styled("● Connected", Style::default().fg(theme.success))
     } else {
-        Span::styled("○ Disconnected", Style::default().fg(Color::Red))
+        Span::styled("○ Disconnected", Style::default().fg(theme.error))
     };
 
-    let zone_name = Span::styled(
-        format!(" │ Zone: {}", app.current_zone_name()),
-        Style::default().fg(Color::Yellow),
-    );
-
-    let help_hint = Span::styled(
-        " │ Press ? for help",
-        Style::default().fg(Color::DarkGray),
-    );
+    let follow = if app.follow { " (following)" } else { "" };
+    let album_shuffle = if app.album_shuffle {
+        " (album shuffle)"
+    } else {
+        ""
+    };
+    let zone_name = match &app.zone_gone {
+        Some(gone) => Span::styled(
+            format!(" │ Zone: {} (gone, z to pick another)", gone.name),
+            Style::default().fg(theme.error),
+        ),
+        None => Span::styled(
+            format!(
+                " │ Zone: {}{}{}",
+                app.current_zone_name(),
+                follow,
+                album_shuffle
+            ),
+            Style::default().fg(theme.heading),
+        ),
+    };
 
-    let left = Line::from(vec![connection_status, zone_name]);
-    let right = Line::from(vec![help_hint]);
+    let mut left = Line::from(vec![connection_status, zone_name]);
+    if app.party.on {
+        left.spans.push(Span::styled(
+            " │ Party mode",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(ends_at) = app.ends_at_display() {
+        left.spans.push(Span::raw(format!(" │ {}", ends_at)));
+    }
+    if let Some((notice, _)) = &app.notice {
+        left.spans.push(Span::styled(
+            format!(" │ {}", notice),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    let mut right = Line::default();
+    for widget in app.plugins.widgets() {
+        right.spans.push(Span::raw(" │ "));
+        for segment in widget {
+            let color = segment.color.as_deref().and_then(|role| theme.role(role));
+            let mut style = Style::default().fg(color.unwrap_or(theme.text));
+            if segment.bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            right.spans.push(Span::styled(segment.text.as_str(), style));
+        }
+    }
+    let help = [(hints::HintKey::Action(Action::ShowHelp), "for help")];
+    if let Some(help) = hints::text(app, &help) {
+        right.spans.push(Span::styled(
+            format!(" │ Press {}", help),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    if let Some(latest) = &app.update_available {
+        right.spans.insert(
+            0,
+            Span::styled(
+                format!(" │ {} available", latest),
+                Style::default().fg(theme.accent),
+            ),
+        );
+    }
 
     // Split status bar into left and right
     let chunks = Layout::default()
@@ -102,28 +269,54 @@ fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     frame.render_widget(
-        Paragraph::new(left).style(Style::default().bg(Color::DarkGray).fg(Color::White)),
+        Paragraph::new(left).style(Style::default().bg(theme.surface).fg(theme.text)),
         chunks[0],
     );
     frame.render_widget(
         Paragraph::new(right)
-            .style(Style::default().bg(Color::DarkGray).fg(Color::White))
+            .style(Style::default().bg(theme.surface).fg(theme.text))
             .alignment(Alignment::Right),
         chunks[1],
     );
 }
 
 /// Draw a popup overlay
-fn draw_popup(frame: &mut Frame, area: Rect, popup: &Popup, app: &App) {
+fn draw_popup(frame: &mut Frame, area: Rect, popup: Popup, app: &mut App) {
     // Create centered popup area
-    let popup_area = centered_rect(60, 60, area);
+    let popup_area = match popup {
+        Popup::ConfirmQuit | Popup::Resume | Popup::PartyUnlock => centered_rect(40, 20, area),
+        Popup::QueueItem | Popup::CoreSwitcher | Popup::DspPresets | Popup::ZoneGone => {
+            centered_rect(50, 40, area)
+        }
+        // Room for a year of heatmap columns
+        Popup::Stats => centered_rect(80, 80, area),
+        _ => centered_rect(60, 60, area),
+    };
 
     // Clear the popup area
     frame.render_widget(ratatui::widgets::Clear, popup_area);
 
     match popup {
-        Popup::Help => help::draw(frame, popup_area),
+        Popup::Help => help::draw(frame, popup_area, app),
         Popup::ZoneSelector => zones::draw_selector(frame, popup_area, app),
+        Popup::ZoneGone => zones::draw_gone(frame, popup_area, app),
+        Popup::CoreSwitcher => cores::draw(frame, popup_area, app),
+        Popup::Trim => trim::draw(frame, popup_area, app),
+        Popup::DspPresets => dsp::draw(frame, popup_area, app),
+        Popup::Outputs => outputs::draw(frame, popup_area, app),
+        Popup::PartyUnlock => party::draw(frame, popup_area, app),
+        Popup::OpenIn => open_in::draw(frame, popup_area, app),
+        Popup::Lyrics => lyrics::draw(frame, popup_area, app),
+        Popup::ItemDetail => item_detail::draw(frame, popup_area, app),
+        Popup::QueueItem => queue::draw_item(frame, popup_area, app),
+        Popup::Volume => volume::draw(frame, popup_area, app),
+        Popup::ThemeSwitcher => themes::draw(frame, popup_area, app),
+        Popup::Onboarding => onboarding::draw(frame, popup_area, app),
+        Popup::ConfirmQuit => confirm_quit::draw(frame, popup_area, app),
+        Popup::Resume => resume::draw(frame, popup_area, app),
+        Popup::Credentials => credentials::draw(frame, popup_area, app),
+        Popup::Focus => focus::draw(frame, popup_area, app),
+        Popup::Stats => stats::draw(frame, popup_area, app),
     }
 }
 
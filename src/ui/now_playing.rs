@@ -1,3 +1,5 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use ratatui::{
     prelude::*,
     widgets::{Gauge, Paragraph},
@@ -5,11 +7,31 @@ use ratatui::{
 use ratatui_image::StatefulImage;
 
 use crate::app::App;
+use crate::config::ProgressStyle;
+use crate::roon::QualityBadge;
+use crate::theme::Theme;
+use crate::visualizer::MAX_LEVEL;
 
 /// Draw the Now Playing view - centered layout
 pub fn draw(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme.clone();
+    // Optional classical metadata lines (composer above title, movement below)
+    let composer = app.composer().map(str::to_string);
+    let (title, subtitle) = app.title_lines();
+    let (title, subtitle) = (title.to_string(), subtitle.map(str::to_string));
+    let composer_height = u16::from(composer.is_some());
+    let subtitle_height = u16::from(subtitle.is_some());
+    let release_info = app.release_info();
+    let release_height = u16::from(release_info.is_some());
+    let visualizer_height = if app.visualizer_visible() {
+        app.config.visualizer.height
+    } else {
+        0
+    };
+
     // Calculate content height: art(20) + spacing(1) + title(1) + artist(1) + album(1) + spacing(1) + time(1) + progress(1) + status(1) + volume(1) = 29
-    let content_height = 29u16;
+    let content_height =
+        29u16 + composer_height + subtitle_height + release_height + visualizer_height;
     let content_width = 50u16;
 
     // Center vertically
@@ -27,17 +49,21 @@ pub fn draw(frame: &mut Frame, area: Rect, app: &mut App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(20), // Album art (larger)
-            Constraint::Length(1),  // Spacing
-            Constraint::Length(1),  // Title
-            Constraint::Length(1),  // Artist
-            Constraint::Length(1),  // Album
-            Constraint::Length(1),  // Spacing
-            Constraint::Length(1),  // Time display
-            Constraint::Length(1),  // Progress bar
-            Constraint::Length(1),  // Playback status icons
-            Constraint::Length(1),  // Volume display
-            Constraint::Min(0),     // Remaining space
+            Constraint::Length(20),                // Album art (larger)
+            Constraint::Length(1),                 // Spacing
+            Constraint::Length(composer_height),   // Composer
+            Constraint::Length(1),                 // Title
+            Constraint::Length(subtitle_height),   // Movement / subtitle
+            Constraint::Length(1),                 // Artist
+            Constraint::Length(1),                 // Album
+            Constraint::Length(release_height),    // Year · label · genres
+            Constraint::Length(1),                 // Spacing
+            Constraint::Length(1),                 // Time display
+            Constraint::Length(1),                 // Progress bar
+            Constraint::Length(visualizer_height), // Spectrum bars
+            Constraint::Length(1),                 // Playback status icons
+            Constraint::Length(1),                 // Volume display
+            Constraint::Min(0),                    // Remaining space
         ])
         .split(centered_area);
 
@@ -45,45 +71,97 @@ pub fn draw(frame: &mut Frame, area: Rect, app: &mut App) {
     draw_album_art(frame, chunks[0], app);
 
     // Track info
-    let (title, artist, album) = app.track_info();
+    let (_, artist, album) = app.track_info();
+
+    // Composer (yellow, classical only)
+    if let Some(composer) = &composer {
+        let composer_text = Paragraph::new(composer.as_str())
+            .style(Style::default().fg(theme.heading))
+            .alignment(Alignment::Center);
+        frame.render_widget(composer_text, chunks[2]);
+    }
 
     // Title (bold, white)
     let title_text = Paragraph::new(title)
-        .style(
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        )
+        .style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
-    frame.render_widget(title_text, chunks[2]);
+    frame.render_widget(title_text, chunks[3]);
+
+    // Movement / subtitle (white, classical only)
+    if let Some(subtitle) = subtitle {
+        let subtitle_text = Paragraph::new(subtitle)
+            .style(Style::default().fg(theme.text))
+            .alignment(Alignment::Center);
+        frame.render_widget(subtitle_text, chunks[4]);
+    }
 
     // Artist (cyan)
     let artist_text = Paragraph::new(artist)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(theme.accent))
         .alignment(Alignment::Center);
-    frame.render_widget(artist_text, chunks[3]);
+    frame.render_widget(artist_text, chunks[5]);
 
     // Album (gray)
     let album_text = Paragraph::new(album)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
-    frame.render_widget(album_text, chunks[4]);
+    frame.render_widget(album_text, chunks[6]);
+
+    // Release info (gray, when metadata is available)
+    if let Some(release_info) = release_info {
+        let release_text = Paragraph::new(release_info)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(release_text, chunks[7]);
+    }
+
+    // Countdown to the next track, in the spacing line
+    if let Some(left) = app.countdown() {
+        let mut spans = vec![Span::styled(
+            format!("Next in {}", left.ceil() as u64),
+            Style::default().fg(theme.accent),
+        )];
+        if let Some(next) = app.next_queue_item() {
+            let name = if next.artist.is_empty() {
+                next.track.clone()
+            } else {
+                format!("{} — {}", next.track, next.artist)
+            };
+            spans.push(Span::styled(
+                format!(" · {}", name),
+                Style::default().fg(theme.muted),
+            ));
+        }
+        let banner = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+        frame.render_widget(banner, chunks[8]);
+    }
 
     // Time display (above progress bar)
     let progress_display = app.progress_display();
-    let time_text = Paragraph::new(progress_display)
-        .style(Style::default().fg(Color::DarkGray))
-        .alignment(Alignment::Center);
-    frame.render_widget(time_text, chunks[6]);
+    let time_style = if app.is_loading() {
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.muted)
+    };
+    let mut time_line = vec![Span::styled(progress_display, time_style)];
+    if let Some(badge) = app.quality_badge() {
+        time_line.push(Span::raw("  "));
+        time_line.push(quality_badge(badge, &theme));
+    }
+    let time_text = Paragraph::new(Line::from(time_line)).alignment(Alignment::Center);
+    frame.render_widget(time_text, chunks[9]);
+    app.hit_areas.time = chunks[9];
 
     // Progress bar (thin, no label)
-    let progress = app.progress_ratio();
-    let gauge = Gauge::default()
-        .gauge_style(Style::default().fg(Color::Cyan).bg(Color::DarkGray))
-        .ratio(progress)
-        .label("")
-        .use_unicode(true);
-    frame.render_widget(gauge, chunks[7]);
+    draw_progress_bar(frame, chunks[10], app);
+    app.hit_areas.progress = chunks[10];
+
+    // Spectrum bars (local zones only)
+    if visualizer_height > 0 {
+        draw_spectrum(frame, chunks[11], &app.spectrum, theme.accent);
+    }
 
     // Playback status icons (shuffle, loop, radio)
     let status_line = format!(
@@ -94,19 +172,253 @@ pub fn draw(frame: &mut Frame, area: Rect, app: &mut App) {
         app.radio_icon()
     );
     let status_text = Paragraph::new(status_line)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
+        .alignment(Alignment::Center);
+    frame.render_widget(status_text, chunks[12]);
+
+    // Volume display, with the leveling gain when leveling is on and the
+    // active DSP preset
+    let mut volume_line = app.volume_display();
+    if let Some(leveling) = app.leveling_display() {
+        volume_line = format!("{}  ·  {}", volume_line, leveling);
+    }
+    if let Some(dsp) = app.dsp_display() {
+        volume_line = format!("{}  ·  {}", volume_line, dsp);
+    }
+    let volume_text = Paragraph::new(volume_line)
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
-    frame.render_widget(status_text, chunks[8]);
+    frame.render_widget(volume_text, chunks[13]);
+    app.hit_areas.volume = chunks[13];
+}
+
+/// Draw visualizer bars with eighth-block characters, spread over the width
+fn draw_spectrum(frame: &mut Frame, area: Rect, bars: &[u8], color: Color) {
+    const BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if bars.is_empty() || area.height == 0 {
+        return;
+    }
+
+    let bar_width = (area.width as usize / bars.len()).max(1);
+    let eighths = usize::from(area.height) * 8;
+    let lines: Vec<Line> = (0..usize::from(area.height))
+        .map(|row| {
+            // Eighths already covered by the rows below this one
+            let below = (usize::from(area.height) - 1 - row) * 8;
+            let text: String = bars
+                .iter()
+                .flat_map(|&level| {
+                    let fill = usize::from(level.min(MAX_LEVEL)) * eighths / usize::from(MAX_LEVEL);
+                    let block = BLOCKS[fill.saturating_sub(below).min(8)];
+                    // Leave a gap between bars when there's room for one
+                    let gap = usize::from(bar_width > 1);
+                    std::iter::repeat_n(block, bar_width - gap).chain(std::iter::repeat_n(' ', gap))
+                })
+                .collect();
+            Line::from(text)
+        })
+        .collect();
 
-    // Volume display
-    let volume_text = Paragraph::new(app.volume_display())
-        .style(Style::default().fg(Color::DarkGray))
+    let spectrum = Paragraph::new(lines)
+        .style(Style::default().fg(color))
         .alignment(Alignment::Center);
-    frame.render_widget(volume_text, chunks[9]);
+    frame.render_widget(spectrum, area);
+}
+
+/// Draw the compact Now Playing panel shown beside Browse/Search in split view
+pub fn draw_compact(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme.clone();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(0),    // Album art
+            Constraint::Length(1), // Spacing
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Artist
+            Constraint::Length(1), // Spacing
+            Constraint::Length(1), // Time display
+            Constraint::Length(1), // Progress bar
+        ])
+        .split(area);
+
+    draw_album_art(frame, chunks[0], app);
+
+    let (title, artist, _) = app.track_info();
+    frame.render_widget(
+        Paragraph::new(title)
+            .style(Style::default().fg(theme.text).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center),
+        chunks[2],
+    );
+    frame.render_widget(
+        Paragraph::new(artist)
+            .style(Style::default().fg(theme.accent))
+            .alignment(Alignment::Center),
+        chunks[3],
+    );
+    frame.render_widget(
+        Paragraph::new(app.progress_display())
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center),
+        chunks[5],
+    );
+    draw_progress_bar(frame, chunks[6], app);
+}
+
+/// Draw the cover over the whole terminal with a thin title/progress overlay
+pub fn draw_fullscreen(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme.clone();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Album art
+            Constraint::Length(1), // Title · artist
+            Constraint::Length(1), // Progress bar
+        ])
+        .split(area);
+
+    draw_album_art(frame, chunks[0], app);
+
+    let (title, artist, _) = app.track_info();
+    let line = Line::from(vec![
+        Span::styled(
+            title,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" · ", Style::default().fg(theme.muted)),
+        Span::styled(artist, Style::default().fg(theme.accent)),
+        Span::styled(
+            format!("  {}", app.progress_display()),
+            Style::default().fg(theme.muted),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), chunks[1]);
+
+    draw_progress_bar(frame, chunks[2], app);
+}
+
+/// The quality badge, colored by the theme: lossless in the success color,
+/// hi-res in the accent, MQA and DSD in the heading and text colors
+fn quality_badge(badge: QualityBadge, theme: &Theme) -> Span<'static> {
+    let color = match badge {
+        QualityBadge::Lossless => theme.success,
+        QualityBadge::HiRes => theme.accent,
+        QualityBadge::Mqa => theme.heading,
+        QualityBadge::Dsd => theme.text,
+    };
+    Span::styled(
+        format!(" {} ", badge.label()),
+        Style::default()
+            .fg(theme.background)
+            .bg(color)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Draw the thin progress bar (live streams have no length, so no bar)
+fn draw_progress_bar(frame: &mut Frame, area: Rect, app: &App) {
+    if app.is_loading() {
+        frame.render_widget(Paragraph::new(loading_bar(area.width, app)), area);
+        return;
+    }
+    if app.is_live() {
+        return;
+    }
+    let theme = &app.theme;
+    if app.config.progress_bar == ProgressStyle::Braille {
+        frame.render_widget(Paragraph::new(braille_bar(area.width, app)), area);
+        return;
+    }
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(theme.accent).bg(theme.surface))
+        .ratio(app.progress_ratio())
+        .label("")
+        .use_unicode(true);
+    frame.render_widget(gauge, area);
+}
+
+/// Indeterminate bar while the zone is loading: a short segment sweeping
+/// back and forth, in the configured bar style
+fn loading_bar(width: u16, app: &App) -> Line<'static> {
+    // One sweep there and back
+    const PERIOD_MS: u128 = 2000;
+    let theme = &app.theme;
+    let width = width as usize;
+    let segment = (width / 6).max(1);
+    let travel = width.saturating_sub(segment);
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let step = (millis % PERIOD_MS * travel as u128 * 2 / PERIOD_MS) as usize;
+    let offset = if step > travel {
+        2 * travel - step
+    } else {
+        step
+    };
+
+    let (cell, fill, track) = match app.config.progress_bar {
+        ProgressStyle::Braille => (
+            char::from_u32(0x2836).unwrap_or('-').to_string(),
+            Style::default().fg(theme.accent),
+            Style::default().fg(theme.muted),
+        ),
+        ProgressStyle::Gauge => (
+            " ".to_string(),
+            Style::default().bg(theme.accent),
+            Style::default().bg(theme.surface),
+        ),
+    };
+    Line::from(vec![
+        Span::styled(cell.repeat(offset), track),
+        Span::styled(cell.repeat(segment.min(width)), fill),
+        Span::styled(cell.repeat(width.saturating_sub(offset + segment)), track),
+    ])
+}
+
+/// Progress as a line of Braille dots, two per cell, with a full-height
+/// playhead dot column splitting elapsed from remaining
+fn braille_bar(width: u16, app: &App) -> Line<'static> {
+    // Middle dot rows of the left and right columns, and full columns
+    const LINE_LEFT: u32 = 0x06;
+    const LINE_RIGHT: u32 = 0x30;
+    const HEAD_LEFT: u32 = 0x47;
+    const HEAD_RIGHT: u32 = 0xB8;
+    let glyph = |dots: u32| char::from_u32(0x2800 + dots).unwrap_or(' ');
+
+    let theme = &app.theme;
+    let width = width as usize;
+    let halves = width * 2;
+    let head = ((app.progress_ratio() * halves as f64) as usize).min(halves.saturating_sub(1));
+    let head_cell = head / 2;
+
+    let line = glyph(LINE_LEFT | LINE_RIGHT);
+    let playhead = if head.is_multiple_of(2) {
+        glyph(HEAD_LEFT | LINE_RIGHT)
+    } else {
+        glyph(LINE_LEFT | HEAD_RIGHT)
+    };
+    Line::from(vec![
+        Span::styled(
+            line.to_string().repeat(head_cell),
+            Style::default().fg(theme.accent),
+        ),
+        Span::styled(
+            playhead.to_string(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            line.to_string().repeat(width.saturating_sub(head_cell + 1)),
+            Style::default().fg(theme.muted),
+        ),
+    ])
 }
 
 /// Draw album art centered
-fn draw_album_art(frame: &mut Frame, area: Rect, app: &mut App) {
+pub(super) fn draw_album_art(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = &app.theme;
     // Center the album art block
     let art_size = area.height.min(area.width);
     let art_x = area.x + (area.width.saturating_sub(art_size)) / 2;
@@ -118,17 +430,16 @@ fn draw_album_art(frame: &mut Frame, area: Rect, app: &mut App) {
         height: area.height,
     };
 
-    // Try to render album art if image and picker are available
-    if let (Some(image), Some(picker)) = (&app.album_art, &mut app.image_picker) {
-        let mut protocol = picker.new_resize_protocol(image.clone());
+    // Render the cached album art protocol if an image is loaded
+    if let Some(protocol) = &mut app.album_art {
         let stateful_image = StatefulImage::new();
-        frame.render_stateful_widget(stateful_image, art_area, &mut protocol);
+        frame.render_stateful_widget(stateful_image, art_area, protocol);
         return;
     }
 
     // Show placeholder if no image
     let placeholder = Paragraph::new("♪ ♫ ♪")
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     frame.render_widget(placeholder, art_area);
 }
@@ -0,0 +1,165 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::{App, OnboardingStep};
+use crate::input::Action;
+
+/// Draw the first-run onboarding popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let (number, title) = match app.onboarding.step {
+        OnboardingStep::Connection => (1, "Connection"),
+        OnboardingStep::Zone => (2, "Default zone"),
+        OnboardingStep::Keys => (3, "Keybindings"),
+    };
+    let block = Block::default()
+        .title(format!(" Welcome to roon-tui — {}/3 {} ", number, title))
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Step content
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    let hints = match app.onboarding.step {
+        OnboardingStep::Connection => "r retry  Enter continue  Esc skip",
+        OnboardingStep::Zone => "j/k select  Enter choose  Esc skip",
+        OnboardingStep::Keys => "Enter finish",
+    };
+
+    match app.onboarding.step {
+        OnboardingStep::Connection => draw_connection(frame, chunks[0], app),
+        OnboardingStep::Zone => draw_zones(frame, chunks[0], app),
+        OnboardingStep::Keys => draw_keys(frame, chunks[0], app),
+    }
+
+    frame.render_widget(
+        Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+/// Step 1: roon CLI and Core reachability
+fn draw_connection(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let check = |ok: bool, label: &str| {
+        let (icon, color) = if ok {
+            ("✓ ", theme.success)
+        } else {
+            ("✗ ", theme.error)
+        };
+        Line::from(vec![
+            Span::styled(icon, Style::default().fg(color)),
+            Span::styled(label.to_string(), Style::default().fg(theme.text)),
+        ])
+    };
+
+    let mut lines = vec![
+        Line::from(""),
+        check(app.onboarding.cli_found, "roon CLI found on PATH"),
+        check(app.connected, "Roon Core reachable"),
+        Line::from(""),
+    ];
+
+    if !app.onboarding.cli_found {
+        lines.push(Line::from(
+            "Install roon-cli (github.com/EdgarPost/roon-cli) and make sure `roon` is on your PATH.",
+        ));
+    } else if !app.connected {
+        lines.push(Line::from(
+            "Start the daemon with `roon daemon`, then enable the extension in Roon → Settings → Extensions.",
+        ));
+        if let Some(err) = &app.error {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(err.as_str(), Style::default().fg(theme.error)));
+        }
+    } else {
+        lines.push(Line::from(format!("Found {} zones.", app.zones.len())));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(theme.muted))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+/// Step 2: startup zone picker
+fn draw_zones(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    if app.zones.is_empty() {
+        let empty = Paragraph::new("No zones available — you can pick one later with z")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .zones
+        .iter()
+        .map(|zone| ListItem::new(Span::styled(app.zone_label(zone), theme.text)))
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.zone_selector_index));
+
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Step 3: cheat card of the most used keys
+fn draw_keys(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let essentials = [
+        (Action::PlayPause, "Play / Pause"),
+        (Action::NextTrack, "Next track"),
+        (Action::PrevTrack, "Previous track"),
+        (Action::VolumeUp, "Volume up"),
+        (Action::VolumeDown, "Volume down"),
+        (Action::ShowZoneSelector, "Select zone"),
+        (Action::SwitchToBrowse, "Browse library"),
+        (Action::SwitchToSearch, "Search library"),
+        (Action::ShowHelp, "All keybindings"),
+        (Action::Quit, "Quit"),
+    ];
+
+    let mut lines = vec![Line::from("")];
+    lines.extend(essentials.iter().map(|(action, description)| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:12}", app.keymap.keys_display(action)),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(*description, Style::default().fg(theme.text)),
+        ])
+    }));
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "Keys can be changed in Settings (5).",
+        Style::default().fg(theme.muted),
+    ));
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
@@ -0,0 +1,68 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+
+/// Draw the "Open in…" service picker popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Open in… ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let has_track = app
+        .current_zone()
+        .map(|z| z.now_playing.is_some())
+        .unwrap_or(false);
+
+    if !has_track || app.config.open_in.is_empty() {
+        let message = if has_track {
+            "No services configured"
+        } else {
+            "No track playing"
+        };
+        let empty = Paragraph::new(vec![Line::from(""), Line::from(message).centered()])
+            .style(Style::default().fg(theme.muted));
+
+        frame.render_widget(empty, inner);
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .config
+        .open_in
+        .iter()
+        .enumerate()
+        .map(|(i, service)| {
+            let style = if i == app.open_in_index {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+
+            ListItem::new(Line::from(Span::styled(&service.name, style)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.open_in_index));
+
+    frame.render_stateful_widget(list, inner, &mut state);
+}
@@ -0,0 +1,90 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+
+/// Draw the outputs popup: the device behind each output of the current
+/// zone, how it's reached and the format it's fed
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Outputs ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let outputs = app
+        .current_zone()
+        .map(|z| z.outputs.as_slice())
+        .unwrap_or_default();
+    let label = |text: &'static str| {
+        Span::styled(format!("  {:10}", text), Style::default().fg(theme.muted))
+    };
+    let value = |text: String| Span::styled(text, Style::default().fg(theme.text));
+
+    let mut lines = Vec::new();
+    for output in outputs {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            output.display_name.as_str(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+        let Some(device) = &output.device else {
+            lines.push(Line::styled(
+                "  No device details from the roon CLI",
+                Style::default().fg(theme.muted),
+            ));
+            continue;
+        };
+        if let Some(name) = &device.name {
+            lines.push(Line::from(vec![label("Device"), value(name.clone())]));
+        }
+        let route: Vec<String> = [
+            device.endpoint.clone(),
+            device.connection.as_deref().map(connection_label),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !route.is_empty() {
+            lines.push(Line::from(vec![label("Via"), value(route.join(" · "))]));
+        }
+        let format = device.format.as_ref().map(|format| format.label());
+        lines.push(Line::from(vec![
+            label("Fed"),
+            value(
+                format
+                    .filter(|f| !f.is_empty())
+                    .unwrap_or_else(|| "—".to_string()),
+            ),
+        ]));
+    }
+    if lines.is_empty() {
+        lines.push(Line::styled(
+            "This zone has no outputs",
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+/// How a device is connected, for display ("USB", "Network")
+fn connection_label(connection: &str) -> String {
+    match connection.to_ascii_lowercase().as_str() {
+        "usb" => "USB".to_string(),
+        "hdmi" => "HDMI".to_string(),
+        "network" => "Network".to_string(),
+        "internal" => "Built-in".to_string(),
+        _ => connection.to_string(),
+    }
+}
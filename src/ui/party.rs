@@ -0,0 +1,35 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+
+/// Draw the passphrase prompt that ends party mode
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Leave Party Mode ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let masked = "•".repeat(app.party.input.chars().count());
+    let feedback = match &app.party.message {
+        Some(message) => Line::styled(message.clone(), Style::default().fg(theme.error)),
+        None => Line::from(""),
+    };
+    let lines = vec![
+        Line::from(""),
+        Line::styled("Passphrase:", Style::default().fg(theme.text)),
+        Line::styled(format!("{}▏", masked), Style::default().fg(theme.accent)),
+        feedback,
+        Line::styled("Enter unlock  Esc cancel", Style::default().fg(theme.muted)),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
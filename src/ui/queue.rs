@@ -0,0 +1,194 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
+};
+
+use crate::app::App;
+
+use super::hints;
+
+/// Draw the queue view
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let state = &app.queue;
+    let hints = hints::text(app, hints::QUEUE);
+    let hints_height = u16::from(hints.is_some());
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),            // Header
+            Constraint::Min(0),               // List
+            Constraint::Length(1),            // Totals
+            Constraint::Length(hints_height), // Hints
+        ])
+        .split(area);
+
+    // Header
+    let header = Paragraph::new(format!("Up next ({} tracks)", state.items.len()))
+        .style(Style::default().fg(theme.heading));
+    frame.render_widget(header, chunks[0]);
+
+    if let Some(err) = &state.error {
+        let error = Paragraph::new(err.as_str())
+            .style(Style::default().fg(theme.error))
+            .alignment(Alignment::Center);
+        frame.render_widget(error, chunks[1]);
+        return;
+    }
+
+    if state.items.is_empty() {
+        let empty = Paragraph::new("Queue is empty")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        // Seconds from now until each item starts; the first is the current track
+        let (starts, remaining) = app.queue_timeline();
+        let rows: Vec<Row> = state
+            .items
+            .iter()
+            .zip(&starts)
+            .map(|(item, &start)| {
+                let starts_at = if start <= 0.0 {
+                    "now".to_string()
+                } else {
+                    format!("in {}", format_span(start))
+                };
+                Row::new(vec![
+                    Cell::from(Line::from(vec![
+                        Span::styled(&item.track, Style::default().fg(theme.text)),
+                        Span::raw("  "),
+                        Span::styled(item.artist.as_str(), Style::default().fg(theme.muted)),
+                    ])),
+                    Cell::from(Line::from(format_span(item.length)).right_aligned()),
+                    Cell::from(Line::from(starts_at).right_aligned())
+                        .style(Style::default().fg(theme.muted)),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(0),     // Track · artist
+                Constraint::Length(8),  // Duration
+                Constraint::Length(11), // Starts at
+            ],
+        )
+        .row_highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+        let mut table_state = TableState::default();
+        table_state.select(Some(state.selected_index));
+
+        frame.render_stateful_widget(table, chunks[1], &mut table_state);
+
+        let totals = Paragraph::new(format!("{} remaining", format_span(remaining)))
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Right);
+        frame.render_widget(totals, chunks[2]);
+    }
+
+    // Hints
+    if let Some(hints) = hints {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[3]);
+    }
+}
+
+/// Draw the details popup of the selected queue item
+pub fn draw_item(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Track details ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Details
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    let index = app.queue.selected_index;
+    let lines = match app.queue.items.get(index) {
+        // The queue moved on while the popup was open
+        None => vec![Line::from(""), Line::from("No longer in the queue")],
+        Some(item) => {
+            let (starts, _) = app.queue_timeline();
+            let starts_at = match starts.get(index) {
+                Some(&start) if start > 0.0 => format!("in {}", format_span(start)),
+                _ => "now".to_string(),
+            };
+            let unknown = || "—".to_string();
+            let field = |label: &str, value: String| {
+                Line::from(vec![
+                    Span::styled(format!("{:<9}", label), Style::default().fg(theme.muted)),
+                    Span::styled(value, Style::default().fg(theme.text)),
+                ])
+            };
+            vec![
+                Line::from(""),
+                Line::styled(
+                    item.track.clone(),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Line::styled(item.artist.clone(), Style::default().fg(theme.text)),
+                Line::from(""),
+                field(
+                    "Album",
+                    Some(item.album.clone())
+                        .filter(|a| !a.is_empty())
+                        .unwrap_or_else(unknown),
+                ),
+                field("Length", format_span(item.length)),
+                field("Starts", starts_at),
+                field("Source", item.source.clone().unwrap_or_else(unknown)),
+                field(
+                    "Quality",
+                    item.quality
+                        .as_ref()
+                        .map(|q| q.label())
+                        .filter(|label| !label.is_empty())
+                        .unwrap_or_else(unknown),
+                ),
+            ]
+        }
+    };
+    let details = Paragraph::new(lines)
+        .style(Style::default().fg(theme.muted))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(details, chunks[0].inner(Margin::new(1, 0)));
+
+    frame.render_widget(
+        Paragraph::new("Esc close")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center),
+        chunks[1],
+    );
+}
+
+/// Format a duration as "3:45" or "1:02:03"
+fn format_span(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let (hours, mins, secs) = (total / 3600, total / 60 % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, mins, secs)
+    } else {
+        format!("{}:{:02}", mins, secs)
+    }
+}
@@ -0,0 +1,36 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::{format_duration, App};
+
+/// Draw the "resume from where you left off?" popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Resume? ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let position = app.resume_offer.unwrap_or_default();
+    let lines = vec![
+        Line::from(""),
+        Line::styled(
+            format!("You left off at {}.", format_duration(position)),
+            Style::default().fg(theme.text),
+        ),
+        Line::from(""),
+        Line::styled(
+            "y resume  n from the start",
+            Style::default().fg(theme.muted),
+        ),
+    ];
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,65 @@
+use ratatui::{prelude::*, widgets::Paragraph};
+
+use crate::app::App;
+
+use super::now_playing;
+
+/// Draw the idle screensaver: cover and track text bouncing slowly around
+/// the screen so nothing stays lit in one place
+pub fn draw(frame: &mut Frame, area: Rect, app: &mut App) {
+    let theme = app.theme.clone();
+    let step = app.screensaver.as_ref().map(|s| s.step).unwrap_or(0);
+
+    // Cells are about twice as tall as wide, so a square cover is 2:1 in cells
+    let art_height = (area.height / 2).max(1);
+    let width = (art_height * 2).min(area.width);
+    let height = (art_height + 2).min(area.height);
+
+    let x = area.x + bounce(step, area.width - width);
+    let y = area.y + bounce(step, area.height - height);
+    let inner = Rect::new(x, y, width, height);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Album art
+            Constraint::Length(1), // Title
+            Constraint::Length(1), // Artist
+        ])
+        .split(inner);
+
+    now_playing::draw_album_art(frame, chunks[0], app);
+
+    let (title, artist, _) = app.track_info();
+    let lines = [
+        (
+            title,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        ),
+        (artist, Style::default().fg(theme.muted)),
+    ];
+    for ((text, style), chunk) in lines.into_iter().zip(&chunks[1..]) {
+        frame.render_widget(
+            Paragraph::new(text.to_string())
+                .style(style)
+                .alignment(Alignment::Center),
+            *chunk,
+        );
+    }
+}
+
+/// Position along a `0..=max` track that moves one cell per step and turns
+/// around at either end
+fn bounce(step: u64, max: u16) -> u16 {
+    if max == 0 {
+        return 0;
+    }
+    let period = 2 * u64::from(max);
+    let position = step % period;
+    let position = if position > u64::from(max) {
+        period - position
+    } else {
+        position
+    };
+    position as u16
+}
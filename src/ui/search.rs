@@ -4,10 +4,19 @@ use ratatui::{
 };
 
 use crate::app::SearchState;
+use crate::roon::NowPlaying;
+use crate::theme::Theme;
 use super::browse;
 
 /// Draw the search view
-pub fn draw(frame: &mut Frame, area: Rect, state: &SearchState) {
+pub fn draw(
+    frame: &mut Frame,
+    area: Rect,
+    state: &SearchState,
+    theme: &Theme,
+    playing: Option<&NowPlaying>,
+    hints: Option<&str>,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -21,24 +30,24 @@ pub fn draw(frame: &mut Frame, area: Rect, state: &SearchState) {
     let cursor = if state.input_active { "█" } else { "" };
     let input_text = format!("Search: {}{}", state.query, cursor);
     let input_style = if state.input_active {
-        Style::default().fg(Color::Cyan)
+        Style::default().fg(theme.accent)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     };
     let input = Paragraph::new(input_text).style(input_style);
     frame.render_widget(input, chunks[0]);
 
     // Results (reuse browse view drawing)
     if !state.results.items.is_empty() || state.results.breadcrumbs.len() > 1 {
-        browse::draw(frame, chunks[2], &state.results);
+        browse::draw(frame, chunks[2], &state.results, theme, playing, hints);
     } else if !state.query.is_empty() && !state.input_active {
         let empty = Paragraph::new("No results found")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(empty, chunks[2]);
     } else {
         let hint = Paragraph::new("Type a search query and press Enter")
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center);
         frame.render_widget(hint, chunks[2]);
     }
@@ -0,0 +1,71 @@
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+
+use super::hints;
+
+/// Draw the settings view (keybinding editor)
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Header
+            Constraint::Min(0),    // Bindings
+            Constraint::Length(1), // Message / hints
+        ])
+        .split(area);
+
+    let header = Paragraph::new("Keybindings").style(Style::default().fg(theme.heading));
+    frame.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = app
+        .keymap
+        .bindings()
+        .iter()
+        .enumerate()
+        .map(|(i, binding)| {
+            let keys = if app.settings.capturing && i == app.settings.selected_index {
+                "press a key…".to_string()
+            } else {
+                app.keymap.keys_display(&binding.action)
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:28}", binding.description),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(keys, Style::default().fg(theme.accent)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(app.settings.selected_index));
+
+    frame.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    // Last edit result, or hints
+    let footer = if app.settings.capturing {
+        Paragraph::new("Press the new key (Esc to cancel)")
+            .style(Style::default().fg(theme.heading))
+    } else if let Some(message) = &app.settings.message {
+        Paragraph::new(message.as_str()).style(Style::default().fg(theme.heading))
+    } else {
+        Paragraph::new(hints::text(app, hints::SETTINGS).unwrap_or_default())
+            .style(Style::default().fg(theme.muted))
+    };
+    frame.render_widget(footer.alignment(Alignment::Center), chunks[2]);
+}
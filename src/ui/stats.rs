@@ -0,0 +1,177 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::clock;
+use crate::history;
+
+/// Weeks listed in the popup
+const MAX_WEEKS: usize = 8;
+
+/// Heatmap cells from no listening to the year's busiest day
+const SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// Width of the weekday labels left of the heatmap
+const LABEL_WIDTH: usize = 4;
+
+/// Draw the listening heatmap and weekly summaries popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Listening stats ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(11), // Heatmap
+            Constraint::Min(0),     // Weeks
+            Constraint::Length(1),  // Hints
+        ])
+        .split(inner);
+
+    draw_heatmap(frame, chunks[0], app);
+
+    let weeks = app.history.weeks();
+    let mut lines = vec![];
+    if !app.config.stats.enabled {
+        lines.push(Line::styled(
+            "Listening stats are off (`enabled = false` under [stats]).",
+            Style::default().fg(theme.muted),
+        ));
+    } else if weeks.is_empty() {
+        lines.push(Line::styled(
+            "Nothing yet — listening time is counted while roon-tui runs.",
+            Style::default().fg(theme.muted),
+        ));
+    }
+    for week in weeks.iter().take(MAX_WEEKS) {
+        lines.push(Line::styled(
+            history::week_label(week.start),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+        lines.push(Line::styled(week.text(), Style::default().fg(theme.text)));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::styled(
+        "Stored on this machine only.",
+        Style::default().fg(theme.muted),
+    ));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: true }), chunks[1]);
+    frame.render_widget(
+        Paragraph::new("h/l year  Esc close")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center),
+        chunks[2],
+    );
+}
+
+/// Calendar of the selected year: a column per week, a row per weekday,
+/// shaded by listening time relative to the year's busiest day
+fn draw_heatmap(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let year = app.stats_year;
+    let first = clock::days_from_civil(year, 1, 1);
+    let last = clock::days_from_civil(year + 1, 1, 1) - 1;
+    let start = history::week_of(first);
+    let columns = ((last - start) / 7 + 1) as usize;
+
+    // Show the latest weeks when there's no room for the whole year
+    let room = (area.width as usize).saturating_sub(LABEL_WIDTH);
+    let skip = columns.saturating_sub(room) as i64;
+    let start = start + skip * 7;
+    let columns = columns - skip as usize;
+
+    let days: Vec<(i64, f64)> = (first..=last).map(|d| (d, app.history.day(d))).collect();
+    let busiest = days.iter().map(|(_, s)| *s).fold(0.0, f64::max);
+    let (total, active) = days
+        .iter()
+        .filter(|(_, s)| *s > 0.0)
+        .fold((0.0, 0), |(total, n), (_, s)| (total + s, n + 1));
+
+    let minutes = (total / 60.0) as u64;
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            format!("◂ {} ▸", year),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {}h {:02}m on {} day{}",
+                minutes / 60,
+                minutes % 60,
+                active,
+                if active == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(theme.muted),
+        ),
+    ])];
+
+    // Month names above the week their first day falls in
+    let mut months = " ".repeat(LABEL_WIDTH + columns);
+    for month in 1..=12 {
+        let column = (clock::days_from_civil(year, month, 1) - start).div_euclid(7);
+        let at = LABEL_WIDTH as i64 + column;
+        let name = clock::MONTHS[month as usize - 1];
+        if column >= 0 && at as usize + name.len() <= months.len() {
+            months.replace_range(at as usize..at as usize + name.len(), name);
+        }
+    }
+    lines.push(Line::styled(months, Style::default().fg(theme.muted)));
+
+    for (row, label) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+        let mut spans = vec![Span::styled(
+            format!("{:<width$}", label, width = LABEL_WIDTH),
+            Style::default().fg(theme.muted),
+        )];
+        for column in 0..columns {
+            let day = start + column as i64 * 7 + row as i64;
+            if !(first..=last).contains(&day) {
+                spans.push(Span::raw(" "));
+                continue;
+            }
+            let seconds = app.history.day(day);
+            let level = if seconds <= 0.0 || busiest <= 0.0 {
+                0
+            } else {
+                ((seconds / busiest * 4.0).ceil() as usize).clamp(1, 4)
+            };
+            let color = if level == 0 {
+                theme.muted
+            } else {
+                theme.accent
+            };
+            spans.push(Span::styled(SHADES[level], Style::default().fg(color)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let mut legend = vec![Span::styled(
+        format!("{:<width$}less ", "", width = LABEL_WIDTH),
+        Style::default().fg(theme.muted),
+    )];
+    for (level, shade) in SHADES.iter().enumerate() {
+        let color = if level == 0 {
+            theme.muted
+        } else {
+            theme.accent
+        };
+        legend.push(Span::styled(*shade, Style::default().fg(color)));
+    }
+    legend.push(Span::styled(" more", Style::default().fg(theme.muted)));
+    lines.push(Line::from(legend));
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
@@ -0,0 +1,67 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+
+use super::hints;
+
+/// Draw the theme switcher popup (the UI behind it previews the highlighted theme)
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Theme ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Themes
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    let items: Vec<ListItem> = app
+        .themes
+        .iter()
+        .map(|t| {
+            // Color swatch of the theme's main roles
+            let swatch: Vec<Span> = [t.accent, t.text, t.heading, t.success, t.error]
+                .into_iter()
+                .map(|color| Span::styled("██", Style::default().fg(color)))
+                .collect();
+            let mut spans = vec![Span::styled(
+                format!("{:20}", t.name),
+                Style::default().fg(theme.text),
+            )];
+            spans.extend(swatch);
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.theme_index));
+
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    if let Some(hints) = hints::text(app, hints::THEMES) {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[1]);
+    }
+}
@@ -0,0 +1,96 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+use crate::trims;
+
+use super::hints;
+
+/// Draw the trims popup: each grouped output's level relative to the
+/// group's reference output
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Group Trims ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Explanation
+            Constraint::Length(1), // Spacing
+            Constraint::Min(0),    // Outputs
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    frame.render_widget(
+        Paragraph::new("Saved per group, restored when it forms again")
+            .style(Style::default().fg(theme.heading)),
+        chunks[0],
+    );
+
+    let outputs = app
+        .current_zone()
+        .map(|z| z.outputs.as_slice())
+        .unwrap_or_default();
+    let reference = trims::reference(outputs);
+    let offsets = trims::offsets(outputs);
+
+    let items: Vec<ListItem> = outputs
+        .iter()
+        .zip(&offsets)
+        .enumerate()
+        .map(|(i, (output, offset))| {
+            let style = if i == app.trim_index {
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let trim = match offset {
+                Some(_) if reference == Some(i) => "reference".to_string(),
+                Some(offset) => trims::display(*offset),
+                None => "—".to_string(),
+            };
+            let level = output
+                .volume
+                .as_ref()
+                .map_or_else(|| "Fixed".to_string(), |vol| vol.level_display());
+
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:24}", output.display_name), style),
+                Span::styled(format!("{:12}", trim), style),
+                Span::styled(level, Style::default().fg(theme.muted)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(theme.surface)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▸ ");
+
+    let mut state = ListState::default();
+    state.select(Some(app.trim_index));
+
+    frame.render_stateful_widget(list, chunks[2], &mut state);
+
+    if let Some(hints) = hints::text(app, hints::TRIM) {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[3]);
+    }
+}
@@ -0,0 +1,98 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::App;
+
+use super::hints;
+
+/// Draw the per-output volume popup
+pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Volume ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1), // Link mode
+            Constraint::Length(1), // Spacing
+            Constraint::Min(0),    // Outputs
+            Constraint::Length(1), // Hints
+        ])
+        .split(inner);
+
+    let link_text = if app.volume_linked {
+        "Linked: all outputs change together"
+    } else {
+        "Unlinked: only the selected output changes"
+    };
+    frame.render_widget(
+        Paragraph::new(link_text).style(Style::default().fg(theme.heading)),
+        chunks[0],
+    );
+
+    let outputs = app
+        .current_zone()
+        .map(|z| z.outputs.as_slice())
+        .unwrap_or_default();
+
+    if outputs.is_empty() {
+        let empty = Paragraph::new("No outputs")
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(empty, chunks[2]);
+    } else {
+        let items: Vec<ListItem> = outputs
+            .iter()
+            .enumerate()
+            .map(|(i, output)| {
+                let style = if app.volume_linked || i == app.volume_output_index {
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+
+                let level = match &output.volume {
+                    Some(vol) if vol.is_muted => format!("{} Muted", app.icons.muted),
+                    Some(vol) => format!("{} {}", app.icons.volume, vol.level_display()),
+                    None => "Fixed".to_string(),
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:24}", output.display_name), style),
+                    Span::styled(level, Style::default().fg(theme.muted)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(theme.surface)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▸ ");
+
+        let mut state = ListState::default();
+        state.select(Some(app.volume_output_index));
+
+        frame.render_stateful_widget(list, chunks[2], &mut state);
+    }
+
+    if let Some(hints) = hints::text(app, hints::VOLUME) {
+        let hints = Paragraph::new(hints)
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(hints, chunks[3]);
+    }
+}
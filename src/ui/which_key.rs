@@ -0,0 +1,51 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::keymap::LeaderMenu;
+
+/// Draw the which-key hint box for an open leader menu (bottom right)
+pub fn draw(frame: &mut Frame, area: Rect, menu: LeaderMenu, app: &App) {
+    let theme = &app.theme;
+    let items = app.keymap.leader_items(menu, app.view);
+    let title = match menu {
+        LeaderMenu::Root => " Leader ".to_string(),
+        LeaderMenu::Section(section) => format!(" {} ", section),
+    };
+
+    let width = 36.min(area.width);
+    let height = (items.len() as u16 + 2).min(area.height);
+    // Keep clear of the status bar
+    let box_area = Rect {
+        x: area.x + area.width - width,
+        y: area.y + area.height.saturating_sub(height + 1),
+        width,
+        height,
+    };
+
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let lines: Vec<Line> = items
+        .into_iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:8}", keys),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(description, Style::default().fg(theme.text)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Clear, box_area);
+    frame.render_widget(Paragraph::new(lines).block(block), box_area);
+}
@@ -1,20 +1,24 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use crate::app::App;
 
+use super::hints;
+
 /// Draw the zone selector popup
 pub fn draw_selector(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let block = Block::default()
         .title(" Select Zone ")
         .borders(Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(theme.background));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
+    let hints = hints::text(app, hints::ZONES);
 
     if app.zones.is_empty() {
         let empty = Paragraph::new(vec![
@@ -23,7 +27,7 @@ pub fn draw_selector(frame: &mut Frame, area: Rect, app: &App) {
             Line::from("").centered(),
             Line::from("Check Roon Core connection").centered(),
         ])
-        .style(Style::default().fg(Color::DarkGray));
+        .style(Style::default().fg(theme.muted));
 
         frame.render_widget(empty, inner);
     } else {
@@ -32,23 +36,23 @@ pub fn draw_selector(frame: &mut Frame, area: Rect, app: &App) {
             .iter()
             .enumerate()
             .map(|(i, zone)| {
-                let is_current = i == app.selected_zone_index;
+                let is_current = Some(i) == app.selected_zone_position();
                 let prefix = if is_current { "● " } else { "○ " };
 
                 let status = if zone.is_playing() { "▶ " } else { "  " };
 
                 let style = if i == app.zone_selector_index {
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(theme.text)
                 };
 
                 let content = Line::from(vec![
                     Span::styled(prefix, style),
-                    Span::styled(status, Style::default().fg(Color::Green)),
-                    Span::styled(&zone.display_name, style),
+                    Span::styled(status, Style::default().fg(theme.success)),
+                    Span::styled(app.zone_label(zone), style),
                 ]);
 
                 ListItem::new(content)
@@ -58,7 +62,7 @@ pub fn draw_selector(frame: &mut Frame, area: Rect, app: &App) {
         let list = List::new(items)
             .highlight_style(
                 Style::default()
-                    .bg(Color::DarkGray)
+                    .bg(theme.surface)
                     .add_modifier(Modifier::BOLD),
             )
             .highlight_symbol("");
@@ -66,6 +70,64 @@ pub fn draw_selector(frame: &mut Frame, area: Rect, app: &App) {
         let mut state = ListState::default();
         state.select(Some(app.zone_selector_index));
 
-        frame.render_stateful_widget(list, inner, &mut state);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),                             // Zones
+                Constraint::Length(u16::from(hints.is_some())), // Hints
+            ])
+            .split(inner);
+        frame.render_stateful_widget(list, chunks[0], &mut state);
+
+        if let Some(hints) = hints {
+            let hints = Paragraph::new(hints)
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center);
+            frame.render_widget(hints, chunks[1]);
+        }
     }
 }
+
+/// Draw the prompt shown when the selected zone went away
+pub fn draw_gone(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .title(" Zone Gone ")
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().bg(theme.background));
+
+    let Some(gone) = &app.zone_gone else {
+        frame.render_widget(block, area);
+        return;
+    };
+    let successor = gone
+        .successor
+        .as_deref()
+        .and_then(|id| app.zones.iter().find(|z| z.zone_id == id));
+    let mut lines = vec![
+        Line::from(""),
+        Line::styled(
+            format!("{} is no longer there.", gone.name),
+            Style::default().fg(theme.text),
+        ),
+    ];
+    let keys = match successor {
+        Some(zone) => {
+            lines.push(Line::styled(
+                format!("Its outputs are now in {}.", app.zone_label(zone)),
+                Style::default().fg(theme.text),
+            ));
+            "Enter follow  z pick a zone  Esc close"
+        }
+        None => "z pick a zone  Esc close",
+    };
+    lines.push(Line::from(""));
+    lines.push(Line::styled(keys, Style::default().fg(theme.muted)));
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/EdgarPost/roon-tui/releases/latest";
+
+/// How long a fetched release tag is trusted before checking again
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Last check result, persisted so we hit the network at most once a day
+#[derive(Debug, Serialize, Deserialize)]
+struct CheckState {
+    /// Unix timestamp of the last check
+    checked_at: u64,
+    /// Latest release tag at that time (e.g. "v0.2.0"), None if the check
+    /// failed
+    latest: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+}
+
+/// Return the latest release tag if it's newer than the running version
+pub async fn check(client: &reqwest::Client) -> Option<String> {
+    let latest = match cached_latest() {
+        Some(latest) => latest?,
+        None => {
+            // A failure is saved too, so offline starts don't retry each time
+            let latest = fetch_latest(client)
+                .await
+                .map_err(|e| tracing::debug!("Update check failed: {}", e))
+                .ok();
+            save_latest(latest.clone());
+            latest?
+        }
+    };
+
+    is_newer(&latest, env!("CARGO_PKG_VERSION")).then_some(latest)
+}
+
+async fn fetch_latest(client: &reqwest::Client) -> Result<String> {
+    let body = client
+        .get(LATEST_RELEASE_URL)
+        .header(reqwest::header::USER_AGENT, "roon-tui")
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let release: Release = serde_json::from_str(&body)?;
+    Ok(release.tag_name)
+}
+
+fn state_path() -> Option<PathBuf> {
+    crate::paths::cache_dir().map(|dir| dir.join("update-check.json"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Result of the last check, if that was less than a day ago
+fn cached_latest() -> Option<Option<String>> {
+    let contents = std::fs::read_to_string(state_path()?).ok()?;
+    let state: CheckState = serde_json::from_str(&contents).ok()?;
+    let age = now().saturating_sub(state.checked_at);
+    (age < CHECK_INTERVAL.as_secs()).then_some(state.latest)
+}
+
+fn save_latest(latest: Option<String>) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    let state = CheckState {
+        checked_at: now(),
+        latest,
+    };
+    if let Err(e) = crate::paths::write_state(&path, &state) {
+        tracing::warn!("Failed to save update check: {}", e);
+    }
+}
+
+/// Compare dotted version numbers, ignoring a leading "v" and any
+/// pre-release suffix ("1.2.0-rc1" counts as 1.2.0)
+fn is_newer(latest: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parse(latest) > parse(current)
+}
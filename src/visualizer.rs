@@ -0,0 +1,95 @@
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+
+use anyhow::{Context, Result};
+use tokio::sync::mpsc;
+
+use crate::config::VisualizerConfig;
+
+/// Highest bar value cava reports (its `ascii_max_range`)
+pub const MAX_LEVEL: u8 = 100;
+
+/// A running cava process reading the configured capture source
+///
+/// cava does the audio capture and FFT; we only ask it for raw ASCII bar
+/// values on stdout and forward each frame. The process is killed on drop.
+pub struct Visualizer {
+    child: Child,
+}
+
+impl Visualizer {
+    /// Start cava and send every frame of bar levels (`0..=MAX_LEVEL`) to `tx`
+    pub fn spawn(
+        config: &VisualizerConfig,
+        framerate: u32,
+        tx: mpsc::Sender<Vec<u8>>,
+    ) -> Result<Self> {
+        let path = config_path().context("No cache directory")?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, cava_config(config, framerate))?;
+
+        let mut child = Command::new(&config.command)
+            .arg("-p")
+            .arg(&path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start {}", config.command))?;
+
+        let stdout = child.stdout.take().context("No stdout")?;
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                let bars = line
+                    .split(';')
+                    .filter_map(|value| value.trim().parse::<u8>().ok())
+                    .collect();
+                // Drop frames the UI hasn't caught up with
+                if let Err(mpsc::error::TrySendError::Closed(_)) = tx.try_send(bars) {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self { child })
+    }
+}
+
+impl Drop for Visualizer {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Generated cava config (`<cache dir>/roon-tui/cava.conf`)
+fn config_path() -> Option<PathBuf> {
+    crate::paths::cache_dir().map(|dir| dir.join("cava.conf"))
+}
+
+fn cava_config(config: &VisualizerConfig, framerate: u32) -> String {
+    format!(
+        "[general]\n\
+         bars = {bars}\n\
+         framerate = {framerate}\n\
+         \n\
+         [input]\n\
+         method = {method}\n\
+         source = {source}\n\
+         \n\
+         [output]\n\
+         method = raw\n\
+         raw_target = /dev/stdout\n\
+         data_format = ascii\n\
+         ascii_max_range = {MAX_LEVEL}\n\
+         bar_delimiter = 59\n\
+         frame_delimiter = 10\n",
+        bars = config.bars,
+        method = config.method,
+        source = config.source,
+    )
+}
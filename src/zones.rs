@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use regex_automata::meta::Regex;
+
+use crate::config::ZonesConfig;
+use crate::roon::Zone;
+
+/// A hidden-zone entry: an exact display name, or a `/regex/`
+enum Matcher {
+    Name(String),
+    Pattern(Regex),
+}
+
+/// Zone rules from the `[zones]` config section, compiled once at startup
+pub struct ZoneRules {
+    hidden: Vec<Matcher>,
+    aliases: BTreeMap<String, String>,
+    order: Vec<String>,
+}
+
+impl ZoneRules {
+    pub fn new(config: &ZonesConfig) -> Self {
+        let hidden = config
+            .hidden
+            .iter()
+            .filter_map(|entry| {
+                let pattern = entry
+                    .strip_prefix('/')
+                    .and_then(|rest| rest.strip_suffix('/'))
+                    .filter(|pattern| !pattern.is_empty());
+                match pattern {
+                    Some(pattern) => match Regex::new(pattern) {
+                        Ok(regex) => Some(Matcher::Pattern(regex)),
+                        Err(e) => {
+                            tracing::warn!("Invalid hidden zone pattern {:?}: {}", entry, e);
+                            None
+                        }
+                    },
+                    None => Some(Matcher::Name(entry.clone())),
+                }
+            })
+            .collect();
+        Self {
+            hidden,
+            aliases: config.aliases.clone(),
+            order: config.order.clone(),
+        }
+    }
+
+    /// Whether a zone is hidden from the selector and all zone lists
+    pub fn is_hidden(&self, display_name: &str) -> bool {
+        self.hidden.iter().any(|matcher| match matcher {
+            Matcher::Name(name) => name == display_name,
+            Matcher::Pattern(regex) => regex.is_match(display_name),
+        })
+    }
+
+    /// Name to show for a zone (its alias, or the Roon name)
+    pub fn label<'a>(&'a self, display_name: &'a str) -> &'a str {
+        self.aliases
+            .get(display_name)
+            .map(String::as_str)
+            .unwrap_or(display_name)
+    }
+
+    /// Put pinned zones first, in their configured order
+    pub fn sort(&self, zones: &mut [Zone]) {
+        zones.sort_by_key(|zone| {
+            self.order
+                .iter()
+                .position(|name| *name == zone.display_name)
+                .unwrap_or(usize::MAX)
+        });
+    }
+
+    /// Pin a new zone order (Roon names)
+    pub fn set_order(&mut self, order: Vec<String>) {
+        self.order = order;
+    }
+}